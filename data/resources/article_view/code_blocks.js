@@ -0,0 +1,35 @@
+(function () {
+    function copy(text) {
+        // a temporary text area, the article itself may not be selectable
+        var area = document.createElement("textarea");
+        area.value = text;
+        area.setAttribute("readonly", "");
+        area.style.position = "fixed";
+        area.style.opacity = "0";
+        document.body.appendChild(area);
+        area.select();
+        var copied = false;
+        try {
+            copied = document.execCommand("copy");
+        } catch (error) {
+            copied = false;
+        }
+        document.body.removeChild(area);
+        return copied;
+    }
+
+    document.querySelectorAll("button.nf-code-copy").forEach(function (button) {
+        var label = button.textContent;
+        button.addEventListener("click", function (event) {
+            event.preventDefault();
+            event.stopPropagation();
+            var pre = button.parentNode.querySelector("pre");
+            if (pre && copy(pre.textContent)) {
+                button.textContent = button.getAttribute("data-nf-copied");
+                setTimeout(function () {
+                    button.textContent = label;
+                }, 1500);
+            }
+        });
+    });
+})();
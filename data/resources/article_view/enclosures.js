@@ -0,0 +1,51 @@
+var NewsFlashEnclosures = (function () {
+    var rates = [0.75, 1, 1.25, 1.5, 1.75, 2];
+    var rate = $RATE;
+
+    function media() {
+        return document.querySelectorAll(".nf-enclosure-media");
+    }
+
+    function setRate(newRate) {
+        rate = newRate;
+        media().forEach(function (element) {
+            element.playbackRate = rate;
+        });
+        document.querySelectorAll(".nf-enclosure-speed").forEach(function (button) {
+            button.textContent = rate + "×";
+        });
+    }
+
+    media().forEach(function (element) {
+        var position = parseFloat(element.getAttribute("data-nf-position"));
+        element.addEventListener("loadedmetadata", function () {
+            element.playbackRate = rate;
+            if (position > 0 && position < element.duration) {
+                element.currentTime = position;
+            }
+            position = 0;
+        });
+    });
+
+    document.querySelectorAll(".nf-enclosure-speed").forEach(function (button) {
+        button.addEventListener("click", function () {
+            var index = rates.indexOf(rate);
+            setRate(rates[(index + 1) % rates.length]);
+        });
+    });
+
+    setRate(rate);
+
+    return {
+        // playback rate and the positions of all enclosures that were started
+        state: function () {
+            var positions = [];
+            media().forEach(function (element) {
+                if (element.readyState > 0) {
+                    positions.push([element.getAttribute("data-nf-url"), element.ended ? 0 : element.currentTime]);
+                }
+            });
+            return JSON.stringify({ rate: rate, positions: positions });
+        }
+    };
+})();
@@ -0,0 +1,110 @@
+var NewsFlashHighlights = (function () {
+    var highlights = $HIGHLIGHTS;
+    var PREFIX_LENGTH = 32;
+    var root = document.querySelector("div.nfcontent");
+
+    function textNodes() {
+        var walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT, null);
+        var nodes = [];
+        while (walker.nextNode()) {
+            nodes.push(walker.currentNode);
+        }
+        return nodes;
+    }
+
+    function wrap(highlight) {
+        var nodes = textNodes();
+        var text = nodes.map(function (node) {
+            return node.nodeValue;
+        }).join("");
+
+        // the prefix picks the right one of several occurrences, fall back to the first if the article changed
+        var start = text.indexOf(highlight.prefix + highlight.text);
+        if (start >= 0) {
+            start += highlight.prefix.length;
+        } else {
+            start = text.indexOf(highlight.text);
+        }
+        if (start < 0) {
+            return;
+        }
+        var end = start + highlight.text.length;
+
+        var offset = 0;
+        nodes.forEach(function (node) {
+            var length = node.nodeValue.length;
+            var from = Math.max(start, offset) - offset;
+            var to = Math.min(end, offset + length) - offset;
+            offset += length;
+            // whitespace between blocks, a mark in there would break lists and tables
+            if (from >= to || node.nodeValue.slice(from, to).trim() === "") {
+                return;
+            }
+
+            var target = node;
+            if (from > 0) {
+                target = target.splitText(from);
+            }
+            if (to - from < target.nodeValue.length) {
+                target.splitText(to - from);
+            }
+            var mark = document.createElement("mark");
+            mark.className = "nf-highlight";
+            if (highlight.note) {
+                mark.title = highlight.note;
+            }
+            target.parentNode.replaceChild(mark, target);
+            mark.appendChild(target);
+        });
+    }
+
+    function clear() {
+        document.querySelectorAll("mark.nf-highlight").forEach(function (mark) {
+            var parent = mark.parentNode;
+            while (mark.firstChild) {
+                parent.insertBefore(mark.firstChild, mark);
+            }
+            parent.removeChild(mark);
+            parent.normalize();
+        });
+    }
+
+    function apply() {
+        if (root) {
+            highlights.forEach(wrap);
+        }
+    }
+
+    apply();
+
+    return {
+        set: function (list) {
+            highlights = list;
+            clear();
+            apply();
+        },
+        selection: function () {
+            var selection = window.getSelection();
+            if (!root || selection.rangeCount === 0 || selection.isCollapsed) {
+                return "null";
+            }
+            var range = selection.getRangeAt(0);
+            if (!root.contains(range.commonAncestorContainer)) {
+                return "null";
+            }
+
+            var before = document.createRange();
+            before.setStart(root, 0);
+            before.setEnd(range.startContainer, range.startOffset);
+            var prefix = before.toString();
+            var text = range.toString();
+            var leading = text.length - text.replace(/^\s+/, "").length;
+            prefix += text.slice(0, leading);
+            text = text.trim();
+            if (text === "") {
+                return "null";
+            }
+            return JSON.stringify({ text: text, prefix: prefix.slice(-PREFIX_LENGTH) });
+        }
+    };
+})();
@@ -0,0 +1,20 @@
+var NewsFlashImages = (function () {
+    function load(placeholder) {
+        placeholder.outerHTML = placeholder.getAttribute("data-nf-image");
+    }
+
+    document.querySelectorAll(".nf-image-placeholder").forEach(function (placeholder) {
+        placeholder.addEventListener("click", function (event) {
+            // placeholders inside links must not open the browser
+            event.preventDefault();
+            event.stopPropagation();
+            load(placeholder);
+        });
+    });
+
+    return {
+        loadAll: function () {
+            document.querySelectorAll(".nf-image-placeholder").forEach(load);
+        }
+    };
+})();
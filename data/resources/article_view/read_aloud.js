@@ -0,0 +1,46 @@
+var NewsFlashReadAloud = (function () {
+    var selector = "p, li, h1, h2, h3, h4, h5, h6, blockquote, pre, figcaption, dt, dd, td";
+    var elements = null;
+
+    function collect() {
+        var candidates = [];
+        var title = document.querySelector("header h1");
+        if (title) {
+            candidates.push(title);
+        }
+        document.querySelectorAll("div.nfcontent").forEach(function (content) {
+            content.querySelectorAll(selector).forEach(function (element) {
+                // the innermost blocks are read, a list item wrapping paragraphs would read them twice
+                if (!element.querySelector(selector) && element.textContent.trim().length > 0) {
+                    candidates.push(element);
+                }
+            });
+        });
+        return candidates;
+    }
+
+    function get() {
+        if (elements === null) {
+            elements = collect();
+        }
+        return elements;
+    }
+
+    return {
+        paragraphs: function () {
+            return JSON.stringify(get().map(function (element) {
+                return element.textContent.replace(/\s+/g, " ").trim();
+            }));
+        },
+        // a negative or past the end index clears the highlight
+        highlight: function (index) {
+            get().forEach(function (element, i) {
+                element.classList.toggle("nf-speaking", i === index);
+            });
+            var element = get()[index];
+            if (element) {
+                element.scrollIntoView({ behavior: "smooth", block: "center" });
+            }
+        }
+    };
+})();
@@ -0,0 +1,27 @@
+(function () {
+    // prefer the page's own markup, otherwise the element holding most of the paragraph text
+    var content = document.querySelector("article") || document.querySelector("main");
+    if (!content) {
+        var scores = new Map();
+        document.querySelectorAll("p").forEach(function (paragraph) {
+            var length = paragraph.textContent.trim().length;
+            if (length < 25 || !paragraph.parentElement) {
+                return;
+            }
+            scores.set(paragraph.parentElement, (scores.get(paragraph.parentElement) || 0) + length);
+        });
+        var best = 0;
+        scores.forEach(function (score, element) {
+            if (score > best) {
+                best = score;
+                content = element;
+            }
+        });
+    }
+
+    var title = document.querySelector("meta[property='og:title']");
+    return JSON.stringify({
+        title: title ? title.content : document.title,
+        content: content ? content.innerHTML : ""
+    });
+})()
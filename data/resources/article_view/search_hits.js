@@ -0,0 +1,86 @@
+var NewsFlashSearch = (function () {
+    var terms = $TERMS;
+    var hits = [];
+    var current = -1;
+
+    function escapeRegExp(text) {
+        return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+    }
+
+    function highlight(root, expression) {
+        var walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT, {
+            acceptNode: function (node) {
+                var parent = node.parentNode;
+                if (
+                    parent.nodeName === "SCRIPT" ||
+                    parent.nodeName === "STYLE" ||
+                    parent.classList.contains("search-hit")
+                ) {
+                    return NodeFilter.FILTER_REJECT;
+                }
+                return NodeFilter.FILTER_ACCEPT;
+            }
+        });
+
+        var nodes = [];
+        while (walker.nextNode()) {
+            nodes.push(walker.currentNode);
+        }
+
+        nodes.forEach(function (node) {
+            var text = node.nodeValue;
+            expression.lastIndex = 0;
+            if (!expression.test(text)) {
+                return;
+            }
+
+            var fragment = document.createDocumentFragment();
+            var last = 0;
+            expression.lastIndex = 0;
+            text.replace(expression, function (match, offset) {
+                fragment.appendChild(document.createTextNode(text.slice(last, offset)));
+                var mark = document.createElement("mark");
+                mark.className = "search-hit";
+                mark.textContent = match;
+                fragment.appendChild(mark);
+                hits.push(mark);
+                last = offset + match.length;
+                return match;
+            });
+            fragment.appendChild(document.createTextNode(text.slice(last)));
+            node.parentNode.replaceChild(fragment, node);
+        });
+    }
+
+    function select(index) {
+        if (hits.length === 0) {
+            return 0;
+        }
+        if (current >= 0) {
+            hits[current].classList.remove("current");
+        }
+        current = (index + hits.length) % hits.length;
+        hits[current].classList.add("current");
+        hits[current].scrollIntoView({ block: "center" });
+        return current + 1;
+    }
+
+    if (terms.length > 0) {
+        var expression = new RegExp(terms.map(escapeRegExp).join("|"), "gi");
+        document.querySelectorAll("header.post h1, div.nfcontent").forEach(function (root) {
+            highlight(root, expression);
+        });
+    }
+
+    return {
+        count: function () {
+            return hits.length;
+        },
+        next: function () {
+            return select(current + 1);
+        },
+        prev: function () {
+            return select(current < 0 ? -1 : current - 1);
+        }
+    };
+})();
@@ -1,4 +1,5 @@
 use open;
+use std::collections::HashSet;
 use std::env;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -13,13 +14,13 @@ use gio::{prelude::ApplicationExtManual, ApplicationExt, Notification, Notificat
 use glib::{clone, object::Cast, source::Continue, translate::ToGlib, Receiver, Sender};
 use gtk::{
     prelude::GtkWindowExtManual, Application, ButtonExt, DialogExt, EntryExt, FileChooserAction, FileChooserDialog,
-    FileChooserExt, FileFilter, GtkApplicationExt, GtkWindowExt, ResponseType, Widget, WidgetExt,
+    FileChooserExt, FileFilter, GtkApplicationExt, GtkWindowExt, ResponseType, SpinButtonExt, Widget, WidgetExt,
 };
 use lazy_static::lazy_static;
 use log::{error, info, warn};
 use news_flash::models::{
-    ArticleID, Category, CategoryID, FatArticle, FavIcon, Feed, FeedID, LoginData, PasswordLogin, PluginCapabilities,
-    PluginID, TagID, Url,
+    ArticleFilter, ArticleID, ArticleOrder, Category, CategoryID, FatArticle, FavIcon, Feed, FeedID, LoginData, Marked,
+    PasswordLogin, PluginCapabilities, PluginID, Read, TagID, Url,
 };
 use news_flash::{NewsFlash, NewsFlashError};
 use parking_lot::RwLock;
@@ -28,18 +29,23 @@ use tokio::runtime::Runtime;
 
 use crate::about_dialog::NewsFlashAbout;
 use crate::add_dialog::{AddCategory, AddPopover};
-use crate::article_list::{MarkUpdate, ReadUpdate};
+use crate::article_list::{ArticleListArticleModel, ArticleListGrouping, ArticleListSort, ReadUpdate};
 use crate::article_view::ArticleView;
+use crate::catch_up_dialog::CatchUpDialog;
 use crate::config::APP_ID;
-use crate::content_page::HeaderSelection;
+use crate::content_page::{ContentPage, HeaderSelection};
 use crate::discover::DiscoverDialog;
+use crate::highlights_dialog::HighlightsDialog;
 use crate::main_window::MainWindow;
 use crate::rename_dialog::RenameDialog;
-use crate::settings::{NewsFlashShortcutWindow, ProxyProtocoll, Settings, SettingsDialog};
+use crate::settings::{Highlight, NewsFlashShortcutWindow, ProxyProtocoll, Settings, SettingsDialog};
 use crate::sidebar::{models::SidebarSelection, FeedListDndAction};
 use crate::undo_bar::UndoActionModel;
 use crate::util::{FileUtil, GtkUtil, Util, CHANNEL_ERROR, RUNTIME_ERROR};
 
+/// How many of the newest unread articles per feed get their full content fetched after a sync.
+const SCRAPE_AFTER_SYNC_LIMIT: i64 = 20;
+
 lazy_static! {
     pub static ref CONFIG_DIR: PathBuf = glib::get_user_config_dir()
         .expect("Failed to find the config dir")
@@ -61,6 +67,7 @@ pub enum Action {
     ErrorSimpleMessage(String),
     Error(String, NewsFlashError),
     UndoableAction(UndoActionModel),
+    Undo,
     LoadFavIcon((Feed, OneShotSender<Option<FavIcon>>)),
     ShowWelcomePage,
     ShowContentPage(Option<PluginID>),
@@ -79,19 +86,54 @@ pub enum Action {
     Sync,
     InitSync,
     MarkArticleRead(ReadUpdate),
-    MarkArticle(MarkUpdate),
+    SetArticlesRead(Vec<ArticleID>, Read),
+    SetArticlesMarked(Vec<ArticleID>, Marked),
+    MarkArticlesRead(Vec<ArticleID>),
+    MarkArticlesAboveRead(ArticleID),
+    MarkArticlesBelowRead(ArticleID),
+    SetListArticlesRead(Vec<ArticleID>, Read),
+    ShowCatchUpDialog,
+    CatchUp(i64),
+    SetSelectedArticlesRead(Read),
+    SetSelectedArticlesMarked(Marked),
+    TagSelectedArticles(TagID),
+    UntagSelectedArticles(TagID),
+    UpdateSelectionTags,
+    ExportSelectedArticles,
+    OpenSelectedArticlesInBrowser,
     ToggleArticleRead,
     ToggleArticleMarked,
     UpdateSidebar,
     UpdateArticleList,
     LoadMoreArticles,
     SidebarSelection(SidebarSelection),
+    SetArticleListSort(ArticleListSort),
+    SetArticleListGrouping(ArticleListGrouping),
     SelectNextArticle,
     SelectPrevArticle,
     HeaderSelection(HeaderSelection),
     UpdateArticleHeader,
     ShowArticle(ArticleID),
+    OpenArticleInTab(ArticleID),
+    OpenArticleInWindow(ArticleID),
     RedrawArticle,
+    LoadArticleImages,
+    SetArticleFeedImages(Option<bool>),
+    SetArticleFeedLinks(Option<bool>),
+    SetArticleFeedFullContent(bool),
+    OpenLinkInternally(String),
+    DownloadEnclosure(String),
+    RemoveEnclosureDownload(String),
+    ReadAloud,
+    ReadAloudSkip,
+    ReadAloudState(bool),
+    ReadAloudFinished(ArticleID),
+    ReadAloudContinue(ArticleID, ArticleID),
+    HighlightSelection(bool),
+    ShowHighlightNoteDialog(ArticleID, String, String),
+    AddHighlight(ArticleID, String, String, Option<String>),
+    UpdateHighlights,
+    ShowHighlightsDialog,
     CloseArticle,
     SearchTerm(String),
     SetSidebarRead,
@@ -103,6 +145,13 @@ pub enum Action {
     RenameFeed((Feed, String)),
     RenameCategoryDialog(CategoryID),
     RenameCategory((Category, String)),
+    SaveSearchDialog,
+    SaveSearch(String),
+    RenameSavedSearchDialog(u32),
+    RenameSavedSearch(u32, String),
+    MoveSavedSearch(u32, i32),
+    DeleteSavedSearch(u32),
+    RemoveSavedSearch(u32),
     DeleteSidebarSelection,
     DeleteFeed(FeedID),
     DeleteCategory(CategoryID),
@@ -112,7 +161,10 @@ pub enum Action {
     DragAndDrop(FeedListDndAction),
     ExportArticle,
     StartGrabArticleContent,
-    FinishGrabArticleContent(Option<FatArticle>),
+    StartGrabTabArticleContent(ArticleID),
+    FinishGrabArticleContent(ArticleID, Option<FatArticle>),
+    ArticleScraped(FatArticle),
+    ScrapeAfterSync,
     ImportOpml,
     ExportOpml,
     QueueQuit,
@@ -220,6 +272,7 @@ impl App {
             // Workaround SINGLE selection mode of listbox
             content_page.sidebar.read().feed_list.read().on_window_show();
             content_page.sidebar.read().tag_list.read().on_window_show();
+            content_page.sidebar.read().saved_search_list.read().on_window_show();
         }));
     }
 
@@ -237,6 +290,7 @@ impl App {
             Action::ErrorSimpleMessage(msg) => self.window.show_error_simple_message(&msg),
             Action::Error(msg, error) => self.window.show_error(&msg, error),
             Action::UndoableAction(action) => self.window.show_undo_bar(action),
+            Action::Undo => self.undo(),
             Action::LoadFavIcon((feed, sender)) => self.load_favicon(feed, sender),
             Action::ShowWelcomePage => self.window.show_welcome_page(),
             Action::ShowContentPage(plugin_id) => {
@@ -257,8 +311,47 @@ impl App {
             Action::ScheduleSync => self.schedule_sync(),
             Action::Sync => self.sync(),
             Action::InitSync => self.init_sync(),
-            Action::MarkArticleRead(update) => self.mark_article_read(update),
-            Action::MarkArticle(update) => self.mark_article(update),
+            Action::MarkArticleRead(update) => self.set_articles_read(vec![update.article_id], update.read),
+            Action::SetArticlesRead(article_ids, read) => self.set_articles_read_undoable(article_ids, read),
+            Action::SetArticlesMarked(article_ids, marked) => self.set_articles_marked_undoable(article_ids, marked),
+            Action::MarkArticlesRead(article_ids) => self.set_articles_read(article_ids, Read::Read),
+            Action::MarkArticlesAboveRead(article_id) => self.window.content_page.mark_articles_beyond_read(
+                article_id,
+                true,
+                &self.news_flash,
+                &self.window.undo_bar,
+                self.threadpool.clone(),
+            ),
+            Action::MarkArticlesBelowRead(article_id) => self.window.content_page.mark_articles_beyond_read(
+                article_id,
+                false,
+                &self.news_flash,
+                &self.window.undo_bar,
+                self.threadpool.clone(),
+            ),
+            Action::SetListArticlesRead(article_ids, read) => self.set_list_articles_read(article_ids, read),
+            Action::ShowCatchUpDialog => self.catch_up_dialog(),
+            Action::CatchUp(days) => {
+                self.window
+                    .catch_up(days, &self.news_flash, self.threadpool.clone(), self.settings.clone())
+            }
+            Action::SetSelectedArticlesRead(read) => {
+                let article_ids = self.selected_article_ids(|article| article.read != read);
+                self.set_list_articles_read(article_ids, read);
+            }
+            Action::SetSelectedArticlesMarked(marked) => {
+                let article_ids = self.selected_article_ids(|article| article.marked != marked);
+                self.set_list_articles_marked(article_ids, marked);
+            }
+            Action::TagSelectedArticles(tag_id) => {
+                self.tag_articles(self.selected_article_ids(|_article| true), tag_id, true)
+            }
+            Action::UntagSelectedArticles(tag_id) => {
+                self.untag_articles(self.selected_article_ids(|_article| true), tag_id, true)
+            }
+            Action::UpdateSelectionTags => self.update_selection_tags(),
+            Action::ExportSelectedArticles => self.export_selected_articles(),
+            Action::OpenSelectedArticlesInBrowser => self.open_selected_articles_in_browser(),
             Action::ToggleArticleRead => self.toggle_article_read(),
             Action::ToggleArticleMarked => self.toggle_article_marked(),
             Action::UpdateSidebar => {
@@ -271,13 +364,57 @@ impl App {
             Action::LoadMoreArticles => self
                 .window
                 .load_more_articles(&self.news_flash, self.threadpool.clone()),
-            Action::SidebarSelection(selection) => self.window.sidebar_selection(selection),
+            Action::SidebarSelection(selection) => {
+                let view_settings = self.settings.read().get_article_list_view(&selection);
+                self.window.content_header.show_article_list_view(&view_settings);
+                self.window.sidebar_selection(selection);
+            }
+            Action::SetArticleListSort(sort) => self.set_article_list_sort(sort),
+            Action::SetArticleListGrouping(grouping) => self.set_article_list_grouping(grouping),
             Action::SelectNextArticle => self.window.content_page.article_list.read().select_next_article(),
             Action::SelectPrevArticle => self.window.content_page.article_list.read().select_prev_article(),
             Action::HeaderSelection(selection) => self.window.set_headerbar_selection(selection),
             Action::UpdateArticleHeader => self.window.update_article_header(&self.news_flash, &self.features),
-            Action::ShowArticle(article_id) => self.window.show_article(article_id, &self.news_flash, &self.features),
-            Action::RedrawArticle => self.window.content_page.article_view.redraw_article(),
+            Action::ShowArticle(article_id) => self.show_article(article_id),
+            Action::OpenArticleInTab(article_id) => self.window.open_article_in_tab(&article_id, &self.news_flash),
+            Action::OpenArticleInWindow(article_id) => {
+                self.window.open_article_in_window(&article_id, &self.news_flash)
+            }
+            Action::RedrawArticle => {
+                self.window.content_page.article_view.redraw_article();
+                self.window
+                    .content_header
+                    .set_images_blocked(self.window.content_page.article_view.images_blocked());
+            }
+            Action::LoadArticleImages => self.window.content_page.article_view.active().load_images(),
+            Action::SetArticleFeedImages(load_images) => self.set_article_feed_images(load_images),
+            Action::SetArticleFeedLinks(internal) => self.set_article_feed_links(internal),
+            Action::SetArticleFeedFullContent(full_content) => self.set_article_feed_full_content(full_content),
+            Action::OpenLinkInternally(uri) => self.window.content_page.article_view.active().open_link(&uri),
+            Action::DownloadEnclosure(url) => self.window.content_page.article_view.active().download_enclosure(&url),
+            Action::RemoveEnclosureDownload(url) => self
+                .window
+                .content_page
+                .article_view
+                .active()
+                .remove_enclosure_download(&url),
+            Action::ReadAloud => self.window.content_page.article_view.active().read_aloud_toggle(),
+            Action::ReadAloudSkip => self.window.content_page.article_view.active().read_aloud_skip(),
+            Action::ReadAloudState(playing) => self.window.content_header.set_read_aloud_playing(playing),
+            Action::ReadAloudFinished(article_id) => self.read_aloud_finished(article_id),
+            Action::ReadAloudContinue(finished, next) => self.read_aloud_continue(finished, next),
+            Action::HighlightSelection(with_note) => self
+                .window
+                .content_page
+                .article_view
+                .active()
+                .highlight_selection(with_note),
+            Action::ShowHighlightNoteDialog(article_id, text, prefix) => {
+                self.highlight_note_dialog(article_id, text, prefix)
+            }
+            Action::AddHighlight(article_id, text, prefix, note) => self.add_highlight(&article_id, text, prefix, note),
+            Action::UpdateHighlights => self.window.content_page.article_view.update_highlights(),
+            Action::ShowHighlightsDialog => self.highlights_dialog(),
             Action::CloseArticle => {
                 self.window.content_page.article_view.close_article();
                 self.window
@@ -294,19 +431,36 @@ impl App {
             Action::AddCategory(title) => self.add_category(title),
             Action::AddTag(color, title) => self.add_tag(color, title),
             Action::RenameFeedDialog(feed_id, category_id) => self.rename_feed_dialog(feed_id, category_id),
-            Action::RenameFeed((feed, new_title)) => self.rename_feed(feed, new_title),
+            Action::RenameFeed((feed, new_title)) => self.rename_feed(feed, new_title, true),
             Action::RenameCategoryDialog(category_id) => self.rename_category_dialog(category_id),
-            Action::RenameCategory((category, new_title)) => self.rename_category(category, new_title),
+            Action::RenameCategory((category, new_title)) => self.rename_category(category, new_title, true),
+            Action::SaveSearchDialog => self.save_search_dialog(),
+            Action::SaveSearch(label) => self.save_search(label),
+            Action::RenameSavedSearchDialog(id) => self.rename_saved_search_dialog(id),
+            Action::RenameSavedSearch(id, label) => self.rename_saved_search(id, label),
+            Action::MoveSavedSearch(id, offset) => self.move_saved_search(id, offset),
+            Action::DeleteSavedSearch(id) => {
+                if let Some(saved_search) = self.settings.read().get_saved_search(id) {
+                    let undo_action = UndoActionModel::DeleteSavedSearch(id, saved_search.label);
+                    Util::send(&self.sender, Action::UndoableAction(undo_action));
+                }
+            }
+            Action::RemoveSavedSearch(id) => self.delete_saved_search(id),
             Action::DeleteSidebarSelection => self.delete_selection(),
             Action::DeleteFeed(feed_id) => self.delete_feed(feed_id),
             Action::DeleteCategory(category_id) => self.delete_category(category_id),
             Action::DeleteTag(tag_id) => self.delete_tag(tag_id),
-            Action::TagArticle(article_id, tag_id) => self.tag_article(article_id, tag_id),
-            Action::UntagArticle(article_id, tag_id) => self.untag_article(article_id, tag_id),
-            Action::DragAndDrop(action) => self.drag_and_drop(action),
+            Action::TagArticle(article_id, tag_id) => self.tag_articles(vec![article_id], tag_id, true),
+            Action::UntagArticle(article_id, tag_id) => self.untag_articles(vec![article_id], tag_id, true),
+            Action::DragAndDrop(action) => self.drag_and_drop(action, true),
             Action::ExportArticle => self.export_article(),
             Action::StartGrabArticleContent => self.start_grab_article_content(),
-            Action::FinishGrabArticleContent(article) => self.finish_grab_article_content(article),
+            Action::StartGrabTabArticleContent(article_id) => self.grab_article_content(article_id, true),
+            Action::FinishGrabArticleContent(article_id, article) => {
+                self.finish_grab_article_content(article_id, article)
+            }
+            Action::ArticleScraped(article) => self.article_scraped(article),
+            Action::ScrapeAfterSync => self.scrape_after_sync(),
             Action::ImportOpml => self.import_opml(),
             Action::ExportOpml => self.export_opml(),
             Action::QueueQuit => self.queue_quit(),
@@ -518,6 +672,7 @@ impl App {
 
         let glib_future = receiver.map(clone!(
             @strong self.news_flash as news_flash,
+            @strong self.settings as settings,
             @weak self.window.content_header as content_header,
             @strong self.sender as sender => @default-panic, move |res|
         {
@@ -529,6 +684,8 @@ impl App {
                 match res {
                     Ok(Ok(new_article_count)) => {
                         content_header.finish_sync();
+                        ContentPage::enclosure_articles_outdated();
+                        Self::prune_reading_positions(news_flash, &settings);
                         Util::send(&sender, Action::UpdateSidebar);
                         Util::send(&sender, Action::UpdateArticleList);
                         let counts = NotificationCounts {
@@ -536,6 +693,7 @@ impl App {
                             unread: unread_count,
                         };
                         Util::send(&sender, Action::ShowNotification(counts));
+                        Util::send(&sender, Action::ScrapeAfterSync);
                     }
                     Ok(Err(error)) => {
                         content_header.finish_sync();
@@ -550,6 +708,39 @@ impl App {
         Util::glib_spawn_future(glib_future);
     }
 
+    /// Forget the reading positions of articles that were removed by the sync and save the rest.
+    fn prune_reading_positions(news_flash: &NewsFlash, settings: &Arc<RwLock<Settings>>) {
+        let article_ids = settings.read().get_article_view_reading_position_ids();
+        if !article_ids.is_empty() {
+            let filter = ArticleFilter {
+                limit: None,
+                offset: None,
+                order: None,
+                unread: None,
+                marked: None,
+                feed: None,
+                feed_blacklist: None,
+                category: None,
+                category_blacklist: None,
+                tag: None,
+                ids: Some(article_ids),
+                newer_than: None,
+                older_than: None,
+                search_term: None,
+            };
+            match news_flash.get_articles(filter) {
+                Ok(articles) => {
+                    let keep: Vec<ArticleID> = articles.into_iter().map(|article| article.article_id).collect();
+                    settings.write().prune_article_view_reading_positions(&keep);
+                }
+                Err(_) => warn!("Failed to look up articles with a reading position"),
+            }
+        }
+        if settings.write().write_reading_positions().is_err() {
+            warn!("Failed to save reading positions");
+        }
+    }
+
     fn init_sync(&self) {
         let (sender, receiver) = oneshot::channel::<Result<i64, NewsFlashError>>();
         self.window.content_header.start_sync();
@@ -578,6 +769,7 @@ impl App {
                 match res {
                     Ok(Ok(new_article_count)) => {
                         content_header.finish_sync();
+                        ContentPage::enclosure_articles_outdated();
                         Util::send(&sender, Action::UpdateSidebar);
                         Util::send(&sender, Action::UpdateArticleList);
                         let counts = NotificationCounts {
@@ -585,6 +777,7 @@ impl App {
                             unread: unread_count,
                         };
                         Util::send(&sender, Action::ShowNotification(counts));
+                        Util::send(&sender, Action::ScrapeAfterSync);
                     }
                     Ok(Err(error)) => {
                         content_header.finish_sync();
@@ -626,13 +819,17 @@ impl App {
         self.icon_threadpool.spawn_ok(thread_future);
     }
 
-    fn mark_article_read(&self, update: ReadUpdate) {
+    fn set_articles_read(&self, article_ids: Vec<ArticleID>, read: Read) {
+        if article_ids.is_empty() {
+            return;
+        }
+
         let (sender, receiver) = oneshot::channel::<Result<(), NewsFlashError>>();
 
         let news_flash = self.news_flash.clone();
         let settings = self.settings.clone();
-        let article_id_vec = vec![update.article_id.clone()];
-        let read_status = update.read;
+        let article_id_vec = article_ids.clone();
+        let read_status = read;
         let global_sender = self.sender.clone();
         let thread_future = async move {
             if let Some(news_flash) = news_flash.read().as_ref() {
@@ -664,7 +861,7 @@ impl App {
             match res {
                 Ok(Ok(())) => {}
                 Ok(Err(error)) => {
-                    let message = format!("Failed to mark article read: '{}'", update.article_id);
+                    let message = format!("Failed to mark {} article(s) read", article_ids.len());
                     error!("{}", message);
                     Util::send(&global_sender, Action::Error(message, error));
                     Util::send(&global_sender, Action::UpdateArticleList);
@@ -680,8 +877,8 @@ impl App {
             Util::send(&global_sender, Action::UpdateSidebar);
             let visible_article = content_page.article_view.get_visible_article();
             if let Some(mut visible_article) = visible_article {
-                if visible_article.article_id == update.article_id {
-                    visible_article.unread = update.read;
+                if article_ids.contains(&visible_article.article_id) {
+                    visible_article.unread = read;
                     content_header.show_article(Some(&visible_article), &news_flash, &features);
                     content_page
                         .article_view
@@ -694,12 +891,16 @@ impl App {
         Util::glib_spawn_future(glib_future);
     }
 
-    fn mark_article(&self, update: MarkUpdate) {
+    fn set_articles_marked(&self, article_ids: Vec<ArticleID>, marked: Marked) {
+        if article_ids.is_empty() {
+            return;
+        }
+
         let (sender, receiver) = oneshot::channel::<Result<(), NewsFlashError>>();
 
         let news_flash = self.news_flash.clone();
-        let article_id_vec = vec![update.article_id.clone()];
-        let mark_status = update.marked;
+        let article_id_vec = article_ids.clone();
+        let mark_status = marked;
         let global_sender = self.sender.clone();
         let settings = self.settings.clone();
         let thread_future = async move {
@@ -732,7 +933,7 @@ impl App {
             match res {
                 Ok(Ok(())) => {}
                 Ok(Err(error)) => {
-                    let message = format!("Failed to star article: '{}'", update.article_id);
+                    let message = format!("Failed to star {} article(s)", article_ids.len());
                     error!("{}", message);
                     Util::send(&global_sender, Action::Error(message, error));
                     Util::send(&global_sender, Action::UpdateArticleList);
@@ -748,8 +949,8 @@ impl App {
             Util::send(&global_sender, Action::UpdateSidebar);
             let visible_article = content_page.article_view.get_visible_article();
             if let Some(mut visible_article) = visible_article {
-                if visible_article.article_id == update.article_id {
-                    visible_article.marked = update.marked;
+                if article_ids.contains(&visible_article.article_id) {
+                    visible_article.marked = marked;
                     content_header.show_article(Some(&visible_article), &news_flash, &features);
                     content_page
                         .article_view
@@ -762,6 +963,148 @@ impl App {
         Util::glib_spawn_future(glib_future);
     }
 
+    /// Ids of the selected articles, limited to the ones matching `filter`.
+    fn selected_article_ids<F: Fn(&ArticleListArticleModel) -> bool>(&self, filter: F) -> Vec<ArticleID> {
+        self.window
+            .content_page
+            .article_list
+            .read()
+            .get_selected_articles()
+            .into_iter()
+            .filter(|article| filter(article))
+            .map(|article| article.id)
+            .collect()
+    }
+
+    fn set_list_row_state(&self, article_ids: &[ArticleID], read: Option<Read>, marked: Option<Marked>) {
+        for article_id in article_ids {
+            self.window
+                .content_page
+                .article_list
+                .read()
+                .set_article_row_state(article_id, read, marked);
+        }
+    }
+
+    /// Update the rows of the article list right away and sync the new state to the backend.
+    fn set_list_articles_read(&self, article_ids: Vec<ArticleID>, read: Read) {
+        self.set_list_row_state(&article_ids, Some(read), None);
+        self.set_articles_read_undoable(article_ids, read);
+    }
+
+    fn set_list_articles_marked(&self, article_ids: Vec<ArticleID>, marked: Marked) {
+        self.set_list_row_state(&article_ids, None, Some(marked));
+        self.set_articles_marked_undoable(article_ids, marked);
+    }
+
+    fn set_articles_read_undoable(&self, article_ids: Vec<ArticleID>, read: Read) {
+        let undo_action = match read {
+            Read::Read => UndoActionModel::SetRead(article_ids.clone()),
+            Read::Unread => UndoActionModel::SetUnread(article_ids.clone()),
+        };
+        self.set_articles_read(article_ids, read);
+        self.window.show_undo_bar(undo_action);
+    }
+
+    fn set_articles_marked_undoable(&self, article_ids: Vec<ArticleID>, marked: Marked) {
+        let undo_action = match marked {
+            Marked::Marked => UndoActionModel::SetMarked(article_ids.clone()),
+            Marked::Unmarked => UndoActionModel::SetUnmarked(article_ids.clone()),
+        };
+        self.set_articles_marked(article_ids, marked);
+        self.window.show_undo_bar(undo_action);
+    }
+
+    fn undo(&self) {
+        let action = match self.window.undo_bar.pop_action() {
+            Some(action) => action,
+            None => return,
+        };
+
+        match action {
+            UndoActionModel::SetRead(article_ids) => self.revert_articles_read(article_ids, Read::Unread),
+            UndoActionModel::SetUnread(article_ids) => self.revert_articles_read(article_ids, Read::Read),
+            UndoActionModel::SetMarked(article_ids) => self.revert_articles_marked(article_ids, Marked::Unmarked),
+            UndoActionModel::SetUnmarked(article_ids) => self.revert_articles_marked(article_ids, Marked::Marked),
+            // the backend places moved items on its own, so only the old parent is restored
+            UndoActionModel::MoveFeed(feed_id, from_id, to_id) => {
+                self.drag_and_drop(FeedListDndAction::MoveFeed(feed_id, to_id, from_id, 0), false)
+            }
+            UndoActionModel::MoveCategory(category_id, from_id, _to_id) => {
+                self.drag_and_drop(FeedListDndAction::MoveCategory(category_id, from_id, 0), false)
+            }
+            UndoActionModel::RenameFeed(feed_id, old_title, _new_title) => {
+                let feed = self.news_flash.read().as_ref().and_then(|news_flash| {
+                    news_flash
+                        .get_feeds()
+                        .ok()
+                        .and_then(|(feeds, _mappings)| feeds.into_iter().find(|feed| feed.feed_id == feed_id))
+                });
+                match feed {
+                    Some(feed) => self.rename_feed(feed, old_title, false),
+                    None => warn!("Undo rename: feed '{}' not found", feed_id),
+                }
+            }
+            UndoActionModel::RenameCategory(category_id, old_title, _new_title) => {
+                let category = self.news_flash.read().as_ref().and_then(|news_flash| {
+                    news_flash
+                        .get_categories()
+                        .ok()
+                        .and_then(|categories| categories.into_iter().find(|c| c.category_id == category_id))
+                });
+                match category {
+                    Some(category) => self.rename_category(category, old_title, false),
+                    None => warn!("Undo rename: category '{}' not found", category_id),
+                }
+            }
+            UndoActionModel::TagArticles(article_ids, tag_id) => self.untag_articles(article_ids, tag_id, false),
+            UndoActionModel::UntagArticles(article_ids, tag_id) => self.tag_articles(article_ids, tag_id, false),
+            // deferred actions are simply dropped without ever being executed
+            UndoActionModel::DeleteFeed(_, _)
+            | UndoActionModel::DeleteCategory(_, _)
+            | UndoActionModel::DeleteTag(_, _)
+            | UndoActionModel::DeleteSavedSearch(_, _)
+            | UndoActionModel::MarkReadOnScroll(_) => {}
+        }
+
+        // update lists
+        Util::send(&self.sender, Action::UpdateSidebar);
+        Util::send(&self.sender, Action::UpdateArticleList);
+    }
+
+    fn revert_articles_read(&self, article_ids: Vec<ArticleID>, read: Read) {
+        self.set_list_row_state(&article_ids, Some(read), None);
+        self.set_articles_read(article_ids, read);
+    }
+
+    fn revert_articles_marked(&self, article_ids: Vec<ArticleID>, marked: Marked) {
+        self.set_list_row_state(&article_ids, None, Some(marked));
+        self.set_articles_marked(article_ids, marked);
+    }
+
+    fn open_selected_articles_in_browser(&self) {
+        let articles = self.window.content_page.article_list.read().get_selected_articles();
+        for article in articles {
+            if let Some(url) = article.url {
+                Util::send(&self.sender, Action::OpenUrlInDefaultBrowser(url.get().to_string()));
+            } else {
+                warn!(
+                    "Open selected articles in browser: No url available for '{}'.",
+                    article.id
+                )
+            }
+        }
+    }
+
+    fn update_selection_tags(&self) {
+        if let Some(news_flash) = self.news_flash.read().as_ref() {
+            match news_flash.get_tags() {
+                Ok(tags) => self.window.content_page.article_list.read().set_selection_tags(&tags),
+                Err(error) => Util::send(&self.sender, Action::Error("Failed to load tags.".to_owned(), error)),
+            }
+        }
+    }
+
     fn toggle_article_read(&self) {
         let selected_article = self
             .window
@@ -770,16 +1113,7 @@ impl App {
             .read()
             .get_selected_article_model();
         if let Some(selected_article) = selected_article {
-            let update = ReadUpdate {
-                article_id: selected_article.id.clone(),
-                read: selected_article.read.invert(),
-            };
-            self.window.content_page.article_list.read().set_article_row_state(
-                &selected_article.id,
-                Some(selected_article.read.invert()),
-                None,
-            );
-            Util::send(&self.sender, Action::MarkArticleRead(update));
+            self.set_list_articles_read(vec![selected_article.id], selected_article.read.invert());
         }
     }
 
@@ -791,17 +1125,7 @@ impl App {
             .read()
             .get_selected_article_model();
         if let Some(selected_article) = selected_article {
-            let update = MarkUpdate {
-                article_id: selected_article.id.clone(),
-                marked: selected_article.marked.invert(),
-            };
-
-            self.window.content_page.article_list.read().set_article_row_state(
-                &selected_article.id,
-                None,
-                Some(selected_article.marked.invert()),
-            );
-            Util::send(&self.sender, Action::MarkArticle(update));
+            self.set_list_articles_marked(vec![selected_article.id], selected_article.marked.invert());
         }
     }
 
@@ -1016,18 +1340,203 @@ impl App {
         }
     }
 
-    fn rename_feed(&self, feed: Feed, new_title: String) {
+    fn save_search_dialog(&self) {
+        let query = match self.window.state.read().get_search_term() {
+            Some(query) => query.clone(),
+            None => return,
+        };
+        let dialog = RenameDialog::save_search(&self.window.widget, &query);
+
+        dialog.rename_button.connect_clicked(clone!(
+            @weak dialog.rename_entry as rename_entry,
+            @weak dialog.dialog as save_dialog,
+            @strong self.sender as sender => @default-panic, move |_button|
+        {
+            let label = rename_entry.get_text().to_owned();
+            if label.is_empty() {
+                Util::send(
+                    &sender,
+                    Action::ErrorSimpleMessage("No valid title to save search.".to_owned()),
+                );
+                save_dialog.emit_close();
+                return;
+            }
+
+            Util::send(&sender, Action::SaveSearch(label));
+            save_dialog.emit_close();
+        }));
+    }
+
+    fn save_search(&self, label: String) {
+        let query = match self.window.state.read().get_search_term() {
+            Some(query) => query.clone(),
+            None => return,
+        };
+        let header = self.window.state.read().get_header_selection().clone();
+        if self.settings.write().add_saved_search(label, query, header).is_err() {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to save search.".to_owned()),
+            );
+        }
+        Util::send(&self.sender, Action::UpdateSidebar);
+    }
+
+    fn highlights_dialog(&self) {
+        let _dialog = HighlightsDialog::new(&self.window.widget, &self.settings, &self.sender);
+    }
+
+    fn highlight_note_dialog(&self, article_id: ArticleID, text: String, prefix: String) {
+        let dialog = RenameDialog::highlight_note(&self.window.widget);
+
+        dialog.rename_button.connect_clicked(clone!(
+            @weak dialog.rename_entry as rename_entry,
+            @weak dialog.dialog as note_dialog,
+            @strong self.sender as sender => @default-panic, move |_button|
+        {
+            let note = rename_entry.get_text().trim().to_owned();
+            let note = if note.is_empty() { None } else { Some(note) };
+            Util::send(
+                &sender,
+                Action::AddHighlight(article_id.clone(), text.clone(), prefix.clone(), note),
+            );
+            note_dialog.emit_close();
+        }));
+    }
+
+    fn add_highlight(&self, article_id: &ArticleID, text: String, prefix: String, note: Option<String>) {
+        let article = match self.window.content_page.article_view.find_article(article_id) {
+            Some(article) => article,
+            None => return,
+        };
+        if self
+            .settings
+            .write()
+            .add_highlight(&article, text, prefix, note)
+            .is_err()
+        {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to save highlight.".to_owned()),
+            );
+            return;
+        }
+        self.window.content_page.article_view.update_highlights();
+    }
+
+    fn rename_saved_search_dialog(&self, id: u32) {
+        let saved_search = match self.settings.read().get_saved_search(id) {
+            Some(saved_search) => saved_search,
+            None => return,
+        };
+        let dialog = RenameDialog::new(
+            &self.window.widget,
+            &SidebarSelection::SavedSearch(id, saved_search.label),
+        );
+
+        dialog.rename_button.connect_clicked(clone!(
+            @weak dialog.rename_entry as rename_entry,
+            @weak dialog.dialog as rename_dialog,
+            @strong self.sender as sender => @default-panic, move |_button|
+        {
+            let new_label = rename_entry.get_text().to_owned();
+            if new_label.is_empty() {
+                Util::send(
+                    &sender,
+                    Action::ErrorSimpleMessage("No valid title to rename saved search.".to_owned()),
+                );
+                rename_dialog.emit_close();
+                return;
+            }
+
+            Util::send(&sender, Action::RenameSavedSearch(id, new_label));
+            rename_dialog.emit_close();
+        }));
+    }
+
+    fn rename_saved_search(&self, id: u32, label: String) {
+        if self.settings.write().rename_saved_search(id, label.clone()).is_err() {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to rename saved search.".to_owned()),
+            );
+        }
+
+        let selected = match self.window.state.read().get_sidebar_selection() {
+            SidebarSelection::SavedSearch(selected_id, _label) => *selected_id == id,
+            _ => false,
+        };
+        if selected {
+            self.window
+                .state
+                .write()
+                .set_sidebar_selection(SidebarSelection::SavedSearch(id, label));
+        }
+        Util::send(&self.sender, Action::UpdateSidebar);
+    }
+
+    fn move_saved_search(&self, id: u32, offset: i32) {
+        if self.settings.write().move_saved_search(id, offset).is_err() {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to move saved search.".to_owned()),
+            );
+        }
+        Util::send(&self.sender, Action::UpdateSidebar);
+    }
+
+    fn delete_saved_search(&self, id: u32) {
+        if self.settings.write().delete_saved_search(id).is_err() {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to delete saved search.".to_owned()),
+            );
+        }
+
+        let selected = match self.window.state.read().get_sidebar_selection() {
+            SidebarSelection::SavedSearch(selected_id, _label) => *selected_id == id,
+            _ => false,
+        };
+        if selected {
+            self.window.state.write().set_sidebar_selection(SidebarSelection::All);
+            self.window.content_page.sidebar.read().select_all_button_no_update();
+            Util::send(&self.sender, Action::UpdateArticleList);
+        }
+        Util::send(&self.sender, Action::UpdateSidebar);
+    }
+
+    fn catch_up_dialog(&self) {
+        let sidebar_selection = self.window.state.read().get_sidebar_selection().clone();
+        let dialog = CatchUpDialog::new(&self.window.widget, &sidebar_selection);
+
+        dialog.catch_up_button.connect_clicked(clone!(
+            @weak dialog.days_spin as days_spin,
+            @weak dialog.dialog as catch_up_dialog,
+            @strong self.sender as sender => @default-panic, move |_button|
+        {
+            Util::send(&sender, Action::CatchUp(i64::from(days_spin.get_value_as_int())));
+            catch_up_dialog.emit_close();
+        }));
+    }
+
+    fn rename_feed(&self, feed: Feed, new_title: String, record_undo: bool) {
         let news_flash = self.news_flash.clone();
         let settings = self.settings.clone();
         let sender = self.sender.clone();
         let thread_future = async move {
             if let Some(news_flash) = news_flash.read().as_ref() {
-                if let Err(error) = Runtime::new().expect(RUNTIME_ERROR).block_on(news_flash.rename_feed(
+                match Runtime::new().expect(RUNTIME_ERROR).block_on(news_flash.rename_feed(
                     &feed,
                     &new_title,
                     &Self::build_client(&settings),
                 )) {
-                    Util::send(&sender, Action::Error("Failed to rename feed.".to_owned(), error));
+                    Ok(_) => {
+                        if record_undo {
+                            let undo_action = UndoActionModel::RenameFeed(feed.feed_id, feed.label, new_title);
+                            Util::send(&sender, Action::UndoableAction(undo_action));
+                        }
+                    }
+                    Err(error) => Util::send(&sender, Action::Error("Failed to rename feed.".to_owned(), error)),
                 }
             }
 
@@ -1085,17 +1594,24 @@ impl App {
         }
     }
 
-    fn rename_category(&self, category: Category, new_title: String) {
+    fn rename_category(&self, category: Category, new_title: String, record_undo: bool) {
         let news_flash = self.news_flash.clone();
         let settings = self.settings.clone();
         let sender = self.sender.clone();
         let thread_future = async move {
             if let Some(news_flash) = news_flash.read().as_ref() {
-                if let Err(error) = Runtime::new()
+                match Runtime::new()
                     .expect(RUNTIME_ERROR)
                     .block_on(news_flash.rename_category(&category, &new_title, &Self::build_client(&settings)))
                 {
-                    Util::send(&sender, Action::Error("Failed to rename category.".to_owned(), error));
+                    Ok(_) => {
+                        if record_undo {
+                            let undo_action =
+                                UndoActionModel::RenameCategory(category.category_id, category.label, new_title);
+                            Util::send(&sender, Action::UndoableAction(undo_action));
+                        }
+                    }
+                    Err(error) => Util::send(&sender, Action::Error("Failed to rename category.".to_owned(), error)),
                 }
             }
 
@@ -1112,9 +1628,14 @@ impl App {
                 warn!("Trying to delete item while 'All Articles' is selected");
                 None
             }
+            SidebarSelection::Podcasts => {
+                warn!("Trying to delete item while 'Podcasts' is selected");
+                None
+            }
             SidebarSelection::Feed(feed_id, _parent_id, label) => Some(UndoActionModel::DeleteFeed(feed_id, label)),
             SidebarSelection::Category(category_id, label) => Some(UndoActionModel::DeleteCategory(category_id, label)),
             SidebarSelection::Tag(tag_id, label) => Some(UndoActionModel::DeleteTag(tag_id, label)),
+            SidebarSelection::SavedSearch(id, label) => Some(UndoActionModel::DeleteSavedSearch(id, label)),
         };
         if let Some(undo_action) = undo_action {
             Util::send(&self.sender, Action::UndoableAction(undo_action));
@@ -1244,87 +1765,112 @@ impl App {
         self.threadpool.spawn_ok(thread_future);
     }
 
-    fn tag_article(&self, article_id: ArticleID, tag_id: TagID) {
+    fn tag_articles(&self, article_ids: Vec<ArticleID>, tag_id: TagID, record_undo: bool) {
+        self.set_articles_tagged(article_ids, tag_id, true, record_undo);
+    }
+
+    fn untag_articles(&self, article_ids: Vec<ArticleID>, tag_id: TagID, record_undo: bool) {
+        self.set_articles_tagged(article_ids, tag_id, false, record_undo);
+    }
+
+    /// Add or remove `tag_id` for all `article_ids` in a single background job.
+    /// Articles that already are in the requested state are skipped so undo only reverts actual changes.
+    fn set_articles_tagged(&self, article_ids: Vec<ArticleID>, tag_id: TagID, tagged: bool, record_undo: bool) {
+        if article_ids.is_empty() {
+            return;
+        }
+
         let news_flash = self.news_flash.clone();
         let settings = self.settings.clone();
         let sender = self.sender.clone();
         let thread_future = async move {
             if let Some(news_flash) = news_flash.read().as_ref() {
-                let tags = match news_flash.get_tags() {
-                    Ok(res) => res,
+                let verb = if tagged { "tag" } else { "untag" };
+                let tag = match news_flash.get_tags() {
+                    Ok(tags) => tags.into_iter().find(|t| t.tag_id == tag_id),
                     Err(error) => {
-                        Util::send(&sender, Action::Error("Failed to tag article.".to_owned(), error));
+                        Util::send(&sender, Action::Error(format!("Failed to {} articles.", verb), error));
                         return;
                     }
                 };
-                let article = match news_flash.get_article(&article_id) {
-                    Ok(res) => res,
-                    Err(error) => {
-                        Util::send(
-                            &sender,
-                            Action::Error("Failed to tag article. Article not found.".to_owned(), error),
-                        );
+                let tag = match tag {
+                    Some(tag) => tag,
+                    None => {
+                        let message = format!("Failed to {} articles: tag with id '{}' not found.", verb, tag_id);
+                        Util::send(&sender, Action::ErrorSimpleMessage(message));
+                        error!("tag not found: {}", tag_id);
                         return;
                     }
                 };
 
-                if let Some(tag) = tags.iter().find(|t| t.tag_id == tag_id).cloned() {
-                    info!("tag article '{}' with '{}'", article_id, tag.tag_id);
-                    if let Err(error) = Runtime::new().expect(RUNTIME_ERROR).block_on(news_flash.tag_article(
-                        &article,
-                        &tag,
-                        &Self::build_client(&settings),
-                    )) {
-                        Util::send(&sender, Action::Error("Failed to tag article.".to_owned(), error));
-                    }
-                } else {
-                    let message = format!("Failed to tag article: tag with id '{}' not found.", tag_id);
-                    Util::send(&sender, Action::ErrorSimpleMessage(message));
-                    error!("tag not found: {}", tag_id);
-                }
-            }
-        };
-
-        self.threadpool.spawn_ok(thread_future);
-    }
-
-    fn untag_article(&self, article_id: ArticleID, tag_id: TagID) {
-        let news_flash = self.news_flash.clone();
-        let settings = self.settings.clone();
-        let sender = self.sender.clone();
-        let thread_future = async move {
-            if let Some(news_flash) = news_flash.read().as_ref() {
-                let tags = match news_flash.get_tags() {
-                    Ok(res) => res,
-                    Err(error) => {
-                        Util::send(&sender, Action::Error("Failed to untag article.".to_owned(), error));
-                        return;
-                    }
+                let filter = |tag: Option<TagID>| ArticleFilter {
+                    limit: None,
+                    offset: None,
+                    order: None,
+                    unread: None,
+                    marked: None,
+                    feed: None,
+                    feed_blacklist: None,
+                    category: None,
+                    category_blacklist: None,
+                    tag,
+                    ids: Some(article_ids.clone()),
+                    newer_than: None,
+                    older_than: None,
+                    search_term: None,
                 };
-                let article = match news_flash.get_article(&article_id) {
+                let articles = news_flash.get_articles(filter(None)).and_then(|articles| {
+                    news_flash
+                        .get_articles(filter(Some(tag.tag_id.clone())))
+                        .map(|tagged_articles| (articles, tagged_articles))
+                });
+                let (articles, tagged_articles) = match articles {
                     Ok(res) => res,
                     Err(error) => {
-                        Util::send(
-                            &sender,
-                            Action::Error("Failed to untag article. Article not found.".to_owned(), error),
-                        );
+                        Util::send(&sender, Action::Error(format!("Failed to {} articles.", verb), error));
                         return;
                     }
                 };
+                let tagged_ids: HashSet<ArticleID> =
+                    tagged_articles.into_iter().map(|article| article.article_id).collect();
 
-                if let Some(tag) = tags.iter().find(|t| t.tag_id == tag_id).cloned() {
-                    info!("untag article '{}' with '{}'", article_id, tag.tag_id);
-                    if let Err(error) = Runtime::new().expect(RUNTIME_ERROR).block_on(news_flash.untag_article(
-                        &article,
-                        &tag,
-                        &Self::build_client(&settings),
-                    )) {
-                        Util::send(&sender, Action::Error("Failed to untag article.".to_owned(), error));
+                let mut runtime = Runtime::new().expect(RUNTIME_ERROR);
+                let client = Self::build_client(&settings);
+                let mut changed_ids = Vec::new();
+                let mut last_error = None;
+                let mut failed = 0;
+                for article in articles {
+                    if tagged_ids.contains(&article.article_id) == tagged {
+                        continue;
+                    }
+
+                    info!("{} article '{}' with '{}'", verb, article.article_id, tag.tag_id);
+                    let result = if tagged {
+                        runtime.block_on(news_flash.tag_article(&article, &tag, &client))
+                    } else {
+                        runtime.block_on(news_flash.untag_article(&article, &tag, &client))
+                    };
+                    match result {
+                        Ok(_) => changed_ids.push(article.article_id),
+                        Err(error) => {
+                            failed += 1;
+                            last_error = Some(error);
+                        }
                     }
-                } else {
-                    let message = format!("Failed to tag article: untag with id '{}' not found.", tag_id);
-                    Util::send(&sender, Action::ErrorSimpleMessage(message));
-                    error!("tag not found: {}", tag_id);
+                }
+
+                if let Some(error) = last_error {
+                    let message = format!("Failed to {} {} articles.", verb, failed);
+                    Util::send(&sender, Action::Error(message, error));
+                }
+
+                if record_undo && !changed_ids.is_empty() {
+                    let undo_action = if tagged {
+                        UndoActionModel::TagArticles(changed_ids, tag.tag_id)
+                    } else {
+                        UndoActionModel::UntagArticles(changed_ids, tag.tag_id)
+                    };
+                    Util::send(&sender, Action::UndoableAction(undo_action));
                 }
             }
         };
@@ -1332,7 +1878,7 @@ impl App {
         self.threadpool.spawn_ok(thread_future);
     }
 
-    fn drag_and_drop(&self, action: FeedListDndAction) {
+    fn drag_and_drop(&self, action: FeedListDndAction, record_undo: bool) {
         let news_flash = self.news_flash.clone();
         let settings = self.settings.clone();
         let sender = self.sender.clone();
@@ -1341,22 +1887,43 @@ impl App {
                 let mut runtime = Runtime::new().expect(RUNTIME_ERROR);
                 match action {
                     FeedListDndAction::MoveCategory(category_id, parent_id, _sort_index) => {
-                        if let Err(error) = runtime.block_on(news_flash.move_category(
+                        let old_parent_id = news_flash.get_categories().ok().and_then(|categories| {
+                            categories
+                                .into_iter()
+                                .find(|c| c.category_id == category_id)
+                                .map(|c| c.parent_id)
+                        });
+                        match runtime.block_on(news_flash.move_category(
                             &category_id,
                             &parent_id,
                             &Self::build_client(&settings),
                         )) {
-                            Util::send(&sender, Action::Error("Failed to move category.".to_owned(), error));
+                            Ok(_) => {
+                                if let (true, Some(old_parent_id)) = (record_undo, old_parent_id) {
+                                    let undo_action =
+                                        UndoActionModel::MoveCategory(category_id, old_parent_id, parent_id);
+                                    Util::send(&sender, Action::UndoableAction(undo_action));
+                                }
+                            }
+                            Err(error) => {
+                                Util::send(&sender, Action::Error("Failed to move category.".to_owned(), error))
+                            }
                         }
                     }
                     FeedListDndAction::MoveFeed(feed_id, from_id, to_id, _sort_index) => {
-                        if let Err(error) = runtime.block_on(news_flash.move_feed(
+                        match runtime.block_on(news_flash.move_feed(
                             &feed_id,
                             &from_id,
                             &to_id,
                             &Self::build_client(&settings),
                         )) {
-                            Util::send(&sender, Action::Error("Failed to move feed.".to_owned(), error));
+                            Ok(_) => {
+                                if record_undo {
+                                    let undo_action = UndoActionModel::MoveFeed(feed_id, from_id, to_id);
+                                    Util::send(&sender, Action::UndoableAction(undo_action));
+                                }
+                            }
+                            Err(error) => Util::send(&sender, Action::Error("Failed to move feed.".to_owned(), error)),
                         }
                     }
                 }
@@ -1384,10 +1951,9 @@ impl App {
             filter.set_name(Some("HTML"));
             dialog.add_filter(&filter);
             dialog.set_filter(&filter);
-            if let Some(title) = &article.title {
-                dialog.set_current_name(&format!("{}.html", title.replace("/", "_")));
-            } else {
-                dialog.set_current_name("Article.html");
+            match article.title.as_deref().and_then(FileUtil::file_name) {
+                Some(title) => dialog.set_current_name(&format!("{}.html", title)),
+                None => dialog.set_current_name("Article.html"),
             }
 
             if let ResponseType::Ok = dialog.run() {
@@ -1404,6 +1970,8 @@ impl App {
                 };
                 let window_state = self.window.state.clone();
                 let settings = self.settings.clone();
+                let highlights = self.settings.read().get_article_highlights(&article.article_id);
+                let highlights_heading = i18n("Highlights");
                 let thread_future = async move {
                     if let Some(news_flash) = news_flash.read().as_ref() {
                         let article = if window_state.read().get_offline() {
@@ -1446,7 +2014,7 @@ impl App {
                                 return;
                             }
                         };
-                        let html = ArticleView::build_article_static(
+                        let mut html = ArticleView::build_article_static(
                             "article",
                             &article,
                             &feed.label,
@@ -1455,6 +2023,11 @@ impl App {
                             None,
                             true,
                         );
+                        if !highlights.is_empty() {
+                            if let Some(body_end) = html.rfind("</body>") {
+                                html.insert_str(body_end, &Highlight::html(&highlights, &highlights_heading));
+                            }
+                        }
                         if FileUtil::write_text_file(&filename, &html).is_err() {
                             Util::send(
                                 &global_sender,
@@ -1477,74 +2050,290 @@ impl App {
         }
     }
 
-    fn start_grab_article_content(&self) {
-        let (sender, receiver) = oneshot::channel::<Result<FatArticle, NewsFlashError>>();
+    fn export_selected_articles(&self) {
+        let article_ids = self.selected_article_ids(|_article| true);
+        if article_ids.is_empty() {
+            return;
+        }
 
-        if let Some(article) = self.window.content_page.article_view.get_visible_article() {
-            // Article already scraped: just swap to scraped content
-            if article.scraped_content.is_some() {
-                Util::send(&self.sender, Action::FinishGrabArticleContent(Some(article)));
-                return;
-            }
+        let dialog = FileChooserDialog::with_buttons(
+            Some("Export Articles"),
+            Some(&self.window.widget),
+            FileChooserAction::SelectFolder,
+            &[("Cancel", ResponseType::Cancel), ("Export", ResponseType::Ok)],
+        );
 
-            self.window.content_header.start_scrap_content_spinner();
+        if let ResponseType::Ok = dialog.run() {
+            let directory = match dialog.get_filename() {
+                Some(directory) => directory,
+                None => {
+                    Util::send(&self.sender, Action::ErrorSimpleMessage("No folder set.".to_owned()));
+                    dialog.emit_close();
+                    return;
+                }
+            };
+
+            self.window.content_header.start_more_actions_spinner();
 
+            let (sender, receiver) = oneshot::channel::<()>();
             let news_flash = self.news_flash.clone();
+            let global_sender = self.sender.clone();
+            let window_state = self.window.state.clone();
             let settings = self.settings.clone();
-            let article_id = article.article_id.clone();
             let thread_future = async move {
                 if let Some(news_flash) = news_flash.read().as_ref() {
-                    let article = Runtime::new()
-                        .expect(RUNTIME_ERROR)
-                        .block_on(news_flash.article_scrap_content(&article_id, &Self::build_client(&settings)));
-                    sender.send(article).expect(CHANNEL_ERROR);
+                    let feeds = match news_flash.get_feeds() {
+                        Ok((feeds, _)) => feeds,
+                        Err(error) => {
+                            Util::send(
+                                &global_sender,
+                                Action::Error("Failed to load feeds from db.".to_owned(), error),
+                            );
+                            sender.send(()).expect(CHANNEL_ERROR);
+                            return;
+                        }
+                    };
+                    let mut runtime = Runtime::new().expect(RUNTIME_ERROR);
+                    let client = Self::build_client(&settings);
+
+                    let mut last_error = None;
+                    let mut failed_loads = 0;
+                    let mut failed_writes = 0;
+                    let mut used_file_names = HashSet::new();
+                    for article_id in article_ids {
+                        let article = if window_state.read().get_offline() {
+                            news_flash.get_fat_article(&article_id)
+                        } else {
+                            runtime.block_on(news_flash.article_download_images(&article_id, &client))
+                        };
+                        let article = match article {
+                            Ok(article) => article,
+                            Err(error) => {
+                                warn!("Failed to load article '{}' for export", article_id);
+                                failed_loads += 1;
+                                last_error = Some(error);
+                                continue;
+                            }
+                        };
+
+                        let feed_name = feeds
+                            .iter()
+                            .find(|f| f.feed_id == article.feed_id)
+                            .map(|f| f.label.clone())
+                            .unwrap_or_default();
+                        let html = ArticleView::build_article_static(
+                            "article", &article, &feed_name, &settings, None, None, true,
+                        );
+                        // articles of different feeds often share a title, the id tells them apart
+                        let article_id =
+                            FileUtil::file_name(&article.article_id.to_string()).unwrap_or_else(|| i18n("Article"));
+                        let mut file_name = article
+                            .title
+                            .as_deref()
+                            .and_then(FileUtil::file_name)
+                            .unwrap_or_else(|| article_id.clone());
+                        if !used_file_names.insert(file_name.to_lowercase()) {
+                            file_name = format!("{} ({})", file_name, article_id);
+                            used_file_names.insert(file_name.to_lowercase());
+                        }
+                        if FileUtil::write_text_file(&directory.join(format!("{}.html", file_name)), &html).is_err() {
+                            failed_writes += 1;
+                        }
+                    }
+
+                    if let Some(error) = last_error {
+                        let message = format!("Failed to load {} articles for export.", failed_loads);
+                        Util::send(&global_sender, Action::Error(message, error));
+                    }
+                    if failed_writes > 0 {
+                        let message = format!("Failed to write {} articles to disc.", failed_writes);
+                        Util::send(&global_sender, Action::ErrorSimpleMessage(message));
+                    }
                 }
+                sender.send(()).expect(CHANNEL_ERROR);
             };
 
-            let glib_future = receiver.map(clone!(
-                @strong self.sender as sender,
-                @strong article.article_id as article_id => @default-panic, move |res| match res
-            {
+            let glib_future = receiver.map(
+                clone!(@weak self.window.content_header as content_header => @default-panic, move |_res| {
+                    content_header.stop_more_actions_spinner();
+                }),
+            );
+
+            self.threadpool.spawn_ok(thread_future);
+            Util::glib_spawn_future(glib_future);
+        }
+        dialog.emit_close();
+    }
+
+    fn start_grab_article_content(&self) {
+        if let Some(article) = self.window.content_page.article_view.get_visible_article() {
+            // Article already scraped: just swap to scraped content
+            if article.scraped_content.is_some() {
+                Util::send(
+                    &self.sender,
+                    Action::FinishGrabArticleContent(article.article_id.clone(), Some(article)),
+                );
+                return;
+            }
+
+            self.window.content_header.start_scrap_content_spinner();
+            self.grab_article_content(article.article_id, true);
+        }
+    }
+
+    /// Scrape the full content of the article. Failures of automatic scrapes are only logged.
+    fn grab_article_content(&self, article_id: ArticleID, report_errors: bool) {
+        let (sender, receiver) = oneshot::channel::<Result<FatArticle, NewsFlashError>>();
+
+        let news_flash = self.news_flash.clone();
+        let settings = self.settings.clone();
+        let thread_article_id = article_id.clone();
+        let thread_future = async move {
+            if let Some(news_flash) = news_flash.read().as_ref() {
+                let article = Runtime::new()
+                    .expect(RUNTIME_ERROR)
+                    .block_on(news_flash.article_scrap_content(&thread_article_id, &Self::build_client(&settings)));
+                sender.send(article).expect(CHANNEL_ERROR);
+            }
+        };
+
+        let glib_future = receiver.map(
+            clone!(@strong self.sender as sender => @default-panic, move |res| match res {
                 Ok(Ok(article)) => {
-                    Util::send(&sender, Action::FinishGrabArticleContent(Some(article)));
+                    Util::send(&sender, Action::FinishGrabArticleContent(article_id.clone(), Some(article)));
                 }
                 Ok(Err(error)) => {
                     let message = format!("Failed to scrape article content: '{}'", article_id);
                     error!("{}", message);
-                    Util::send(&sender, Action::Error(message, error));
-                    Util::send(&sender, Action::FinishGrabArticleContent(None));
+                    if report_errors {
+                        Util::send(&sender, Action::Error(message, error));
+                    }
+                    Util::send(&sender, Action::FinishGrabArticleContent(article_id.clone(), None));
                 }
                 Err(error) => {
                     let message = format!("Sender error: {}", error);
                     error!("{}", message);
-                    Util::send(&sender, Action::ErrorSimpleMessage(message));
-                    Util::send(&sender, Action::FinishGrabArticleContent(None));
+                    if report_errors {
+                        Util::send(&sender, Action::ErrorSimpleMessage(message));
+                    }
+                    Util::send(&sender, Action::FinishGrabArticleContent(article_id.clone(), None));
                 }
-            }));
+            }),
+        );
 
-            self.threadpool.spawn_ok(thread_future);
-            Util::glib_spawn_future(glib_future);
+        self.threadpool.spawn_ok(thread_future);
+        Util::glib_spawn_future(glib_future);
+    }
+
+    /// Fetch the full content of the newest unread articles of all feeds that always show it,
+    /// so they are ready (also offline) once opened.
+    fn scrape_after_sync(&self) {
+        if !self.settings.read().get_scrape_after_sync() || self.window.state.read().get_offline() {
+            return;
+        }
+        let feed_ids = self.settings.read().get_article_view_full_content_feeds();
+        if feed_ids.is_empty() {
+            return;
         }
+
+        let news_flash = self.news_flash.clone();
+        let settings = self.settings.clone();
+        let global_sender = self.sender.clone();
+        let thread_future = async move {
+            let article_ids = match news_flash.read().as_ref() {
+                Some(news_flash) => Self::unscraped_article_ids(news_flash, &feed_ids),
+                None => return,
+            };
+
+            let client = Self::build_client(&settings);
+            let mut runtime = Runtime::new().expect(RUNTIME_ERROR);
+            for article_id in article_ids {
+                // only hold on to news_flash for one article at a time, a logout may be waiting for it
+                let news_flash = news_flash.read();
+                let news_flash = match news_flash.as_ref() {
+                    Some(news_flash) => news_flash,
+                    None => return,
+                };
+                match runtime.block_on(news_flash.article_scrap_content(&article_id, &client)) {
+                    Ok(article) => Util::send(&global_sender, Action::ArticleScraped(article)),
+                    Err(_error) => warn!("Failed to scrape article content: '{}'", article_id),
+                }
+            }
+            info!("Fetched full content of new articles after sync");
+        };
+
+        self.threadpool.spawn_ok(thread_future);
     }
 
-    fn finish_grab_article_content(&self, article: Option<FatArticle>) {
+    /// The newest unread articles of the feeds that have no full content yet.
+    fn unscraped_article_ids(news_flash: &NewsFlash, feed_ids: &[FeedID]) -> Vec<ArticleID> {
+        let mut article_ids = Vec::new();
+        for feed_id in feed_ids {
+            let articles = match news_flash.get_articles(ArticleFilter {
+                limit: Some(SCRAPE_AFTER_SYNC_LIMIT),
+                offset: None,
+                order: Some(ArticleOrder::NewestFirst),
+                unread: Some(Read::Unread),
+                marked: None,
+                feed: Some(feed_id.clone()),
+                feed_blacklist: None,
+                category: None,
+                category_blacklist: None,
+                tag: None,
+                ids: None,
+                newer_than: None,
+                older_than: None,
+                search_term: None,
+            }) {
+                Ok(articles) => articles,
+                Err(_error) => {
+                    warn!("Failed to read articles of feed '{}'", feed_id);
+                    continue;
+                }
+            };
+            for article in articles {
+                match news_flash.get_fat_article(&article.article_id) {
+                    Ok(fat_article) if fat_article.scraped_content.is_none() => article_ids.push(article.article_id),
+                    _ => continue,
+                }
+            }
+        }
+        article_ids
+    }
+
+    fn finish_grab_article_content(&self, article_id: ArticleID, article: Option<FatArticle>) {
         self.window.content_header.stop_scrap_content_spinner();
 
-        let article_id = match article {
-            Some(article) => Some(article.article_id),
-            None => self
-                .window
-                .content_page
-                .article_view
-                .get_visible_article()
-                .map(|a| a.article_id),
-        };
+        match article {
+            Some(article) => self.article_scraped(article),
+            // back to the original content, unless the reader moved on to another article in the meantime
+            None => {
+                if self.is_visible_article(&article_id) {
+                    self.window.show_article(article_id, &self.news_flash, &self.features);
+                }
+            }
+        }
+    }
 
-        if let Some(article_id) = article_id {
-            self.window.show_article(article_id, &self.news_flash, &self.features);
+    /// Show the full content wherever the article is on display.
+    fn article_scraped(&self, article: FatArticle) {
+        self.window.content_page.article_view.update_scraped_article(&article);
+        // the reader may have moved on to another article in the meantime
+        if self.is_visible_article(&article.article_id) {
+            self.window
+                .show_article(article.article_id, &self.news_flash, &self.features);
         }
     }
 
+    fn is_visible_article(&self, article_id: &ArticleID) -> bool {
+        self.window
+            .content_page
+            .article_view
+            .get_visible_article()
+            .map(|visible_article| &visible_article.article_id == article_id)
+            .unwrap_or(false)
+    }
+
     fn import_opml(&self) {
         let (sender, receiver) = oneshot::channel::<()>();
 
@@ -1672,8 +2461,131 @@ impl App {
         dialog.emit_close();
     }
 
+    fn set_article_feed_images(&self, load_images: Option<bool>) {
+        let feed_id = match self.window.content_page.article_view.get_visible_article() {
+            Some(article) => article.feed_id,
+            None => return,
+        };
+        if self
+            .settings
+            .write()
+            .set_article_view_feed_images(&feed_id, load_images)
+            .is_err()
+        {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to set setting 'feed images'.".to_owned()),
+            );
+            return;
+        }
+        Util::send(&self.sender, Action::RedrawArticle);
+    }
+
+    /// Show the article and fetch its full content right away if its feed always shows it.
+    fn show_article(&self, article_id: ArticleID) {
+        self.window.show_article(article_id, &self.news_flash, &self.features);
+
+        if self.window.state.read().get_offline() {
+            return;
+        }
+        if let Some(article) = self.window.content_page.article_view.get_visible_article() {
+            if article.scraped_content.is_none()
+                && self
+                    .settings
+                    .read()
+                    .get_article_view_feed_full_content(&article.feed_id)
+            {
+                self.window.content_header.start_scrap_content_spinner();
+                self.grab_article_content(article.article_id, false);
+            }
+        }
+    }
+
+    fn set_article_feed_full_content(&self, full_content: bool) {
+        let article = match self.window.content_page.article_view.get_visible_article() {
+            Some(article) => article,
+            None => return,
+        };
+        if self
+            .settings
+            .write()
+            .set_article_view_feed_full_content(&article.feed_id, full_content)
+            .is_err()
+        {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to set setting 'feed full content'.".to_owned()),
+            );
+            return;
+        }
+        if full_content && !self.window.state.read().get_offline() {
+            self.start_grab_article_content();
+        }
+    }
+
+    fn set_article_feed_links(&self, internal: Option<bool>) {
+        let feed_id = match self.window.content_page.article_view.get_visible_article() {
+            Some(article) => article.feed_id,
+            None => return,
+        };
+        if self
+            .settings
+            .write()
+            .set_article_view_feed_internal_links(&feed_id, internal)
+            .is_err()
+        {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to set setting 'feed links'.".to_owned()),
+            );
+        }
+    }
+
+    /// Go on with the next unread article of the list if the reader wants to keep listening.
+    fn read_aloud_finished(&self, article_id: ArticleID) {
+        if !self.settings.read().get_article_view_read_aloud_continue() {
+            return;
+        }
+        self.window.content_page.continue_read_aloud(
+            article_id,
+            &self.news_flash,
+            &self.window.undo_bar,
+            self.threadpool.clone(),
+        );
+    }
+
+    /// Keep reading aloud with `next` unless the reader moved on from `finished` in the meantime.
+    fn read_aloud_continue(&self, finished: ArticleID, next: ArticleID) {
+        let visible_article_id = self
+            .window
+            .content_page
+            .article_view
+            .get_visible_article()
+            .map(|article| article.article_id);
+        if visible_article_id.as_ref() != Some(&finished) {
+            return;
+        }
+        self.window
+            .content_page
+            .article_view
+            .read_aloud_continue_with(next.clone());
+        if !self.window.content_page.article_list.read().select_article_by_id(&next) {
+            // further down than the list is loaded
+            let update = ReadUpdate {
+                article_id: next.clone(),
+                read: Read::Read,
+            };
+            Util::send(&self.sender, Action::MarkArticleRead(update));
+            Util::send(&self.sender, Action::ShowArticle(next));
+        }
+    }
+
     fn queue_quit(&self) {
         *self.shutdown_in_progress.write() = true;
+        self.window.content_page.article_view.save_view_state();
+        if self.settings.write().write_reading_positions().is_err() {
+            warn!("Failed to save reading positions");
+        }
         self.window.widget.close();
         self.window.execute_pending_undoable_action();
 
@@ -1717,6 +2629,42 @@ impl App {
             .update_offline();
     }
 
+    fn set_article_list_sort(&self, sort: ArticleListSort) {
+        let selection = self.window.state.read().get_sidebar_selection().clone();
+        let mut view_settings = self.settings.read().get_article_list_view(&selection);
+        view_settings.sort = sort;
+        if self
+            .settings
+            .write()
+            .set_article_list_view(&selection, view_settings)
+            .is_err()
+        {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to set setting 'article list sort'.".to_owned()),
+            );
+        }
+        Util::send(&self.sender, Action::UpdateArticleList);
+    }
+
+    fn set_article_list_grouping(&self, grouping: ArticleListGrouping) {
+        let selection = self.window.state.read().get_sidebar_selection().clone();
+        let mut view_settings = self.settings.read().get_article_list_view(&selection);
+        view_settings.grouping = grouping;
+        if self
+            .settings
+            .write()
+            .set_article_list_view(&selection, view_settings)
+            .is_err()
+        {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to set setting 'article list grouping'.".to_owned()),
+            );
+        }
+        Util::send(&self.sender, Action::UpdateArticleList);
+    }
+
     pub fn build_client(settings: &Arc<RwLock<Settings>>) -> Client {
         let proxy_error = "Failed to build proxy";
 
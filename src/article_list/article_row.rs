@@ -1,15 +1,18 @@
-use super::models::{ArticleListArticleModel, ArticleListModel, MarkUpdate, ReadUpdate};
+use super::models::{ArticleListArticleModel, ArticleListModel};
 use crate::app::Action;
+use crate::i18n::i18n;
 use crate::main_window_state::MainWindowState;
+use crate::search_query;
 use crate::util::{BuilderHelper, DateUtil, GtkUtil, Util};
 use chrono::NaiveDateTime;
 use futures::channel::oneshot;
 use futures::future::FutureExt;
-use gdk::{EventType, NotifyType};
-use glib::{clone, object::Cast, translate::ToGlib, Sender};
+use gdk::{EventType, NotifyType, Rectangle};
+use gio::{Menu, MenuItem};
+use glib::{clone, object::Cast, translate::ToGlib, Sender, ToVariant};
 use gtk::{
-    ContainerExt, EventBox, Image, ImageExt, Inhibit, Label, LabelExt, ListBoxRow, ListBoxRowExt, Stack, StackExt,
-    StyleContextExt, Widget, WidgetExt,
+    BinExt, ContainerExt, EventBox, Image, ImageExt, Inhibit, Label, LabelExt, ListBoxRow, ListBoxRowExt, Popover,
+    PopoverExt, Stack, StackExt, StyleContextExt, Widget, WidgetExt,
 };
 use log::warn;
 use news_flash::models::{ArticleID, FavIcon, Marked, Read};
@@ -17,6 +20,8 @@ use parking_lot::RwLock;
 use std::ops::Drop;
 use std::sync::Arc;
 
+const MIDDLE_MOUSE_BUTTON: u32 = 2;
+
 pub struct ArticleRow {
     widget: ListBoxRow,
     marked_handle: Arc<RwLock<Marked>>,
@@ -30,10 +35,14 @@ pub struct ArticleRow {
 }
 
 impl ArticleRow {
+    /// Fill `row` with the widgets representing `article`.
+    /// The content is removed from the row again once the `ArticleRow` is dropped.
     pub fn new(
         article: &ArticleListArticleModel,
+        row: &ListBoxRow,
         list_model: &Arc<RwLock<ArticleListModel>>,
         state: &Arc<RwLock<MainWindowState>>,
+        search_terms: &[String],
         sender: Sender<Action>,
     ) -> Self {
         let builder = BuilderHelper::new("article");
@@ -49,7 +58,8 @@ impl ArticleRow {
         let feed_label = builder.get::<Label>("feed_label");
         let date_label = builder.get::<Label>("date_label");
 
-        let row = Self::create_row(&article_eventbox);
+        row.set_size_request(-1, -1);
+        row.add(&article_eventbox);
 
         let scale = GtkUtil::get_scale(&favicon);
 
@@ -69,9 +79,9 @@ impl ArticleRow {
         let surface = GtkUtil::create_surface_from_icon_name("unread", 16, scale);
         unread.set_from_surface(Some(&surface));
 
-        title_label.set_text(&article.title);
+        Self::set_label_text(&title_label, &article.title, search_terms);
         title_label.set_tooltip_text(Some(&article.title));
-        summary_label.set_text(&article.summary);
+        Self::set_label_text(&summary_label, &article.summary, search_terms);
         feed_label.set_text(&article.feed_title);
         date_label.set_text(&DateUtil::format(&article.date));
 
@@ -110,6 +120,7 @@ impl ArticleRow {
             &title_label,
             &row_hovered,
         ));
+        connected_signals.append(&mut Self::setup_context_menu(&article_eventbox, &article.id, &sender));
         connected_signals.append(&mut Self::setup_unread_eventbox(
             &sender,
             state,
@@ -131,7 +142,7 @@ impl ArticleRow {
         ));
 
         ArticleRow {
-            widget: row,
+            widget: row.clone(),
             marked_handle,
             read_handle,
             marked_stack,
@@ -162,17 +173,80 @@ impl ArticleRow {
         self.date_label.set_text(&DateUtil::format(&date));
     }
 
-    fn create_row(widget: &EventBox) -> ListBoxRow {
+    pub fn create_row(height: i32) -> ListBoxRow {
         let row = ListBoxRow::new();
         row.set_activatable(true);
         row.set_can_focus(false);
-        row.add(widget);
+        row.set_size_request(-1, height);
         let context = row.get_style_context();
         context.remove_class("activatable");
 
         row
     }
 
+    fn setup_context_menu(
+        eventbox: &EventBox,
+        article_id: &ArticleID,
+        sender: &Sender<Action>,
+    ) -> Vec<(usize, Widget)> {
+        let mut vec = Vec::new();
+
+        vec.push((
+            eventbox
+                .connect_button_press_event(clone!(
+                    @strong article_id,
+                    @strong sender => @default-panic, move |eventbox, event|
+                {
+                    if event.get_event_type() != EventType::ButtonPress {
+                        return Inhibit(false);
+                    }
+                    if event.get_button() == MIDDLE_MOUSE_BUTTON {
+                        Util::send(&sender, Action::OpenArticleInTab(article_id.clone()));
+                        return Inhibit(true);
+                    }
+                    if event.get_button() != 3 {
+                        return Inhibit(false);
+                    }
+
+                    let article_id = article_id.to_string().to_variant();
+                    let open_model = Menu::new();
+                    let tab_item = MenuItem::new(Some(&i18n("Open in New Tab")), None);
+                    tab_item.set_action_and_target_value(Some("article-list.open-in-tab"), Some(&article_id));
+                    open_model.append_item(&tab_item);
+                    let window_item = MenuItem::new(Some(&i18n("Open in New Window")), None);
+                    window_item.set_action_and_target_value(Some("article-list.open-in-window"), Some(&article_id));
+                    open_model.append_item(&window_item);
+
+                    let read_model = Menu::new();
+                    let above_item = MenuItem::new(Some(&i18n("Mark Above as Read")), None);
+                    above_item.set_action_and_target_value(Some("article-list.mark-above-read"), Some(&article_id));
+                    read_model.append_item(&above_item);
+                    let below_item = MenuItem::new(Some(&i18n("Mark Below as Read")), None);
+                    below_item.set_action_and_target_value(Some("article-list.mark-below-read"), Some(&article_id));
+                    read_model.append_item(&below_item);
+
+                    let model = Menu::new();
+                    model.append_section(None, &open_model);
+                    model.append_section(None, &read_model);
+
+                    let (x, y) = event.get_position();
+                    let popover = Popover::from_model(Some(eventbox), &model);
+                    popover.set_pointing_to(&Rectangle {
+                        x: x as i32,
+                        y: y as i32,
+                        width: 1,
+                        height: 1,
+                    });
+                    popover.popup();
+                    Inhibit(true)
+                }))
+                .to_glib() as usize,
+            eventbox.clone().upcast::<Widget>(),
+        ));
+
+        vec
+    }
+
     fn setup_unread_eventbox(
         sender: &Sender<Action>,
         state: &Arc<RwLock<MainWindowState>>,
@@ -249,11 +323,7 @@ impl ArticleRow {
                     *read.write() = new_state;
                     Self::update_title_label(&title_label, new_state);
                     list_model.write().set_read(&article_id, new_state);
-                    let update = ReadUpdate {
-                        article_id: article_id.clone(),
-                        read: new_state,
-                    };
-                    Util::send(&sender, Action::MarkArticleRead(update));
+                    Util::send(&sender, Action::SetArticlesRead(vec![article_id.clone()], new_state));
                     Inhibit(true)
                 }))
                 .to_glib() as usize,
@@ -336,12 +406,7 @@ impl ArticleRow {
                     let new_marked = marked.read().invert();
                     *marked.write() = new_marked;
                     list_model.write().set_marked(&article_id, new_marked);
-
-                    let update = MarkUpdate {
-                        article_id: article_id.clone(),
-                        marked: new_marked,
-                    };
-                    Util::send(&sender, Action::MarkArticle(update));
+                    Util::send(&sender, Action::SetArticlesMarked(vec![article_id.clone()], new_marked));
                     Inhibit(true)
                 }))
                 .to_glib() as usize,
@@ -423,6 +488,33 @@ impl ArticleRow {
         vec
     }
 
+    /// Set `text` on `label` with the hits of the active search highlighted.
+    fn set_label_text(label: &Label, text: &str, search_terms: &[String]) {
+        if search_terms.is_empty() {
+            return label.set_text(text);
+        }
+
+        let context = label.get_style_context();
+        let hex = |name: &str| {
+            context.lookup_color(name).map(|color| {
+                format!(
+                    "#{:02x}{:02x}{:02x}",
+                    (color.red * 255.0).round() as u8,
+                    (color.green * 255.0).round() as u8,
+                    (color.blue * 255.0).round() as u8
+                )
+            })
+        };
+        let span = match (hex("search-highlight-bg"), hex("search-highlight-fg")) {
+            (Some(background), Some(foreground)) => search_query::highlight_span(&background, &foreground),
+            _ => "<span weight=\"bold\">".to_owned(),
+        };
+        match search_query::highlight_markup(text, search_terms, &span) {
+            Some(markup) => label.set_markup(&markup),
+            None => label.set_text(text),
+        }
+    }
+
     fn update_title_label(title_label: &Label, read: Read) {
         let context = title_label.get_style_context();
         match read {
@@ -457,5 +549,14 @@ impl Drop for ArticleRow {
         for (signal_id, widget) in &self.connected_signals {
             GtkUtil::disconnect_signal(Some(*signal_id), widget);
         }
+
+        // keep the height of the row so the list doesn't jump around once the content is gone
+        let height = self.widget.get_allocated_height();
+        if height > 1 {
+            self.widget.set_size_request(-1, height);
+        }
+        if let Some(content) = self.widget.get_child() {
+            self.widget.remove(&content);
+        }
     }
 }
@@ -11,11 +11,15 @@ use crate::settings::Settings;
 use crate::sidebar::models::SidebarSelection;
 use crate::util::{BuilderHelper, GtkUtil, Util};
 use gdk::RGBA;
-use glib::{clone, source::Continue, translate::ToGlib, Sender};
-use gtk::{Label, LabelExt, ListBoxExt, ListBoxRowExt, ScrolledWindow, Stack, StackExt, StackTransitionType};
+use gio::{ActionMapExt, Menu, MenuItem, SimpleAction, SimpleActionGroup};
+use glib::{clone, source::Continue, translate::ToGlib, Sender, ToVariant, VariantTy};
+use gtk::{
+    Box, Button, ButtonExt, Label, LabelExt, ListBoxExt, ListBoxRowExt, MenuButton, MenuButtonExt, Revealer,
+    RevealerExt, ScrolledWindow, SelectionMode, Stack, StackExt, StackTransitionType, ToggleButtonExt, WidgetExt,
+};
 use models::ArticleListChangeSet;
-pub use models::{ArticleListArticleModel, ArticleListModel, MarkUpdate, ReadUpdate};
-use news_flash::models::{ArticleID, Marked, Read};
+pub use models::{ArticleListArticleModel, ArticleListGrouping, ArticleListModel, ArticleListSort, ReadUpdate};
+use news_flash::models::{ArticleID, Marked, Read, Tag, TagID};
 use parking_lot::RwLock;
 use single::SingleArticleList;
 use std::sync::Arc;
@@ -29,6 +33,7 @@ pub enum CurrentList {
 
 pub struct ArticleList {
     sender: Sender<Action>,
+    layout: Box,
     stack: Stack,
     list_1: Arc<RwLock<SingleArticleList>>,
     list_2: Arc<RwLock<SingleArticleList>>,
@@ -39,6 +44,7 @@ pub struct ArticleList {
     current_list: Arc<RwLock<CurrentList>>,
     settings: Arc<RwLock<Settings>>,
     empty_label: Label,
+    selection_tag_menu: Menu,
 }
 
 impl ArticleList {
@@ -49,34 +55,47 @@ impl ArticleList {
         sender: Sender<Action>,
     ) -> Self {
         let builder = BuilderHelper::new("article_list");
+        let layout = builder.get::<Box>("article_list_layout");
         let stack = builder.get::<Stack>("article_list_stack");
         let empty_scroll = builder.get::<ScrolledWindow>("empty_scroll");
         let empty_label = builder.get::<Label>("empty_label");
 
-        let list_1 = SingleArticleList::new(sender.clone(), content_header.clone());
-        let list_2 = SingleArticleList::new(sender.clone(), content_header.clone());
+        let model = Arc::new(RwLock::new(ArticleListModel::new(
+            &settings.read().get_article_list_order(),
+            ArticleListSort::default(),
+            ArticleListGrouping::default(),
+        )));
+
+        let list_1 = SingleArticleList::new(sender.clone(), content_header.clone(), &model, global_state, settings);
+        let list_2 = SingleArticleList::new(sender.clone(), content_header.clone(), &model, global_state, settings);
 
         let local_state = MainWindowState::new();
-        let model = ArticleListModel::new(&settings.read().get_article_list_order());
 
         stack.add_named(&list_1.widget(), "list_1");
         stack.add_named(&list_2.widget(), "list_2");
         stack.add_named(&empty_scroll, "empty");
 
         let settings = settings.clone();
+        let list_1 = Arc::new(RwLock::new(list_1));
+        let list_2 = Arc::new(RwLock::new(list_2));
+        let current_list = Arc::new(RwLock::new(CurrentList::List1));
+        let selection_tag_menu = Self::setup_selection_bar(&builder, &sender, &list_1, &list_2, &current_list);
+        Self::setup_row_actions(&layout, &sender);
 
         let mut article_list = ArticleList {
             sender,
+            layout,
             stack,
-            list_1: Arc::new(RwLock::new(list_1)),
-            list_2: Arc::new(RwLock::new(list_2)),
-            list_model: Arc::new(RwLock::new(model)),
+            list_1,
+            list_2,
+            list_model: model,
             list_activate_signal: None,
             local_state,
             global_state: global_state.clone(),
-            current_list: Arc::new(RwLock::new(CurrentList::List1)),
+            current_list,
             settings,
             empty_label,
+            selection_tag_menu,
         };
 
         article_list.setup_list_selected_singal();
@@ -84,8 +103,196 @@ impl ArticleList {
         article_list
     }
 
-    pub fn widget(&self) -> gtk::Stack {
-        self.stack.clone()
+    pub fn widget(&self) -> gtk::Box {
+        self.layout.clone()
+    }
+
+    fn setup_selection_bar(
+        builder: &BuilderHelper,
+        sender: &Sender<Action>,
+        list_1: &Arc<RwLock<SingleArticleList>>,
+        list_2: &Arc<RwLock<SingleArticleList>>,
+        current_list: &Arc<RwLock<CurrentList>>,
+    ) -> Menu {
+        let revealer = builder.get::<Revealer>("selection_revealer");
+        let label = builder.get::<Label>("selection_label");
+        let read_button = builder.get::<Button>("selection_read_button");
+        let unread_button = builder.get::<Button>("selection_unread_button");
+        let mark_button = builder.get::<Button>("selection_mark_button");
+        let unmark_button = builder.get::<Button>("selection_unmark_button");
+        let tag_button = builder.get::<MenuButton>("selection_tag_button");
+        let export_button = builder.get::<Button>("selection_export_button");
+        let open_button = builder.get::<Button>("selection_open_button");
+        let cancel_button = builder.get::<Button>("selection_cancel_button");
+
+        for list in &[list_1, list_2] {
+            list.read().list().connect_selected_rows_changed(clone!(
+                @weak revealer,
+                @weak label => @default-panic, move |list|
+            {
+                if list.get_selection_mode() != SelectionMode::Multiple {
+                    revealer.set_reveal_child(false);
+                    return;
+                }
+
+                let count = list.get_selected_rows().len();
+                if count == 0 {
+                    list.set_selection_mode(SelectionMode::Browse);
+                    list.set_activate_on_single_click(true);
+                }
+                revealer.set_reveal_child(count > 0);
+                label.set_text(&i18n_f("{} selected", &[&count.to_string()]));
+            }));
+        }
+
+        read_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::SetSelectedArticlesRead(Read::Read));
+        }));
+        unread_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::SetSelectedArticlesRead(Read::Unread));
+        }));
+        mark_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::SetSelectedArticlesMarked(Marked::Marked));
+        }));
+        unmark_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::SetSelectedArticlesMarked(Marked::Unmarked));
+        }));
+        export_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::ExportSelectedArticles);
+        }));
+        open_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::OpenSelectedArticlesInBrowser);
+        }));
+        let (list_1, list_2, current_list) = (list_1.clone(), list_2.clone(), current_list.clone());
+        cancel_button.connect_clicked(clone!(
+            @weak list_1,
+            @weak list_2,
+            @weak current_list => @default-panic, move |_button|
+        {
+            match *current_list.read() {
+                CurrentList::List1 => list_1.read().leave_selection_mode(),
+                CurrentList::List2 => list_2.read().leave_selection_mode(),
+                CurrentList::Empty => {}
+            }
+        }));
+
+        let string_type = VariantTy::new("s").expect("Invalid variant type");
+        let tag_action = SimpleAction::new("tag", Some(string_type));
+        tag_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, parameter| {
+            if let Some(tag_id) = parameter.and_then(|parameter| parameter.get_str()) {
+                Util::send(&sender, Action::TagSelectedArticles(TagID::new(tag_id)));
+            }
+        }));
+        let untag_action = SimpleAction::new("untag", Some(string_type));
+        untag_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, parameter| {
+            if let Some(tag_id) = parameter.and_then(|parameter| parameter.get_str()) {
+                Util::send(&sender, Action::UntagSelectedArticles(TagID::new(tag_id)));
+            }
+        }));
+        let action_group = SimpleActionGroup::new();
+        action_group.add_action(&tag_action);
+        action_group.add_action(&untag_action);
+        tag_button.insert_action_group("selection", Some(&action_group));
+
+        let tag_menu = Menu::new();
+        tag_button.set_menu_model(Some(&tag_menu));
+        tag_button.connect_toggled(clone!(@strong sender => @default-panic, move |button| {
+            if button.get_active() {
+                Util::send(&sender, Action::UpdateSelectionTags);
+            }
+        }));
+
+        tag_menu
+    }
+
+    fn setup_row_actions(layout: &Box, sender: &Sender<Action>) {
+        let string_type = VariantTy::new("s").expect("Invalid variant type");
+        let mark_above_action = SimpleAction::new("mark-above-read", Some(string_type));
+        mark_above_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, parameter| {
+            if let Some(article_id) = parameter.and_then(|parameter| parameter.get_str()) {
+                Util::send(&sender, Action::MarkArticlesAboveRead(ArticleID::new(article_id)));
+            }
+        }));
+        let mark_below_action = SimpleAction::new("mark-below-read", Some(string_type));
+        mark_below_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, parameter| {
+            if let Some(article_id) = parameter.and_then(|parameter| parameter.get_str()) {
+                Util::send(&sender, Action::MarkArticlesBelowRead(ArticleID::new(article_id)));
+            }
+        }));
+        let open_in_tab_action = SimpleAction::new("open-in-tab", Some(string_type));
+        open_in_tab_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, parameter| {
+            if let Some(article_id) = parameter.and_then(|parameter| parameter.get_str()) {
+                Util::send(&sender, Action::OpenArticleInTab(ArticleID::new(article_id)));
+            }
+        }));
+        let open_in_window_action = SimpleAction::new("open-in-window", Some(string_type));
+        open_in_window_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, parameter| {
+            if let Some(article_id) = parameter.and_then(|parameter| parameter.get_str()) {
+                Util::send(&sender, Action::OpenArticleInWindow(ArticleID::new(article_id)));
+            }
+        }));
+        let action_group = SimpleActionGroup::new();
+        action_group.add_action(&mark_above_action);
+        action_group.add_action(&mark_below_action);
+        action_group.add_action(&open_in_tab_action);
+        action_group.add_action(&open_in_window_action);
+        layout.insert_action_group("article-list", Some(&action_group));
+    }
+
+    /// Fill the tag menu of the selection bar with the tags that can be added to or removed from the selection.
+    pub fn set_selection_tags(&self, tags: &[Tag]) {
+        let add_model = Menu::new();
+        let remove_model = Menu::new();
+        for tag in tags {
+            let tag_id = tag.tag_id.to_string().to_variant();
+
+            let add_item = MenuItem::new(Some(&tag.label), None);
+            add_item.set_action_and_target_value(Some("selection.tag"), Some(&tag_id));
+            add_model.append_item(&add_item);
+
+            let remove_item = MenuItem::new(Some(&tag.label), None);
+            remove_item.set_action_and_target_value(Some("selection.untag"), Some(&tag_id));
+            remove_model.append_item(&remove_item);
+        }
+
+        self.selection_tag_menu.remove_all();
+        if tags.is_empty() {
+            self.selection_tag_menu.append(Some(&i18n("No Tags")), None);
+        } else {
+            self.selection_tag_menu
+                .append_submenu(Some(&i18n("Add Tag")), &add_model);
+            self.selection_tag_menu
+                .append_submenu(Some(&i18n("Remove Tag")), &remove_model);
+        }
+    }
+
+    pub fn get_selected_articles(&self) -> Vec<ArticleListArticleModel> {
+        match self.get_current_list() {
+            Some(current_list) => {
+                let list_model = self.list_model.read();
+                current_list
+                    .read()
+                    .get_selected_indices()
+                    .iter()
+                    .filter_map(|index| list_model.calculate_selection(*index).cloned())
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    pub fn unread_above(&self, article_id: &ArticleID) -> Vec<ArticleID> {
+        self.list_model.read().unread_above(article_id)
+    }
+
+    pub fn unread_below(&self, article_id: &ArticleID) -> Vec<ArticleID> {
+        self.list_model.read().unread_below(article_id)
+    }
+
+    pub fn leave_selection_mode(&self) {
+        if let Some(current_list) = self.get_current_list() {
+            current_list.read().leave_selection_mode();
+        }
     }
 
     pub fn get_background_color(&self) -> RGBA {
@@ -102,7 +309,11 @@ impl ArticleList {
             CurrentList::List2 | CurrentList::Empty => CurrentList::List1,
         };
         *self.current_list.write() = current_list;
-        let mut empty_model = ArticleListModel::new(&self.settings.read().get_article_list_order());
+        let mut empty_model = ArticleListModel::new(
+            &self.settings.read().get_article_list_order(),
+            new_list.sort_by(),
+            new_list.grouping(),
+        );
         let diff = empty_model.generate_diff(&mut new_list);
 
         self.execute_diff(diff);
@@ -115,6 +326,14 @@ impl ArticleList {
     pub fn update(&mut self, mut new_list: ArticleListModel, new_state: &Arc<RwLock<MainWindowState>>) {
         self.stack.set_transition_type(self.calc_transition_type(new_state));
 
+        let grouping = self
+            .settings
+            .read()
+            .get_article_list_view(new_state.read().get_sidebar_selection())
+            .grouping;
+        self.list_1.read().set_grouping(grouping);
+        self.list_2.read().set_grouping(grouping);
+
         // check if list model is empty and display a message
         if new_list.len() == 0 {
             self.empty_label.set_label(&self.compose_empty_message(new_state));
@@ -157,25 +376,20 @@ impl ArticleList {
     }
 
     pub fn add_more_articles(&mut self, new_list: ArticleListModel) {
-        let list = match *self.current_list.read() {
-            CurrentList::List1 => &mut self.list_1,
-            CurrentList::List2 => &mut self.list_2,
-            CurrentList::Empty => return,
-        };
+        if *self.current_list.read() == CurrentList::Empty {
+            return;
+        }
 
-        for model in new_list.models() {
-            if self.list_model.write().add_model(model.clone()).is_ok() {
-                gtk::idle_add(clone!(
-                    @weak self.global_state as global_state,
-                    @weak self.list_model as list_model,
-                    @strong list,
-                    @strong model => @default-panic, move ||
-                {
-                    list.write().add(&model, -1, &list_model, &global_state);
-                    Continue(false)
-                }));
-            }
+        // depending on the sort order the new articles don't necessarily end up at the bottom of the list
+        let mut merged_list = self.list_model.read().merge(&new_list);
+        {
+            let old_list = self.list_model.clone();
+            let mut old_list = old_list.write();
+            let list_diff = old_list.generate_diff(&mut merged_list);
+            self.execute_diff(list_diff);
         }
+
+        *self.list_model.write() = merged_list;
     }
 
     fn execute_diff(&self, diff: Vec<ArticleListChangeSet>) {
@@ -184,10 +398,21 @@ impl ArticleList {
             CurrentList::List2 => &self.list_2,
         };
 
+        // detach all rows that change their position first
+        // this way the positions of all following insertions refer to the final order of the list
+        for diff in &diff {
+            if let ArticleListChangeSet::Move(id, _pos) = diff {
+                list.write().detach(id);
+            }
+        }
+
         for diff in diff {
             match diff {
                 ArticleListChangeSet::Add(article, pos) => {
-                    list.write().add(article, pos, &self.list_model, &self.global_state);
+                    list.write().add(article, pos);
+                }
+                ArticleListChangeSet::Move(id, pos) => {
+                    list.write().attach(&id, pos);
                 }
                 ArticleListChangeSet::Remove(id) => {
                     list.write().remove(id.clone());
@@ -246,7 +471,7 @@ impl ArticleList {
                 @weak self.list_2 as list_2 => @default-panic, move |_list, row|
             {
                 let selected_index = row.get_index();
-                let selected_article = list_model.write().calculate_selection(selected_index).cloned();
+                let selected_article = list_model.read().calculate_selection(selected_index).cloned();
                 if let Some(selected_article) = selected_article {
                     if selected_article.read == Read::Unread && !global_state.read().get_offline() {
                         let update = ReadUpdate {
@@ -270,8 +495,14 @@ impl ArticleList {
     }
 
     fn require_new_list(&self, new_state: &RwLock<MainWindowState>) -> bool {
+        let view_settings = self
+            .settings
+            .read()
+            .get_article_list_view(new_state.read().get_sidebar_selection());
         if self.local_state == *new_state.read()
             && self.settings.read().get_article_list_order() == self.list_model.read().order()
+            && view_settings.sort == self.list_model.read().sort_by()
+            && view_settings.grouping == self.list_model.read().grouping()
             && *self.current_list.read() != CurrentList::Empty
         {
             return false;
@@ -316,6 +547,20 @@ impl ArticleList {
                     None => i18n("No starred articles"),
                 },
             },
+            SidebarSelection::Podcasts => match new_state.read().get_header_selection() {
+                HeaderSelection::All => match new_state.read().get_search_term() {
+                    Some(search) => i18n_f("No podcast episodes that fit \"{}\"", &[&search]),
+                    None => i18n("No podcast episodes"),
+                },
+                HeaderSelection::Unread => match new_state.read().get_search_term() {
+                    Some(search) => i18n_f("No unread podcast episodes that fit \"{}\"", &[&search]),
+                    None => i18n("No unread podcast episodes"),
+                },
+                HeaderSelection::Marked => match new_state.read().get_search_term() {
+                    Some(search) => i18n_f("No starred podcast episodes that fit \"{}\"", &[&search]),
+                    None => i18n("No starred podcast episodes"),
+                },
+            },
             SidebarSelection::Category(_id, title) => match new_state.read().get_header_selection() {
                 HeaderSelection::All => match new_state.read().get_search_term() {
                     Some(search) => i18n_f("No articles that fit \"{}\" in category \"{}\"", &[&search, &title]),
@@ -364,6 +609,26 @@ impl ArticleList {
                     None => i18n_f("No starred articles in tag \"{}\"", &[&title]),
                 },
             },
+            SidebarSelection::SavedSearch(_id, title) => match new_state.read().get_header_selection() {
+                HeaderSelection::All => match new_state.read().get_search_term() {
+                    Some(search) => i18n_f("No articles that fit \"{}\" in search \"{}\"", &[&search, &title]),
+                    None => i18n_f("No articles in search \"{}\"", &[&title]),
+                },
+                HeaderSelection::Unread => match new_state.read().get_search_term() {
+                    Some(search) => i18n_f(
+                        "No unread articles that fit \"{}\" in search \"{}\"",
+                        &[&search, &title],
+                    ),
+                    None => i18n_f("No unread articles in search \"{}\"", &[&title]),
+                },
+                HeaderSelection::Marked => match new_state.read().get_search_term() {
+                    Some(search) => i18n_f(
+                        "No starred articles that fit \"{}\" in search \"{}\"",
+                        &[&search, &title],
+                    ),
+                    None => i18n_f("No starred articles in search \"{}\"", &[&title]),
+                },
+            },
         }
     }
 
@@ -387,10 +652,10 @@ impl ArticleList {
         if let Some(current_list) = self.get_current_list() {
             let selected_index = current_list.read().get_selected_index();
             if let Some(selected_index) = selected_index {
-                let selected_row = self.list_model.write().calculate_selection(selected_index).cloned();
+                let selected_row = self.list_model.read().calculate_selection(selected_index).cloned();
                 let next_row = self
                     .list_model
-                    .write()
+                    .read()
                     .calculate_selection(selected_index + direction)
                     .cloned();
 
@@ -403,12 +668,34 @@ impl ArticleList {
                     }
                 }
             } else {
-                let first_row = self.list_model.write().first().cloned();
+                self.jump_to_position(0);
+            }
+        }
+    }
 
-                if let Some(first_row) = first_row {
-                    current_list.read().select_after(&first_row.id, 300);
-                    current_list.read().animate_scroll_absolute(0.0);
-                }
+    /// Select the article with `article_id` and scroll it into view, `false` if it isn't part of the list.
+    pub fn select_article_by_id(&self, article_id: &ArticleID) -> bool {
+        if !self.list_model.read().contains(article_id) {
+            return false;
+        }
+        match self.get_current_list() {
+            Some(current_list) => {
+                current_list.read().select_after(article_id, 300);
+                current_list.read().scroll_to_article(article_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Select the article at `position` and scroll it into view.
+    /// The content of the rows passed on the way is only realized if they stay visible.
+    pub fn jump_to_position(&self, position: i32) {
+        if let Some(current_list) = self.get_current_list() {
+            let article = self.list_model.read().calculate_selection(position).cloned();
+            if let Some(article) = article {
+                current_list.read().select_after(&article.id, 300);
+                current_list.read().scroll_to_article(&article.id);
             }
         }
     }
@@ -417,7 +704,7 @@ impl ArticleList {
         if let Some(current_list) = self.get_current_list() {
             let selected_index = current_list.read().get_selected_index();
             if let Some(selected_index) = selected_index {
-                let selected_row = self.list_model.write().calculate_selection(selected_index).cloned();
+                let selected_row = self.list_model.read().calculate_selection(selected_index).cloned();
 
                 if let Some(selected_row) = selected_row {
                     return Some(selected_row);
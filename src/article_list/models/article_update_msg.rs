@@ -1,4 +1,4 @@
-use news_flash::models::{ArticleID, Marked, Read};
+use news_flash::models::{ArticleID, Read};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -6,9 +6,3 @@ pub struct ReadUpdate {
     pub article_id: ArticleID,
     pub read: Read,
 }
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MarkUpdate {
-    pub article_id: ArticleID,
-    pub marked: Marked,
-}
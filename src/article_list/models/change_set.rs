@@ -7,6 +7,7 @@ use std::fmt;
 pub enum ArticleListChangeSet<'a> {
     Remove(ArticleID),
     Add(&'a ArticleListArticleModel, i32), // pos
+    Move(ArticleID, i32),                  // pos
     UpdateRead(ArticleID, Read),
     UpdateMarked(ArticleID, Marked),
     UpdateDateString(ArticleID, NaiveDateTime),
@@ -23,6 +24,10 @@ impl<'a> PartialEq for ArticleListChangeSet<'a> {
                 ArticleListChangeSet::Add(other_model, other_pos) => model.id == other_model.id && pos == other_pos,
                 _ => false,
             },
+            ArticleListChangeSet::Move(id, pos) => match other {
+                ArticleListChangeSet::Move(other_id, other_pos) => id == other_id && pos == other_pos,
+                _ => false,
+            },
             ArticleListChangeSet::UpdateRead(id, read) => match other {
                 ArticleListChangeSet::UpdateRead(other_id, other_read) => id == other_id && read == other_read,
                 _ => false,
@@ -44,6 +49,7 @@ impl<'a> fmt::Display for ArticleListChangeSet<'a> {
         match self {
             ArticleListChangeSet::Add(model, pos) => write!(f, "Add id='{}' pos='{}'", model.id, pos),
             ArticleListChangeSet::Remove(id) => write!(f, "Remove id='{}'", id),
+            ArticleListChangeSet::Move(id, pos) => write!(f, "Move id='{}' pos='{}'", id, pos),
             ArticleListChangeSet::UpdateMarked(id, marked) => {
                 write!(f, "UpdateMarked id='{}' marked='{:?}'", id, marked)
             }
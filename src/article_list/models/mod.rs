@@ -2,31 +2,39 @@ mod article;
 mod article_update_msg;
 mod change_set;
 mod error;
+mod sort;
 
 use crate::content_page::HeaderSelection;
+use crate::util::DateUtil;
 pub use article::ArticleListArticleModel;
-pub use article_update_msg::{MarkUpdate, ReadUpdate};
+pub use article_update_msg::ReadUpdate;
 pub use change_set::ArticleListChangeSet;
 use chrono::{Duration, NaiveDate, Utc};
 use error::{ArticleListModelError, ArticleListModelErrorKind};
 use log::warn;
 use news_flash::models::{Article, ArticleID, ArticleOrder, Feed, Marked, Read};
-use std::collections::HashSet;
+pub use sort::{ArticleListGrouping, ArticleListSort};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ArticleListModel {
     models: Vec<ArticleListArticleModel>,
     ids: HashSet<ArticleID>,
     sort: ArticleOrder,
+    sort_by: ArticleListSort,
+    grouping: ArticleListGrouping,
     created: NaiveDate,
 }
 
 impl ArticleListModel {
-    pub fn new(sort: &ArticleOrder) -> Self {
+    pub fn new(sort: &ArticleOrder, sort_by: ArticleListSort, grouping: ArticleListGrouping) -> Self {
         ArticleListModel {
             models: Vec::new(),
             ids: HashSet::new(),
             sort: sort.clone(),
+            sort_by,
+            grouping,
             created: Utc::now().naive_utc().date(),
         }
     }
@@ -35,6 +43,14 @@ impl ArticleListModel {
         self.sort.clone()
     }
 
+    pub fn sort_by(&self) -> ArticleListSort {
+        self.sort_by
+    }
+
+    pub fn grouping(&self) -> ArticleListGrouping {
+        self.grouping
+    }
+
     pub fn add(&mut self, article: Article, feed: &Feed) -> Result<(), ArticleListModelError> {
         if self.contains(&article.article_id) {
             warn!("Listmodel already contains id {}", article.article_id);
@@ -59,6 +75,13 @@ impl ArticleListModel {
         self.ids.contains(article_id)
     }
 
+    pub fn get(&self, article_id: &ArticleID) -> Option<&ArticleListArticleModel> {
+        if !self.contains(article_id) {
+            return None;
+        }
+        self.models.iter().find(|model| &model.id == article_id)
+    }
+
     pub fn len(&self) -> usize {
         self.models.len()
     }
@@ -97,107 +120,158 @@ impl ArticleListModel {
 
     pub fn generate_diff<'a>(&'a mut self, new_list: &'a mut ArticleListModel) -> Vec<ArticleListChangeSet> {
         let mut diff: Vec<ArticleListChangeSet> = Vec::new();
-        let mut list_pos = 0;
-        let mut old_index = 0;
-        let mut new_index = 0;
-        self.sort();
+        // the old list keeps the order it was built with, which is the order of the rows on screen
+        // even if the read or marked state of its articles changed since
         new_list.sort();
-        let old_items = &mut self.models;
-        let new_items = &mut new_list.models;
-        let new_articles = &mut new_list.ids;
+        let old_items = &self.models;
+        let new_items = &new_list.models;
+        let new_articles = &new_list.ids;
 
         let new_day = new_list.created > self.created;
 
-        loop {
-            let old_item = old_items.get(old_index);
-            let new_item = new_items.get(new_index);
+        let old_positions: HashMap<&ArticleID, usize> = old_items
+            .iter()
+            .enumerate()
+            .map(|(pos, model)| (&model.id, pos))
+            .collect();
 
-            // iterated through both lists -> done
-            if old_item.is_none() && new_item.is_none() {
-                break;
+        // remove all items that are not part of the new list anymore
+        for old_model in old_items {
+            if !new_articles.contains(&old_model.id) {
+                diff.push(ArticleListChangeSet::Remove(old_model.id.clone()));
             }
+        }
 
-            // add all items after old_items ran out of items to compare
-            if let Some(new_model) = new_item {
-                if old_item.is_none() {
-                    new_index += 1;
-                    diff.push(ArticleListChangeSet::Add(&new_model, list_pos));
-                    list_pos += 1;
+        // Walk the new list once: items that keep their relative order stay where they are,
+        // all others are moved. Positions always refer to the final order of the list.
+        let mut last_kept_pos: Option<usize> = None;
+        for (list_pos, new_model) in new_items.iter().enumerate() {
+            let old_pos = match old_positions.get(&new_model.id) {
+                Some(old_pos) => *old_pos,
+                None => {
+                    diff.push(ArticleListChangeSet::Add(new_model, list_pos as i32));
                     continue;
                 }
-            }
+            };
 
-            // remove all items after new_items ran out of items to compare
-            if let Some(old_model) = old_item {
-                if new_item.is_none() {
-                    diff.push(ArticleListChangeSet::Remove(old_model.id.clone()));
-                    old_index += 1;
-                    continue;
+            match last_kept_pos {
+                Some(last_pos) if old_pos < last_pos => {
+                    diff.push(ArticleListChangeSet::Move(new_model.id.clone(), list_pos as i32));
                 }
+                _ => last_kept_pos = Some(old_pos),
             }
 
-            if let Some(old_model) = old_item {
-                if let Some(new_model) = new_item {
-                    // still the same item -> check for read & marked state
-                    if new_model == old_model {
-                        if new_model.read != old_model.read {
-                            diff.push(ArticleListChangeSet::UpdateRead(new_model.id.clone(), new_model.read));
-                        }
-                        if new_model.marked != old_model.marked {
-                            diff.push(ArticleListChangeSet::UpdateMarked(
-                                new_model.id.clone(),
-                                new_model.marked,
-                            ));
-                        }
-                        // check if it is a new day and if the date was formated as 'today'/'yesterday'
-                        if new_day && self.created - old_model.date.date() <= Duration::days(1) {
-                            diff.push(ArticleListChangeSet::UpdateDateString(
-                                new_model.id.clone(),
-                                new_model.date,
-                            ));
-                        }
-                        list_pos += 1;
-                        old_index += 1;
-                        new_index += 1;
-                        continue;
-                    }
-
-                    if new_articles.contains(&old_model.id) {
-                        diff.push(ArticleListChangeSet::Add(&new_model, list_pos));
-                        list_pos += 1;
-                        new_index += 1;
-                        continue;
-                    }
-
-                    // items differ -> remove old item and move on
-                    diff.push(ArticleListChangeSet::Remove(old_model.id.clone()));
-                    old_index += 1;
-                    continue;
-                }
+            // still the same item -> check for read & marked state
+            let old_model = &old_items[old_pos];
+            if new_model.read != old_model.read {
+                diff.push(ArticleListChangeSet::UpdateRead(new_model.id.clone(), new_model.read));
+            }
+            if new_model.marked != old_model.marked {
+                diff.push(ArticleListChangeSet::UpdateMarked(
+                    new_model.id.clone(),
+                    new_model.marked,
+                ));
+            }
+            // check if it is a new day and if the date was formated as 'today'/'yesterday'
+            if (new_day && self.created - old_model.date.date() <= Duration::days(1))
+                || new_model.date != old_model.date
+            {
+                diff.push(ArticleListChangeSet::UpdateDateString(
+                    new_model.id.clone(),
+                    new_model.date,
+                ));
             }
         }
 
         diff
     }
 
+    /// Sort by group first and by `sort_by` within each group, so every group header shows up only once.
     fn sort(&mut self) {
-        match self.sort {
-            ArticleOrder::OldestFirst => {
-                self.models.sort_by(|a, b| a.date.cmp(&b.date));
+        let order = self.sort.clone();
+        let by_date = move |a: &ArticleListArticleModel, b: &ArticleListArticleModel| match order {
+            ArticleOrder::OldestFirst => a.date.cmp(&b.date),
+            ArticleOrder::NewestFirst => a.date.cmp(&b.date).reverse(),
+        };
+        let by_feed = |a: &ArticleListArticleModel, b: &ArticleListArticleModel| {
+            a.feed_title.to_lowercase().cmp(&b.feed_title.to_lowercase())
+        };
+        let group_order = self.sort.clone();
+        let grouping = self.grouping;
+        let by_group = move |a: &ArticleListArticleModel, b: &ArticleListArticleModel| match grouping {
+            ArticleListGrouping::None => Ordering::Equal,
+            ArticleListGrouping::Date => {
+                let ordering = DateUtil::local_date(&a.date).cmp(&DateUtil::local_date(&b.date));
+                match group_order {
+                    ArticleOrder::OldestFirst => ordering,
+                    ArticleOrder::NewestFirst => ordering.reverse(),
+                }
             }
-            ArticleOrder::NewestFirst => {
-                self.models.sort_by(|a, b| a.date.cmp(&b.date).reverse());
+            ArticleListGrouping::Feed => by_feed(a, b),
+        };
+
+        match self.sort_by {
+            ArticleListSort::Date => self.models.sort_by(|a, b| by_group(a, b).then_with(|| by_date(a, b))),
+            ArticleListSort::Feed => self
+                .models
+                .sort_by(|a, b| by_group(a, b).then_with(|| by_feed(a, b)).then_with(|| by_date(a, b))),
+            ArticleListSort::Title => self.models.sort_by(|a, b| {
+                by_group(a, b)
+                    .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+                    .then_with(|| by_date(a, b))
+            }),
+            ArticleListSort::UnreadFirst => self.models.sort_by(|a, b| {
+                by_group(a, b)
+                    .then_with(|| (a.read == Read::Read).cmp(&(b.read == Read::Read)))
+                    .then_with(|| by_date(a, b))
+            }),
+            ArticleListSort::StarredFirst => self.models.sort_by(|a, b| {
+                by_group(a, b)
+                    .then_with(|| (a.marked == Marked::Unmarked).cmp(&(b.marked == Marked::Unmarked)))
+                    .then_with(|| by_date(a, b))
+            }),
+        }
+    }
+
+    /// Merge the models of `other` into a copy of this list.
+    pub fn merge(&self, other: &ArticleListModel) -> ArticleListModel {
+        let mut merged = self.clone();
+        for model in other.models() {
+            if !merged.contains(&model.id) {
+                merged.ids.insert(model.id.clone());
+                merged.models.push(model.clone());
             }
         }
+        merged
     }
 
-    pub fn first(&mut self) -> Option<&ArticleListArticleModel> {
-        self.sort();
-        self.models.iter().next()
+    /// Unread articles listed above the article with `article_id`.
+    pub fn unread_above(&self, article_id: &ArticleID) -> Vec<ArticleID> {
+        match self.models.iter().position(|model| &model.id == article_id) {
+            Some(pos) => Self::unread_ids(&self.models[..pos]),
+            None => Vec::new(),
+        }
+    }
+
+    /// Unread articles listed below the article with `article_id`.
+    pub fn unread_below(&self, article_id: &ArticleID) -> Vec<ArticleID> {
+        match self.models.iter().position(|model| &model.id == article_id) {
+            Some(pos) => Self::unread_ids(&self.models[pos + 1..]),
+            None => Vec::new(),
+        }
+    }
+
+    fn unread_ids(models: &[ArticleListArticleModel]) -> Vec<ArticleID> {
+        models
+            .iter()
+            .filter(|model| model.read == Read::Unread)
+            .map(|model| model.id.clone())
+            .collect()
     }
 
-    pub fn calculate_selection(&mut self, selected_index: i32) -> Option<&ArticleListArticleModel> {
-        self.sort();
+    /// The models are only sorted when a new list is built. This way the order stays in sync with the rows of the list
+    /// even if sorting by read or marked state and the state of an article changes in between.
+    pub fn calculate_selection(&self, selected_index: i32) -> Option<&ArticleListArticleModel> {
         if let Some((_index, article)) = self
             .models
             .iter()
@@ -209,3 +283,382 @@ impl ArticleListModel {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ArticleListArticleModel, ArticleListChangeSet, ArticleListGrouping, ArticleListModel, ArticleListSort,
+    };
+    use chrono::NaiveDate;
+    use news_flash::models::{ArticleID, ArticleOrder, Feed, FeedID, Marked, Read};
+
+    #[test]
+    fn articlelist_diff_move() {
+        let feed = Feed {
+            feed_id: FeedID::new("feed_1"),
+            label: "Feed 1".to_owned(),
+            feed_url: None,
+            icon_url: None,
+            sort_index: Some(0),
+            website: None,
+        };
+        let article_1 = ArticleListArticleModel {
+            id: ArticleID::new("article_1"),
+            title: "Article 1".to_owned(),
+            feed_id: feed.feed_id.clone(),
+            feed_title: feed.label.clone(),
+            date: NaiveDate::from_ymd(2020, 1, 3).and_hms(12, 0, 0),
+            summary: String::new(),
+            read: Read::Unread,
+            marked: Marked::Unmarked,
+            url: None,
+            news_flash_feed: feed.clone(),
+        };
+        let mut article_2 = ArticleListArticleModel {
+            id: ArticleID::new("article_2"),
+            date: NaiveDate::from_ymd(2020, 1, 2).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+        let article_3 = ArticleListArticleModel {
+            id: ArticleID::new("article_3"),
+            date: NaiveDate::from_ymd(2020, 1, 1).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+
+        let mut old_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        old_list
+            .add_model(article_1.clone())
+            .expect("Failed to add article_1 (old_list)");
+        old_list
+            .add_model(article_2.clone())
+            .expect("Failed to add article_2 (old_list)");
+        old_list
+            .add_model(article_3.clone())
+            .expect("Failed to add article_3 (old_list)");
+
+        let mut new_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        article_2.date = NaiveDate::from_ymd(2020, 1, 4).and_hms(12, 0, 0);
+        new_list
+            .add_model(article_1)
+            .expect("Failed to add article_1 (new_list)");
+        new_list
+            .add_model(article_2)
+            .expect("Failed to add article_2 (new_list)");
+        new_list
+            .add_model(article_3)
+            .expect("Failed to add article_3 (new_list)");
+
+        let diff = old_list.generate_diff(&mut new_list);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(
+            diff.get(0),
+            Some(&ArticleListChangeSet::UpdateDateString(
+                ArticleID::new("article_2"),
+                NaiveDate::from_ymd(2020, 1, 4).and_hms(12, 0, 0)
+            ))
+        );
+        assert_eq!(
+            diff.get(1),
+            Some(&ArticleListChangeSet::Move(ArticleID::new("article_1"), 1))
+        );
+    }
+
+    #[test]
+    fn articlelist_diff_add_remove_update() {
+        let feed = Feed {
+            feed_id: FeedID::new("feed_1"),
+            label: "Feed 1".to_owned(),
+            feed_url: None,
+            icon_url: None,
+            sort_index: Some(0),
+            website: None,
+        };
+        let mut article_1 = ArticleListArticleModel {
+            id: ArticleID::new("article_1"),
+            title: "Article 1".to_owned(),
+            feed_id: feed.feed_id.clone(),
+            feed_title: feed.label.clone(),
+            date: NaiveDate::from_ymd(2020, 1, 4).and_hms(12, 0, 0),
+            summary: String::new(),
+            read: Read::Unread,
+            marked: Marked::Unmarked,
+            url: None,
+            news_flash_feed: feed.clone(),
+        };
+        let article_2 = ArticleListArticleModel {
+            id: ArticleID::new("article_2"),
+            date: NaiveDate::from_ymd(2020, 1, 3).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+        let article_3 = ArticleListArticleModel {
+            id: ArticleID::new("article_3"),
+            date: NaiveDate::from_ymd(2020, 1, 2).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+        let article_4 = ArticleListArticleModel {
+            id: ArticleID::new("article_4"),
+            date: NaiveDate::from_ymd(2020, 1, 1).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+
+        let mut old_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        old_list
+            .add_model(article_1.clone())
+            .expect("Failed to add article_1 (old_list)");
+        old_list
+            .add_model(article_2)
+            .expect("Failed to add article_2 (old_list)");
+        old_list
+            .add_model(article_3.clone())
+            .expect("Failed to add article_3 (old_list)");
+
+        let mut new_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        article_1.read = Read::Read;
+        new_list
+            .add_model(article_1)
+            .expect("Failed to add article_1 (new_list)");
+        new_list
+            .add_model(article_3)
+            .expect("Failed to add article_3 (new_list)");
+        new_list
+            .add_model(article_4.clone())
+            .expect("Failed to add article_4 (new_list)");
+
+        let diff = old_list.generate_diff(&mut new_list);
+        assert_eq!(diff.len(), 3);
+        assert_eq!(
+            diff.get(0),
+            Some(&ArticleListChangeSet::Remove(ArticleID::new("article_2")))
+        );
+        assert_eq!(
+            diff.get(1),
+            Some(&ArticleListChangeSet::UpdateRead(
+                ArticleID::new("article_1"),
+                Read::Read
+            ))
+        );
+        assert_eq!(diff.get(2), Some(&ArticleListChangeSet::Add(&article_4, 2)));
+    }
+
+    #[test]
+    fn articlelist_diff_rotate() {
+        let feed = Feed {
+            feed_id: FeedID::new("feed_1"),
+            label: "Feed 1".to_owned(),
+            feed_url: None,
+            icon_url: None,
+            sort_index: Some(0),
+            website: None,
+        };
+        let mut article_1 = ArticleListArticleModel {
+            id: ArticleID::new("article_1"),
+            title: "Article 1".to_owned(),
+            feed_id: feed.feed_id.clone(),
+            feed_title: feed.label.clone(),
+            date: NaiveDate::from_ymd(2020, 1, 4).and_hms(12, 0, 0),
+            summary: String::new(),
+            read: Read::Unread,
+            marked: Marked::Unmarked,
+            url: None,
+            news_flash_feed: feed.clone(),
+        };
+        let article_2 = ArticleListArticleModel {
+            id: ArticleID::new("article_2"),
+            date: NaiveDate::from_ymd(2020, 1, 3).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+        let article_3 = ArticleListArticleModel {
+            id: ArticleID::new("article_3"),
+            date: NaiveDate::from_ymd(2020, 1, 2).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+
+        let mut old_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        old_list
+            .add_model(article_1.clone())
+            .expect("Failed to add article_1 (old_list)");
+        old_list
+            .add_model(article_2.clone())
+            .expect("Failed to add article_2 (old_list)");
+        old_list
+            .add_model(article_3.clone())
+            .expect("Failed to add article_3 (old_list)");
+
+        let mut new_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        article_1.date = NaiveDate::from_ymd(2020, 1, 1).and_hms(12, 0, 0);
+        new_list
+            .add_model(article_2)
+            .expect("Failed to add article_2 (new_list)");
+        new_list
+            .add_model(article_3)
+            .expect("Failed to add article_3 (new_list)");
+        new_list
+            .add_model(article_1)
+            .expect("Failed to add article_1 (new_list)");
+
+        let diff = old_list.generate_diff(&mut new_list);
+        let moves: Vec<&ArticleListChangeSet> = diff
+            .iter()
+            .filter(|change| matches!(change, ArticleListChangeSet::Move(_, _)))
+            .collect();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0], &ArticleListChangeSet::Move(ArticleID::new("article_1"), 2));
+    }
+
+    #[test]
+    fn articlelist_diff_unread_first() {
+        let feed = Feed {
+            feed_id: FeedID::new("feed_1"),
+            label: "Feed 1".to_owned(),
+            feed_url: None,
+            icon_url: None,
+            sort_index: Some(0),
+            website: None,
+        };
+        let article_1 = ArticleListArticleModel {
+            id: ArticleID::new("article_1"),
+            title: "Article 1".to_owned(),
+            feed_id: feed.feed_id.clone(),
+            feed_title: feed.label.clone(),
+            date: NaiveDate::from_ymd(2020, 1, 3).and_hms(12, 0, 0),
+            summary: String::new(),
+            read: Read::Unread,
+            marked: Marked::Unmarked,
+            url: None,
+            news_flash_feed: feed.clone(),
+        };
+        let article_2 = ArticleListArticleModel {
+            id: ArticleID::new("article_2"),
+            date: NaiveDate::from_ymd(2020, 1, 2).and_hms(12, 0, 0),
+            read: Read::Read,
+            ..article_1.clone()
+        };
+        let article_3 = ArticleListArticleModel {
+            id: ArticleID::new("article_3"),
+            date: NaiveDate::from_ymd(2020, 1, 1).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+
+        let mut old_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        old_list
+            .add_model(article_1.clone())
+            .expect("Failed to add article_1 (old_list)");
+        old_list
+            .add_model(article_2.clone())
+            .expect("Failed to add article_2 (old_list)");
+        old_list
+            .add_model(article_3.clone())
+            .expect("Failed to add article_3 (old_list)");
+
+        let mut new_list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::UnreadFirst,
+            ArticleListGrouping::None,
+        );
+        new_list
+            .add_model(article_1)
+            .expect("Failed to add article_1 (new_list)");
+        new_list
+            .add_model(article_2)
+            .expect("Failed to add article_2 (new_list)");
+        new_list
+            .add_model(article_3)
+            .expect("Failed to add article_3 (new_list)");
+
+        let diff = old_list.generate_diff(&mut new_list);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(
+            diff.get(0),
+            Some(&ArticleListChangeSet::Move(ArticleID::new("article_2"), 2))
+        );
+    }
+
+    #[test]
+    fn articlelist_unread_above_below() {
+        let feed = Feed {
+            feed_id: FeedID::new("feed_1"),
+            label: "Feed 1".to_owned(),
+            feed_url: None,
+            icon_url: None,
+            sort_index: Some(0),
+            website: None,
+        };
+        let article_1 = ArticleListArticleModel {
+            id: ArticleID::new("article_1"),
+            title: "Article 1".to_owned(),
+            feed_id: feed.feed_id.clone(),
+            feed_title: feed.label.clone(),
+            date: NaiveDate::from_ymd(2020, 1, 4).and_hms(12, 0, 0),
+            summary: String::new(),
+            read: Read::Unread,
+            marked: Marked::Unmarked,
+            url: None,
+            news_flash_feed: feed.clone(),
+        };
+        let article_2 = ArticleListArticleModel {
+            id: ArticleID::new("article_2"),
+            date: NaiveDate::from_ymd(2020, 1, 3).and_hms(12, 0, 0),
+            read: Read::Read,
+            ..article_1.clone()
+        };
+        let article_3 = ArticleListArticleModel {
+            id: ArticleID::new("article_3"),
+            date: NaiveDate::from_ymd(2020, 1, 2).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+        let article_4 = ArticleListArticleModel {
+            id: ArticleID::new("article_4"),
+            date: NaiveDate::from_ymd(2020, 1, 1).and_hms(12, 0, 0),
+            ..article_1.clone()
+        };
+
+        let mut list = ArticleListModel::new(
+            &ArticleOrder::NewestFirst,
+            ArticleListSort::Date,
+            ArticleListGrouping::None,
+        );
+        list.add_model(article_1).expect("Failed to add article_1");
+        list.add_model(article_2).expect("Failed to add article_2");
+        list.add_model(article_3).expect("Failed to add article_3");
+        list.add_model(article_4).expect("Failed to add article_4");
+
+        assert_eq!(
+            list.unread_above(&ArticleID::new("article_3")),
+            vec![ArticleID::new("article_1")]
+        );
+        assert_eq!(
+            list.unread_below(&ArticleID::new("article_2")),
+            vec![ArticleID::new("article_3"), ArticleID::new("article_4")]
+        );
+        assert!(list.unread_below(&ArticleID::new("article_4")).is_empty());
+        assert!(list.unread_above(&ArticleID::new("unknown")).is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::i18n::i18n;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ArticleListSort {
+    Date,
+    Feed,
+    Title,
+    UnreadFirst,
+    StarredFirst,
+}
+
+impl ArticleListSort {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            ArticleListSort::Date => "date",
+            ArticleListSort::Feed => "feed",
+            ArticleListSort::Title => "title",
+            ArticleListSort::UnreadFirst => "unread-first",
+            ArticleListSort::StarredFirst => "starred-first",
+        }
+    }
+
+    pub fn parse(sort: &str) -> Option<Self> {
+        match sort {
+            "date" => Some(ArticleListSort::Date),
+            "feed" => Some(ArticleListSort::Feed),
+            "title" => Some(ArticleListSort::Title),
+            "unread-first" => Some(ArticleListSort::UnreadFirst),
+            "starred-first" => Some(ArticleListSort::StarredFirst),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            ArticleListSort::Date => i18n("Date"),
+            ArticleListSort::Feed => i18n("Feed"),
+            ArticleListSort::Title => i18n("Title"),
+            ArticleListSort::UnreadFirst => i18n("Unread First"),
+            ArticleListSort::StarredFirst => i18n("Starred First"),
+        }
+    }
+
+    pub fn all() -> [Self; 5] {
+        [
+            ArticleListSort::Date,
+            ArticleListSort::Feed,
+            ArticleListSort::Title,
+            ArticleListSort::UnreadFirst,
+            ArticleListSort::StarredFirst,
+        ]
+    }
+}
+
+impl Default for ArticleListSort {
+    fn default() -> Self {
+        ArticleListSort::Date
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ArticleListGrouping {
+    None,
+    Date,
+    Feed,
+}
+
+impl ArticleListGrouping {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            ArticleListGrouping::None => "none",
+            ArticleListGrouping::Date => "date",
+            ArticleListGrouping::Feed => "feed",
+        }
+    }
+
+    pub fn parse(grouping: &str) -> Option<Self> {
+        match grouping {
+            "none" => Some(ArticleListGrouping::None),
+            "date" => Some(ArticleListGrouping::Date),
+            "feed" => Some(ArticleListGrouping::Feed),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            ArticleListGrouping::None => i18n("None"),
+            ArticleListGrouping::Date => i18n("Date"),
+            ArticleListGrouping::Feed => i18n("Feed"),
+        }
+    }
+
+    pub fn all() -> [Self; 3] {
+        [
+            ArticleListGrouping::None,
+            ArticleListGrouping::Date,
+            ArticleListGrouping::Feed,
+        ]
+    }
+}
+
+impl Default for ArticleListGrouping {
+    fn default() -> Self {
+        ArticleListGrouping::None
+    }
+}
@@ -1,28 +1,35 @@
 use super::article_row::ArticleRow;
 use super::models::ArticleListArticleModel;
+use super::models::ArticleListGrouping;
 use super::models::ArticleListModel;
 use crate::app::Action;
 use crate::article_list::ReadUpdate;
 use crate::content_page::ContentHeader;
 use crate::main_window_state::MainWindowState;
-use crate::util::{BuilderHelper, GtkUtil, Util};
+use crate::search_query::SearchQuery;
+use crate::settings::Settings;
+use crate::undo_bar::UndoActionModel;
+use crate::util::{BuilderHelper, DateUtil, GtkUtil, Util};
 use chrono::NaiveDateTime;
-use gdk::RGBA;
+use gdk::{ModifierType, RGBA};
 use glib::{clone, object::Cast, source::Continue, translate::ToGlib, Sender};
 use gtk::{
-    prelude::WidgetExtManual, AdjustmentExt, ContainerExt, ListBox, ListBoxExt, ListBoxRowExt, ScrolledWindow,
-    ScrolledWindowExt, SettingsExt, StateFlags, StyleContextExt, TickCallbackId, WidgetExt,
+    prelude::WidgetExtManual, AdjustmentExt, ContainerExt, Inhibit, Label, LabelExt, ListBox, ListBoxExt, ListBoxRow,
+    ListBoxRowExt, ScrolledWindow, ScrolledWindowExt, SelectionMode, SettingsExt, StateFlags, StyleContextExt,
+    TickCallbackId, Widget, WidgetExt,
 };
 use news_flash::models::{
     article::{Marked, Read},
     ArticleID,
 };
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 const LIST_BOTTOM_THREASHOLD: f64 = 200.0;
 const SCROLL_TRANSITION_DURATION: i64 = 500 * 1000;
+const PLACEHOLDER_ROW_HEIGHT: i32 = 100;
+const MARK_READ_ON_SCROLL_DELAY: u32 = 1000;
 
 #[derive(Clone)]
 struct ScrollAnimationProperties {
@@ -33,11 +40,246 @@ struct ScrollAnimationProperties {
     pub transition_diff: Arc<RwLock<Option<f64>>>,
 }
 
+/// The article shown by a row and everything needed to decide which group header it belongs to.
+struct RowInfo {
+    id: ArticleID,
+    date: NaiveDateTime,
+    feed: String,
+}
+
+/// Only rows close to the visible part of the list get their content built.
+/// All other rows are empty placeholders of the same height.
+#[derive(Clone)]
+struct RowRealizer {
+    sender: Sender<Action>,
+    scroll: ScrolledWindow,
+    list: ListBox,
+    row_infos: Arc<RwLock<HashMap<ListBoxRow, RowInfo>>>,
+    articles: Arc<RwLock<HashMap<ArticleID, ArticleRow>>>,
+    list_model: Arc<RwLock<ArticleListModel>>,
+    state: Arc<RwLock<MainWindowState>>,
+    /// the search term of the list and the words to highlight, so the term is only parsed once per list
+    search_terms: Arc<RwLock<(Option<String>, Vec<String>)>>,
+    idle_source: Arc<RwLock<Option<u32>>>,
+}
+
+impl RowRealizer {
+    pub fn queue(&self) {
+        if self.idle_source.read().is_some() {
+            return;
+        }
+
+        let realizer = self.clone();
+        *self.idle_source.write() = Some(
+            gtk::idle_add(move || {
+                *realizer.idle_source.write() = None;
+                realizer.realize_visible_rows();
+                Continue(false)
+            })
+            .to_glib(),
+        );
+    }
+
+    fn realize_visible_rows(&self) {
+        let vadjustment = match self.scroll.get_vadjustment() {
+            Some(vadjustment) => vadjustment,
+            None => return,
+        };
+
+        // list not allocated yet
+        let page_size = vadjustment.get_page_size();
+        if page_size <= 0.0 {
+            return;
+        }
+
+        let row_count = self.row_infos.read().len() as i32;
+        if row_count == 0 {
+            return;
+        }
+
+        // keep one page above and below the visible area realized
+        let top = (vadjustment.get_value() - page_size).max(0.0);
+        let bottom = vadjustment.get_value() + 2.0 * page_size;
+        let first = self
+            .list
+            .get_row_at_y(top as i32)
+            .map(|row| row.get_index())
+            .unwrap_or(0);
+        let last = self
+            .list
+            .get_row_at_y(bottom as i32)
+            .map(|row| row.get_index())
+            .unwrap_or(row_count - 1);
+
+        let mut articles = self.articles.write();
+        articles.retain(|_id, article_row| {
+            let index = article_row.widget().get_index();
+            index >= first && index <= last
+        });
+
+        let search_terms = self.search_terms();
+        let row_infos = self.row_infos.read();
+        let list_model = self.list_model.read();
+        for index in first..=last {
+            let row = match self.list.get_row_at_index(index) {
+                Some(row) => row,
+                None => break,
+            };
+            // the row knows its article, the order of the model doesn't matter
+            let article = match row_infos.get(&row).and_then(|info| list_model.get(&info.id)) {
+                Some(article) => article,
+                None => continue,
+            };
+            if articles.contains_key(&article.id) {
+                continue;
+            }
+
+            let article_row = ArticleRow::new(
+                article,
+                &row,
+                &self.list_model,
+                &self.state,
+                &search_terms,
+                self.sender.clone(),
+            );
+            articles.insert(article.id.clone(), article_row);
+        }
+    }
+
+    fn search_terms(&self) -> Vec<String> {
+        let search_term = self.state.read().get_search_term().clone();
+        let mut search_terms = self.search_terms.write();
+        if search_terms.0 != search_term {
+            search_terms.1 = search_term
+                .as_ref()
+                .and_then(|search_term| SearchQuery::parse(search_term).ok())
+                .map(|query| query.highlight_terms())
+                .unwrap_or_default();
+            search_terms.0 = search_term;
+        }
+        search_terms.1.clone()
+    }
+}
+
+/// Collects unread articles that got scrolled out of view at the top of the list
+/// and marks them read in a single batch once scrolling stopped.
+#[derive(Clone)]
+struct ScrollReadTracker {
+    sender: Sender<Action>,
+    scroll: ScrolledWindow,
+    list: ListBox,
+    row_infos: Arc<RwLock<HashMap<ListBoxRow, RowInfo>>>,
+    articles: Arc<RwLock<HashMap<ArticleID, ArticleRow>>>,
+    list_model: Arc<RwLock<ArticleListModel>>,
+    state: Arc<RwLock<MainWindowState>>,
+    settings: Arc<RwLock<Settings>>,
+    pending: Arc<RwLock<Vec<ArticleID>>>,
+    last_value: Arc<RwLock<f64>>,
+    /// Set while the list scrolls itself, e.g. to bring the selected article into view.
+    programmatic_scroll: Arc<RwLock<bool>>,
+    timeout_source: Arc<RwLock<Option<u32>>>,
+}
+
+impl ScrollReadTracker {
+    pub fn update(&self) {
+        let vadjustment = match self.scroll.get_vadjustment() {
+            Some(vadjustment) => vadjustment,
+            None => return,
+        };
+
+        // only scrolling down moves articles out of view at the top
+        let value = vadjustment.get_value();
+        let last_value = std::mem::replace(&mut *self.last_value.write(), value);
+        if value <= last_value || *self.programmatic_scroll.read() {
+            return;
+        }
+        if !self.settings.read().get_article_list_mark_read_on_scroll() || self.state.read().get_offline() {
+            return;
+        }
+
+        // walk the rows in the order they are shown, which isn't necessarily the order of the model,
+        // rows above the previous scroll position were already taken care of
+        let mut scrolled_past = Vec::new();
+        {
+            let row_infos = self.row_infos.read();
+            let list_model = self.list_model.read();
+            let mut index = self
+                .list
+                .get_row_at_y(last_value as i32)
+                .map(|row| row.get_index().max(0))
+                .unwrap_or(0);
+            while let Some(row) = self.list.get_row_at_index(index) {
+                index += 1;
+                let allocation = row.get_allocation();
+                if allocation.height <= 1 {
+                    continue;
+                }
+                if f64::from(allocation.y + allocation.height) > value {
+                    break;
+                }
+                let article = match row_infos.get(&row).and_then(|info| list_model.get(&info.id)) {
+                    Some(article) => article,
+                    None => continue,
+                };
+                if article.read == Read::Unread {
+                    scrolled_past.push(article.id.clone());
+                }
+            }
+        }
+
+        if scrolled_past.is_empty() {
+            return;
+        }
+
+        for article_id in &scrolled_past {
+            self.list_model.write().set_read(article_id, Read::Read);
+            if let Some(article_row) = self.articles.write().get_mut(article_id) {
+                article_row.update_unread(Read::Read);
+            }
+        }
+        self.pending.write().append(&mut scrolled_past);
+
+        GtkUtil::remove_source(self.timeout_source.write().take());
+        let tracker = self.clone();
+        *self.timeout_source.write() = Some(
+            gtk::timeout_add(MARK_READ_ON_SCROLL_DELAY, move || {
+                tracker.timeout_source.write().take();
+                tracker.flush();
+                Continue(false)
+            })
+            .to_glib(),
+        );
+    }
+
+    /// Hand the collected articles over to the undo bar, which marks them read once it times out.
+    pub fn flush(&self) {
+        GtkUtil::remove_source(self.timeout_source.write().take());
+        let article_ids: Vec<ArticleID> = self.pending.write().drain(..).collect();
+        if !article_ids.is_empty() {
+            Util::send(
+                &self.sender,
+                Action::UndoableAction(UndoActionModel::MarkReadOnScroll(article_ids)),
+            );
+        }
+    }
+
+    pub fn reset(&self) {
+        self.flush();
+        *self.last_value.write() = 0.0;
+    }
+}
+
 pub struct SingleArticleList {
     sender: Sender<Action>,
     scroll: ScrolledWindow,
-    articles: HashMap<ArticleID, Arc<RwLock<ArticleRow>>>,
+    rows: Arc<RwLock<HashMap<ArticleID, ListBoxRow>>>,
+    articles: Arc<RwLock<HashMap<ArticleID, ArticleRow>>>,
+    realizer: RowRealizer,
+    scroll_read_tracker: ScrollReadTracker,
+    grouping: Arc<RwLock<ArticleListGrouping>>,
+    row_infos: Arc<RwLock<HashMap<ListBoxRow, RowInfo>>>,
     list: ListBox,
+    detached_selection: HashSet<ArticleID>,
     select_after_signal: Arc<RwLock<Option<u32>>>,
     scroll_cooldown: Arc<RwLock<bool>>,
     scroll_animation_data: ScrollAnimationProperties,
@@ -45,14 +287,103 @@ pub struct SingleArticleList {
 }
 
 impl SingleArticleList {
-    pub fn new(sender: Sender<Action>, content_header: Arc<ContentHeader>) -> Self {
+    pub fn new(
+        sender: Sender<Action>,
+        content_header: Arc<ContentHeader>,
+        list_model: &Arc<RwLock<ArticleListModel>>,
+        state: &Arc<RwLock<MainWindowState>>,
+        settings: &Arc<RwLock<Settings>>,
+    ) -> Self {
         let builder = BuilderHelper::new("article_list_single");
         let scroll = builder.get::<ScrolledWindow>("article_list_scroll");
         let list = builder.get::<ListBox>("article_list_box");
 
         let scroll_cooldown = Arc::new(RwLock::new(false));
+        let rows = Arc::new(RwLock::new(HashMap::new()));
+        let articles = Arc::new(RwLock::new(HashMap::new()));
+        let row_infos = Arc::new(RwLock::new(HashMap::new()));
+
+        let realizer = RowRealizer {
+            sender: sender.clone(),
+            scroll: scroll.clone(),
+            list: list.clone(),
+            row_infos: row_infos.clone(),
+            articles: articles.clone(),
+            list_model: list_model.clone(),
+            state: state.clone(),
+            search_terms: Arc::new(RwLock::new((None, Vec::new()))),
+            idle_source: Arc::new(RwLock::new(None)),
+        };
+
+        let scroll_read_tracker = ScrollReadTracker {
+            sender: sender.clone(),
+            scroll: scroll.clone(),
+            list: list.clone(),
+            row_infos: row_infos.clone(),
+            articles: articles.clone(),
+            list_model: list_model.clone(),
+            state: state.clone(),
+            settings: settings.clone(),
+            pending: Arc::new(RwLock::new(Vec::new())),
+            last_value: Arc::new(RwLock::new(0.0)),
+            programmatic_scroll: Arc::new(RwLock::new(false)),
+            timeout_source: Arc::new(RwLock::new(None)),
+        };
+
+        let grouping = Arc::new(RwLock::new(ArticleListGrouping::None));
+        list.set_header_func(Some(Box::new(clone!(
+            @weak grouping,
+            @weak row_infos => @default-panic, move |row, before|
+        {
+            let row_infos = row_infos.read();
+            let group = Self::group_name(row_infos.get(row), *grouping.read());
+            let group_before = before.and_then(|before| Self::group_name(row_infos.get(before), *grouping.read()));
+
+            match group {
+                Some(group) if Some(&group) != group_before.as_ref() => {
+                    let current_group = row
+                        .get_header()
+                        .and_then(|header| header.downcast::<Label>().ok())
+                        .map(|label| label.get_text().as_str().to_owned());
+                    if current_group.as_ref() != Some(&group) {
+                        row.set_header(Some(&Self::create_group_header(&group)));
+                    }
+                }
+                _ => {
+                    if row.get_header().is_some() {
+                        row.set_header(None::<&Widget>);
+                    }
+                }
+            }
+        }))));
+
+        // ctrl/shift + click switches the list to selection mode, a plain click leaves it again
+        // rows are only activated (and the article opened) by a single click outside of selection mode
+        list.connect_button_press_event(|list, event| {
+            if event.get_button() == 1 {
+                let modifiers = event.get_state();
+                if modifiers.intersects(ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK) {
+                    list.set_activate_on_single_click(false);
+                    list.set_selection_mode(SelectionMode::Multiple);
+                } else if list.get_selection_mode() == SelectionMode::Multiple {
+                    list.set_selection_mode(SelectionMode::Browse);
+                    list.set_activate_on_single_click(true);
+                }
+            }
+            Inhibit(false)
+        });
 
         if let Some(vadjustment) = scroll.get_vadjustment() {
+            vadjustment.connect_value_changed(clone!(
+                @strong realizer,
+                @strong scroll_read_tracker => @default-panic, move |_vadj|
+            {
+                realizer.queue();
+                scroll_read_tracker.update();
+            }));
+            vadjustment.connect_changed(clone!(@strong realizer => @default-panic, move |_vadj| {
+                realizer.queue();
+            }));
             vadjustment.connect_value_changed(clone!(
                 @weak scroll_cooldown,
                 @strong sender => @default-panic, move |vadj|
@@ -82,8 +413,14 @@ impl SingleArticleList {
         SingleArticleList {
             sender,
             scroll,
-            articles: HashMap::new(),
+            rows,
+            articles,
+            realizer,
+            scroll_read_tracker,
+            grouping,
+            row_infos,
             list,
+            detached_selection: HashSet::new(),
             select_after_signal: Arc::new(RwLock::new(None)),
             scroll_cooldown,
             scroll_animation_data: ScrollAnimationProperties {
@@ -115,36 +452,66 @@ impl SingleArticleList {
         color
     }
 
-    pub fn add(
-        &mut self,
-        article: &ArticleListArticleModel,
-        pos: i32,
-        model: &Arc<RwLock<ArticleListModel>>,
-        state: &Arc<RwLock<MainWindowState>>,
-    ) {
-        let article_row = ArticleRow::new(&article, model, state, self.sender.clone());
-        self.list.insert(&article_row.widget(), pos);
-        article_row.widget().show();
-        self.articles
-            .insert(article.id.clone(), Arc::new(RwLock::new(article_row)));
+    pub fn add(&mut self, article: &ArticleListArticleModel, pos: i32) {
+        let row = ArticleRow::create_row(PLACEHOLDER_ROW_HEIGHT);
+        self.row_infos.write().insert(
+            row.clone(),
+            RowInfo {
+                id: article.id.clone(),
+                date: article.date,
+                feed: article.feed_title.clone(),
+            },
+        );
+        self.list.insert(&row, pos);
+        row.show();
+        self.rows.write().insert(article.id.clone(), row);
+        self.realizer.queue();
     }
 
     pub fn remove(&mut self, id: ArticleID) {
-        if let Some(article_row) = self.articles.get(&id) {
-            self.list.remove(&article_row.read().widget());
+        let _ = self.articles.write().remove(&id);
+        if let Some(row) = self.rows.write().remove(&id) {
+            self.list.remove(&row);
+            self.row_infos.write().remove(&row);
         }
-        let _ = self.articles.remove(&id);
+        self.realizer.queue();
+    }
+
+    /// Take the row out of the list without destroying it, so it can be inserted again at its new position.
+    pub fn detach(&mut self, id: &ArticleID) {
+        if let Some(row) = self.rows.read().get(id) {
+            if row.is_selected() {
+                self.detached_selection.insert(id.clone());
+            }
+            self.list.remove(row);
+        }
+    }
+
+    pub fn attach(&mut self, id: &ArticleID, pos: i32) {
+        if let Some(row) = self.rows.read().get(id) {
+            self.list.insert(row, pos);
+            if self.detached_selection.remove(id) {
+                self.list.select_row(Some(row));
+            }
+        }
+        self.realizer.queue();
     }
 
     pub fn clear(&mut self) {
         *self.scroll_cooldown.write() = true;
+        self.scroll_read_tracker.reset();
         for row in self.list.get_children() {
             gtk::idle_add(clone!(@weak self.list as list => @default-panic, move || {
                 list.remove(&row);
                 Continue(false)
             }));
         }
-        self.articles.clear();
+        self.articles.write().clear();
+        self.rows.write().clear();
+        self.row_infos.write().clear();
+        self.detached_selection.clear();
+        self.list.set_selection_mode(SelectionMode::Browse);
+        self.list.set_activate_on_single_click(true);
         if let Some(vadjustment) = self.scroll.get_vadjustment() {
             vadjustment.set_value(0.0);
         }
@@ -152,32 +519,62 @@ impl SingleArticleList {
     }
 
     pub fn update_marked(&mut self, id: &ArticleID, marked: Marked) {
-        if let Some(article_handle) = self.articles.get(id) {
-            article_handle.write().update_marked(marked);
+        if let Some(article_row) = self.articles.write().get_mut(id) {
+            article_row.update_marked(marked);
         }
     }
 
     pub fn update_read(&mut self, id: &ArticleID, read: Read) {
-        if let Some(article_handle) = self.articles.get(id) {
-            article_handle.write().update_unread(read);
+        if let Some(article_row) = self.articles.write().get_mut(id) {
+            article_row.update_unread(read);
         }
     }
 
     pub fn update_date_string(&mut self, id: &ArticleID, date: NaiveDateTime) {
-        if let Some(article_handle) = self.articles.get(id) {
-            article_handle.write().update_date_string(date);
+        if let Some(article_row) = self.articles.write().get_mut(id) {
+            article_row.update_date_string(date);
+        }
+        if let Some(row) = self.rows.read().get(id) {
+            if let Some(info) = self.row_infos.write().get_mut(row) {
+                info.date = date;
+            }
+            // the group may have changed, e.g. from 'Today' to 'Yesterday'
+            row.changed();
+        }
+    }
+
+    pub fn set_grouping(&self, grouping: ArticleListGrouping) {
+        if *self.grouping.read() != grouping {
+            *self.grouping.write() = grouping;
+            self.list.invalidate_headers();
+        }
+    }
+
+    fn group_name(info: Option<&RowInfo>, grouping: ArticleListGrouping) -> Option<String> {
+        let info = info?;
+        match grouping {
+            ArticleListGrouping::None => None,
+            ArticleListGrouping::Date => Some(DateUtil::format_group(&info.date)),
+            ArticleListGrouping::Feed => Some(info.feed.clone()),
         }
     }
 
+    fn create_group_header(group: &str) -> Label {
+        let label = Label::new(Some(group));
+        label.set_xalign(0.0);
+        label.get_style_context().add_class("article-list-group-header");
+        label.show();
+        label
+    }
+
     pub fn get_allocated_row_height(&self, id: &ArticleID) -> Option<i32> {
-        self.articles
-            .get(id)
-            .map(|row| row.read().widget().get_allocated_height())
+        self.rows.read().get(id).map(|row| row.get_allocated_height())
     }
 
     pub fn select_after(&self, id: &ArticleID, time: u32) {
-        if let Some(article_handle) = self.articles.get(id) {
-            self.list.select_row(Some(&article_handle.read().widget()));
+        let row = self.rows.read().get(id).cloned();
+        if let Some(row) = row {
+            self.list.select_row(Some(&row));
             Util::send(
                 &self.sender,
                 Action::MarkArticleRead(ReadUpdate {
@@ -189,7 +586,6 @@ impl SingleArticleList {
             GtkUtil::remove_source(*self.select_after_signal.read());
             *self.select_after_signal.write() = None;
 
-            let article_widget = article_handle.read().widget();
             *self.select_after_signal.write() = Some(
                 gtk::timeout_add(
                     time,
@@ -201,7 +597,7 @@ impl SingleArticleList {
                             return Continue(false);
                         }
 
-                        article_widget.activate();
+                        row.activate();
 
                         *select_after_signal.write() = None;
                         Continue(false)
@@ -212,6 +608,25 @@ impl SingleArticleList {
         }
     }
 
+    /// Scroll just far enough for the row of `id` to be completely visible.
+    /// Works for rows that don't have their content realized yet as well.
+    pub fn scroll_to_article(&self, id: &ArticleID) {
+        let row = self.rows.read().get(id).cloned();
+        if let Some(row) = row {
+            let allocation = row.get_allocation();
+            let row_top = f64::from(allocation.y);
+            let row_bottom = f64::from(allocation.y + allocation.height);
+            let value = self.get_scroll_value();
+            let page_size = self.get_scroll_page_size();
+
+            if row_top < value {
+                self.animate_scroll_absolute(row_top);
+            } else if row_bottom > value + page_size {
+                self.animate_scroll_absolute(row_bottom - page_size);
+            }
+        }
+    }
+
     pub fn animate_scroll_diff(&self, diff: f64) {
         let pos = self.get_scroll_value() + diff;
         self.animate_scroll_absolute(pos)
@@ -260,7 +675,8 @@ impl SingleArticleList {
             @weak self.scroll_animation_data.transition_start_value as transition_start_value,
             @weak self.scroll_animation_data.scroll_callback_id as callback_id,
             @weak self.scroll_animation_data.start_time as start_time,
-            @weak self.scroll_animation_data.end_time as end_time => @default-panic, move |widget, clock| {
+            @weak self.scroll_animation_data.end_time as end_time,
+            @weak self.scroll_read_tracker.programmatic_scroll as programmatic_scroll => @default-panic, move |widget, clock| {
             let scroll = widget
                 .clone()
                 .downcast::<ScrolledWindow>()
@@ -273,7 +689,7 @@ impl SingleArticleList {
             let start_time_value = Util::some_or_default(*start_time.read(), 0);
 
             if !widget.get_mapped() {
-                Self::set_scroll_value_static(&scroll, start_value + diff_value);
+                Self::set_scroll_value_static(&scroll, &programmatic_scroll, start_value + diff_value);
                 return Continue(false);
             }
 
@@ -289,7 +705,7 @@ impl SingleArticleList {
 
             let t = Util::ease_out_cubic(t);
 
-            Self::set_scroll_value_static(&scroll, start_value + (t * diff_value));
+            Self::set_scroll_value_static(&scroll, &programmatic_scroll, start_value + (t * diff_value));
 
             if Self::get_scroll_value_static(&scroll) <= 0.0 || now >= end_time_value {
                 scroll.queue_draw();
@@ -306,17 +722,20 @@ impl SingleArticleList {
     }
 
     fn set_scroll_value(&self, pos: f64) {
-        Self::set_scroll_value_static(&self.scroll, pos)
+        Self::set_scroll_value_static(&self.scroll, &self.scroll_read_tracker.programmatic_scroll, pos)
     }
 
-    fn set_scroll_value_static(scroll: &ScrolledWindow, pos: f64) {
+    /// Scrolling done by the list itself doesn't count as reading the articles passed on the way.
+    fn set_scroll_value_static(scroll: &ScrolledWindow, programmatic_scroll: &RwLock<bool>, pos: f64) {
         if let Some(vadjustment) = scroll.get_vadjustment() {
             let pos = if (pos + 1.0).abs() < 0.001 {
                 vadjustment.get_upper() - vadjustment.get_page_size()
             } else {
                 pos
             };
+            *programmatic_scroll.write() = true;
             vadjustment.set_value(pos);
+            *programmatic_scroll.write() = false;
         }
     }
 
@@ -357,13 +776,31 @@ impl SingleArticleList {
         self.list.get_selected_row().map(|row| row.get_index())
     }
 
+    pub fn get_selected_indices(&self) -> Vec<i32> {
+        self.list
+            .get_selected_rows()
+            .iter()
+            .map(|row| row.get_index())
+            .collect()
+    }
+
+    pub fn is_selection_mode(&self) -> bool {
+        self.list.get_selection_mode() == SelectionMode::Multiple
+    }
+
+    pub fn leave_selection_mode(&self) {
+        self.list.unselect_all();
+        self.list.set_selection_mode(SelectionMode::Browse);
+        self.list.set_activate_on_single_click(true);
+    }
+
     pub fn set_article_row_state(&self, article_id: &ArticleID, read: Option<Read>, marked: Option<Marked>) {
-        if let Some(article_row) = self.articles.get(article_id) {
+        if let Some(article_row) = self.articles.write().get_mut(article_id) {
             if let Some(read) = read {
-                article_row.write().update_unread(read);
+                article_row.update_unread(read);
             }
             if let Some(marked) = marked {
-                article_row.write().update_marked(marked);
+                article_row.update_marked(marked);
             }
         }
     }
@@ -0,0 +1,225 @@
+use super::ArticleView;
+use crate::app::Action;
+use crate::i18n::i18n;
+use crate::main_window::MainWindow;
+use crate::util::Util;
+use glib::{clone, object::Cast, Sender};
+use gtk::{
+    ActionBar, ActionBarExt, Box, BoxExt, Button, ButtonExt, ContainerExt, GtkWindowExt, IconSize, Image, Inhibit,
+    Label, LabelExt, Orientation, ReliefStyle, StyleContextExt, ToggleButton, ToggleButtonExt, WidgetExt, Window,
+};
+use news_flash::models::{Enclosure, FatArticle};
+use pango::EllipsizeMode;
+use webkit2gtk::{WebView, WebViewExt};
+
+const ZOOM_STEP: f64 = 0.25;
+
+/// A single article shown next to the main article view, either as tab or in a window of its own.
+/// It is a complete article view with its own zoom level and scraped content toggle and keeps its article until closed.
+#[derive(Clone)]
+pub struct ArticleTab {
+    widget: Box,
+    view: ArticleView,
+    title: String,
+    feed_name: String,
+}
+
+impl ArticleTab {
+    pub fn new(
+        view: ArticleView,
+        article: FatArticle,
+        feed_name: String,
+        enclosures: Vec<Enclosure>,
+        sender: &Sender<Action>,
+    ) -> Self {
+        let scraped_button = ToggleButton::new();
+        scraped_button.add(&Image::from_icon_name(
+            Some("accessories-dictionary-symbolic"),
+            IconSize::Button,
+        ));
+        scraped_button.set_tooltip_text(Some(&i18n("Show full content")));
+        scraped_button.set_active(article.scraped_content.is_some());
+
+        let zoom_out_button = Button::from_icon_name(Some("zoom-out-symbolic"), IconSize::Button);
+        zoom_out_button.set_tooltip_text(Some(&i18n("Zoom Out")));
+        let zoom_reset_button = Button::with_label("100%");
+        zoom_reset_button.set_tooltip_text(Some(&i18n("Reset Zoom")));
+        let zoom_in_button = Button::from_icon_name(Some("zoom-in-symbolic"), IconSize::Button);
+        zoom_in_button.set_tooltip_text(Some(&i18n("Zoom In")));
+        let zoom_box = Box::new(Orientation::Horizontal, 0);
+        zoom_box.get_style_context().add_class("linked");
+        zoom_box.pack_start(&zoom_out_button, false, false, 0);
+        zoom_box.pack_start(&zoom_reset_button, false, false, 0);
+        zoom_box.pack_start(&zoom_in_button, false, false, 0);
+
+        let browser_button = Button::from_icon_name(Some("web-browser-symbolic"), IconSize::Button);
+        browser_button.set_tooltip_text(Some(&i18n("Open in Browser")));
+        browser_button.set_sensitive(article.url.is_some());
+
+        let action_bar = ActionBar::new();
+        action_bar.pack_start(&scraped_button);
+        action_bar.pack_end(&browser_button);
+        action_bar.pack_end(&zoom_box);
+
+        let widget = Box::new(Orientation::Vertical, 0);
+        widget.pack_start(&view.top_overlay, true, true, 0);
+        widget.pack_end(&action_bar, false, false, 0);
+        widget.show_all();
+
+        if let Some(url) = &article.url {
+            let url = url.get().to_string();
+            browser_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+                Util::send(&sender, Action::OpenUrlInDefaultBrowser(url.clone()));
+            }));
+        }
+
+        let tab = ArticleTab {
+            widget,
+            view,
+            title: article.title.clone().unwrap_or_else(|| i18n("Untitled Article")),
+            feed_name: feed_name.clone(),
+        };
+
+        scraped_button.connect_toggled(clone!(
+            @strong tab.view as view,
+            @strong sender => @default-panic, move |button|
+        {
+            let prefer_scraped_content = button.get_active();
+            view.set_prefer_scraped_content(prefer_scraped_content);
+            if !prefer_scraped_content {
+                return;
+            }
+            if let Some(article) = view.get_visible_article() {
+                if article.scraped_content.is_none() {
+                    Util::send(&sender, Action::StartGrabTabArticleContent(article.article_id));
+                }
+            }
+        }));
+
+        zoom_out_button.connect_clicked(clone!(@strong tab.view as view => @default-panic, move |_button| {
+            if let Some(webview) = view.visible_webview() {
+                webview.set_zoom_level(webview.get_zoom_level() - ZOOM_STEP);
+            }
+        }));
+        zoom_reset_button.connect_clicked(clone!(@strong tab.view as view => @default-panic, move |_button| {
+            if let Some(webview) = view.visible_webview() {
+                webview.set_zoom_level(1.0);
+            }
+        }));
+        zoom_in_button.connect_clicked(clone!(@strong tab.view as view => @default-panic, move |_button| {
+            if let Some(webview) = view.visible_webview() {
+                webview.set_zoom_level(webview.get_zoom_level() + ZOOM_STEP);
+            }
+        }));
+        // the view alternates between its two webviews whenever the article is rendered again
+        for webview in tab.view.stack.get_children() {
+            if let Ok(webview) = webview.downcast::<WebView>() {
+                webview.connect_property_zoom_level_notify(
+                    clone!(@weak zoom_reset_button => @default-panic, move |webview| {
+                        Self::update_zoom_label(webview, &zoom_reset_button);
+                    }),
+                );
+            }
+        }
+
+        let prefer_scraped_content = article.scraped_content.is_some();
+        tab.view.prefer_scraped_content.write().replace(prefer_scraped_content);
+        tab.view.show_article(article, feed_name, enclosures);
+        if let Some(webview) = tab.view.visible_webview() {
+            Self::update_zoom_label(&webview, &zoom_reset_button);
+        }
+        tab
+    }
+
+    pub fn widget(&self) -> Box {
+        self.widget.clone()
+    }
+
+    pub fn view(&self) -> &ArticleView {
+        &self.view
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn feed_name(&self) -> &str {
+        &self.feed_name
+    }
+
+    /// The label of the tab with a button to close it.
+    pub fn tab_label(&self) -> (Box, Button) {
+        let label = Label::new(Some(&self.title));
+        label.set_ellipsize(EllipsizeMode::End);
+        label.set_max_width_chars(20);
+        label.set_tooltip_text(Some(&self.title));
+
+        let close_button = Button::from_icon_name(Some("window-close-symbolic"), IconSize::Menu);
+        close_button.set_relief(ReliefStyle::None);
+        close_button.set_tooltip_text(Some(&i18n("Close Tab")));
+
+        let tab_label = Box::new(Orientation::Horizontal, 5);
+        tab_label.pack_start(&label, true, true, 0);
+        tab_label.pack_end(&close_button, false, false, 0);
+        tab_label.show_all();
+        (tab_label, close_button)
+    }
+
+    /// Whether the article is shown in a window of its own that has the focus.
+    pub fn is_active_window(&self) -> bool {
+        self.widget
+            .get_parent()
+            .and_then(|parent| parent.downcast::<Window>().ok())
+            .map(|window| window.is_active())
+            .unwrap_or(false)
+    }
+
+    /// The find shortcuts of the main window, for articles shown in a window of their own.
+    pub fn connect_shortcuts(&self, window: &Window) {
+        window.connect_key_press_event(
+            clone!(@strong self.view as view => @default-panic, move |_window, event| {
+                // check the shift variants first: "<ctl>G" also matches ctrl+shift+G
+                if MainWindow::check_shortcut("find_previous", &view.settings, event) {
+                    view.find_previous();
+                    return Inhibit(true);
+                }
+                if MainWindow::check_shortcut("find_next", &view.settings, event) {
+                    view.find_next();
+                    return Inhibit(true);
+                }
+                if MainWindow::check_shortcut("find", &view.settings, event)
+                    || MainWindow::check_shortcut("search", &view.settings, event)
+                {
+                    view.show_find_bar();
+                    return Inhibit(true);
+                }
+                Inhibit(false)
+            }),
+        );
+    }
+
+    /// Show `article` again if it is the one of this tab, e.g. once its content was scraped.
+    pub fn update_article(&self, article: &FatArticle) {
+        let shown = self
+            .view
+            .visible_article
+            .read()
+            .as_ref()
+            .map(|visible_article| visible_article.article_id == article.article_id)
+            .unwrap_or(false);
+        if shown {
+            self.view.visible_article.write().replace(article.clone());
+            self.view.redraw();
+        }
+    }
+
+    /// Remember how far the article was read and stop everything still running in it.
+    pub fn close(&self) {
+        self.view.close_article();
+    }
+
+    fn update_zoom_label(webview: &WebView, zoom_reset_button: &Button) {
+        let percent = (webview.get_zoom_level() * 100.0).round() as i64;
+        zoom_reset_button.set_label(&format!("{}%", percent));
+    }
+}
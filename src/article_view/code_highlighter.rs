@@ -0,0 +1,199 @@
+use super::sanitizer::HtmlSanitizer;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+// longer blocks are shown without highlighting, parsing them would stall rendering the article
+static MAX_CODE_LENGTH: usize = 20_000;
+// class names of the highlighted scopes, e.g. `keyword.operator` becomes `nf-code-keyword nf-code-operator`
+static CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "nf-code-" };
+
+lazy_static! {
+    static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    static ref CODE_BLOCK: Regex =
+        Regex::new(r#"(?is)<pre\b((?:"[^"]*"|'[^']*'|[^'">])*)>(.*?)</pre\s*>"#).expect("invalid code block regex");
+    static ref CODE_WRAPPER: Regex = Regex::new(r#"(?is)^\s*<code\b((?:"[^"]*"|'[^']*'|[^'">])*)>(.*)</code\s*>\s*$"#)
+        .expect("invalid code wrapper regex");
+    static ref LANGUAGE_ATTRIBUTE: Regex =
+        Regex::new(r#"(?i)\b(?:class|data-lang|lang)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#)
+            .expect("invalid language attribute regex");
+    static ref ENTITY: Regex = Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").expect("invalid entity regex");
+    static ref PYTHON_HINT: Regex =
+        Regex::new(r"(?m)^\s*(def|class)\s+\w+.*:\s*$|^\s*from\s+[\w.]+\s+import\s").expect("invalid python regex");
+    static ref SQL_HINT: Regex = Regex::new(
+        r"(?is)^\s*(select\b.+\bfrom|insert\s+into|update\b.+\bset|delete\s+from|create\s+(table|index|view))\b"
+    )
+    .expect("invalid sql regex");
+    static ref SHELL_HINT: Regex =
+        Regex::new(r"(?m)^(#!/.*sh\b|\$ |(sudo|apt|apt-get|dnf|flatpak|cargo|npm|pip|git|cd|export) )")
+            .expect("invalid shell regex");
+}
+
+/// Syntax highlighting for the `<pre>` blocks of an article through syntect. The language is taken from the
+/// class names feeds usually put on the block (`language-rust`, `lang-py`, ...) or guessed from the code.
+/// Every block also gets a copy button that `code_blocks.js` brings to life.
+pub struct CodeHighlighter;
+
+impl CodeHighlighter {
+    pub fn highlight(html: &str, copy_label: &str, copied_label: &str) -> String {
+        CODE_BLOCK
+            .replace_all(html, |captures: &Captures| {
+                let pre_attributes = &captures[1];
+                let (code_attributes, inner) = match CODE_WRAPPER.captures(&captures[2]) {
+                    Some(wrapper) => (Some(wrapper[1].to_owned()), wrapper[2].to_owned()),
+                    None => (None, captures[2].to_owned()),
+                };
+
+                // blocks that come with markup of their own (e.g. highlighted by the feed) are left alone
+                let highlighted = if inner.contains('<') || inner.len() > MAX_CODE_LENGTH {
+                    None
+                } else {
+                    let code = Self::unescape(&inner);
+                    Self::syntax_of(pre_attributes)
+                        .or_else(|| code_attributes.as_deref().and_then(Self::syntax_of))
+                        .or_else(|| Self::guess_language(&code).and_then(|token| SYNTAX_SET.find_syntax_by_token(token)))
+                        .and_then(|syntax| Self::highlight_code(&code, syntax))
+                };
+                let body = highlighted.unwrap_or(inner);
+                let body = match code_attributes {
+                    Some(attributes) => format!("<code{}>{}</code>", attributes, body),
+                    None => body,
+                };
+
+                format!(
+                    "<div class=\"nf-code\"><button class=\"nf-code-copy\" type=\"button\" data-nf-copied=\"{}\">{}</button>\
+                     <pre{}>{}</pre></div>",
+                    HtmlSanitizer::escape(copied_label),
+                    HtmlSanitizer::escape(copy_label),
+                    pre_attributes,
+                    body
+                )
+            })
+            .into_owned()
+    }
+
+    fn syntax_of(attributes: &str) -> Option<&'static SyntaxReference> {
+        LANGUAGE_ATTRIBUTE.captures_iter(attributes).find_map(|captures| {
+            let value = captures
+                .get(1)
+                .or_else(|| captures.get(2))
+                .or_else(|| captures.get(3))
+                .map(|value| value.as_str().to_lowercase())?;
+            value.split_whitespace().find_map(|class| {
+                let name = ["language-", "lang-", "highlight-source-", "highlight-", "brush:"]
+                    .iter()
+                    .find_map(|prefix| class.strip_prefix(prefix))
+                    .unwrap_or(class);
+                let name = match name {
+                    "shell" | "console" | "zsh" => "bash",
+                    "markup" => "html",
+                    name => name,
+                };
+                SYNTAX_SET.find_syntax_by_token(name)
+            })
+        })
+    }
+
+    fn guess_language(code: &str) -> Option<&'static str> {
+        let trimmed = code.trim_start();
+        if trimmed.starts_with("<?php") {
+            Some("php")
+        } else if trimmed.starts_with('<') && trimmed.trim_end().ends_with('>') {
+            Some("html")
+        } else if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && serde_json::from_str::<serde_json::Value>(code).is_ok()
+        {
+            Some("json")
+        } else if code.contains("#include") {
+            Some("c")
+        } else if code.contains("fn ") && (code.contains("let ") || code.contains("->") || code.contains("::")) {
+            Some("rust")
+        } else if code.contains("package main") || (code.contains("func ") && code.contains(":=")) {
+            Some("go")
+        } else if code.contains("public class ") || code.contains("public static void") {
+            Some("java")
+        } else if PYTHON_HINT.is_match(code) {
+            Some("python")
+        } else if code.contains("function")
+            || code.contains("=>")
+            || code.contains("console.")
+            || code.contains("const ")
+        {
+            Some("js")
+        } else if SQL_HINT.is_match(code) {
+            Some("sql")
+        } else if SHELL_HINT.is_match(code) {
+            Some("bash")
+        } else {
+            None
+        }
+    }
+
+    fn highlight_code(code: &str, syntax: &SyntaxReference) -> Option<String> {
+        let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, CLASS_STYLE);
+        for line in LinesWithEndings::from(code) {
+            generator.parse_html_for_line_which_includes_newline(line).ok()?;
+        }
+        Some(generator.finalize())
+    }
+
+    fn unescape(text: &str) -> String {
+        ENTITY
+            .replace_all(text, |captures: &Captures| {
+                let entity = &captures[1];
+                let c = match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some('\u{a0}'),
+                    _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                        u32::from_str_radix(&entity[2..], 16).ok().and_then(std::char::from_u32)
+                    }
+                    _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(std::char::from_u32),
+                    _ => None,
+                };
+                c.map(|c| c.to_string()).unwrap_or_else(|| captures[0].to_owned())
+            })
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CodeHighlighter;
+
+    #[test]
+    fn highlight_by_class() {
+        let html = r#"<pre><code class="language-rust">let x = "a &lt; b"; // done</code></pre>"#;
+        let highlighted = CodeHighlighter::highlight(html, "Copy", "Copied");
+        assert!(highlighted.starts_with(
+            "<div class=\"nf-code\"><button class=\"nf-code-copy\" type=\"button\" data-nf-copied=\"Copied\">Copy</button>\
+             <pre><code class=\"language-rust\">"
+        ));
+        assert!(highlighted.contains("<span class=\"nf-code-storage nf-code-type nf-code-rust\">let</span>"));
+        assert!(highlighted.contains("a &lt; b"));
+        assert!(highlighted.contains("<span class=\"nf-code-comment nf-code-line"));
+        assert!(highlighted.ends_with("</code></pre></div>"));
+    }
+
+    #[test]
+    fn guess_and_keep_markup() {
+        let html = "<pre>def add(a, b):\n    return a + 1</pre>";
+        let highlighted = CodeHighlighter::highlight(html, "Copy", "Copied");
+        assert!(highlighted.contains("nf-code-python\">def</span>"));
+        assert!(highlighted.contains("<span class=\"nf-code-constant nf-code-numeric"));
+
+        // already highlighted by the feed
+        let html = "<pre><code><span class=\"k\">fn</span> main() {}</code></pre>";
+        assert!(CodeHighlighter::highlight(html, "Copy", "Copied")
+            .contains("<code><span class=\"k\">fn</span> main() {}</code>"));
+
+        // nothing that looks like code
+        let html = "<pre>  _\n (o)\n</pre>";
+        assert!(CodeHighlighter::highlight(html, "Copy", "Copied").ends_with("<pre>  _\n (o)\n</pre></div>"));
+    }
+}
@@ -0,0 +1,98 @@
+use super::sanitizer::HtmlSanitizer;
+use crate::settings::Settings;
+use gio::{NetworkMonitor, NetworkMonitorExt};
+use lazy_static::lazy_static;
+use news_flash::models::FeedID;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    // a <picture> is replaced as a whole, its <source> elements load just like the <img> inside
+    static ref IMAGE: Regex = Regex::new(r#"(?is)<picture\b.*?</picture\s*>|<img\b(?:"[^"]*"|'[^']*'|[^'">])*>"#)
+        .expect("invalid image regex");
+    static ref MEDIA: Regex =
+        Regex::new(r#"(?i)<(?:video|audio)\b(?:"[^"]*"|'[^']*'|[^'">])*>"#).expect("invalid media regex");
+    static ref MEDIA_LOADING_ATTRIBUTE: Regex =
+        Regex::new(r#"(?i)\s(?:poster|preload)(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'>]+))?"#)
+            .expect("invalid media attribute regex");
+}
+
+/// Decides whether remote images of an article are loaded right away or replaced
+/// with click-to-load placeholders.
+pub struct DataSaver;
+
+impl DataSaver {
+    /// Per-feed overrides win over the global data saver setting.
+    pub fn blocks_images(settings: &Settings, feed_id: &FeedID) -> bool {
+        if let Some(load_images) = settings.get_article_view_feed_images(feed_id) {
+            return !load_images;
+        }
+        if settings.get_article_view_data_saver() {
+            return true;
+        }
+        settings.get_article_view_data_saver_metered() && Self::is_metered()
+    }
+
+    pub fn is_metered() -> bool {
+        NetworkMonitor::get_default()
+            .map(|monitor| monitor.get_network_metered())
+            .unwrap_or(false)
+    }
+
+    /// Called whenever the connection becomes metered or stops being metered.
+    pub fn connect_metered_changed<F: Fn() + 'static>(callback: F) {
+        if let Some(monitor) = NetworkMonitor::get_default() {
+            monitor.connect_property_network_metered_notify(move |_monitor| callback());
+        }
+    }
+
+    /// Replace every `<img>` and `<picture>` with a placeholder that keeps the original markup around,
+    /// `image_placeholders.js` puts it back once the placeholder is clicked.
+    /// Videos lose their poster and audio and video aren't preloaded.
+    /// CSS backgrounds never make it past `HtmlSanitizer`.
+    pub fn replace_images(html: &str, label: &str) -> String {
+        let html = IMAGE.replace_all(html, |captures: &Captures| {
+            format!(
+                "<span class=\"nf-image-placeholder\" role=\"button\" data-nf-image=\"{}\">{}</span>",
+                HtmlSanitizer::escape(&captures[0]),
+                HtmlSanitizer::escape(label)
+            )
+        });
+        MEDIA
+            .replace_all(&html, |captures: &Captures| {
+                let tag = MEDIA_LOADING_ATTRIBUTE.replace_all(&captures[0], "");
+                let end = if tag.ends_with("/>") {
+                    tag.len() - 2
+                } else {
+                    tag.len() - 1
+                };
+                format!("{} preload=\"none\"{}", tag[..end].trim_end(), &tag[end..])
+            })
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DataSaver;
+
+    #[test]
+    fn replace_images() {
+        let html = r#"<p>Text</p><IMG src="a.png?x=1&y=2" alt='A > B'><a href="b"><img src=b.png /></a>"#;
+        assert_eq!(
+            DataSaver::replace_images(html, "Load Image"),
+            "<p>Text</p>\
+             <span class=\"nf-image-placeholder\" role=\"button\" \
+             data-nf-image=\"&lt;IMG src=&quot;a.png?x=1&amp;y=2&quot; alt='A &gt; B'&gt;\">Load Image</span>\
+             <a href=\"b\"><span class=\"nf-image-placeholder\" role=\"button\" \
+             data-nf-image=\"&lt;img src=b.png /&gt;\">Load Image</span></a>"
+        );
+
+        let html = r#"<picture><source srcset="a.webp"><img src="a.png"></picture><video src="v.mp4" poster="p.jpg" controls></video>"#;
+        assert_eq!(
+            DataSaver::replace_images(html, "Load Image"),
+            "<span class=\"nf-image-placeholder\" role=\"button\" \
+             data-nf-image=\"&lt;picture&gt;&lt;source srcset=&quot;a.webp&quot;&gt;&lt;img src=&quot;a.png&quot;&gt;&lt;/picture&gt;\">\
+             Load Image</span><video src=\"v.mp4\" controls preload=\"none\"></video>"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use super::sanitizer::HtmlSanitizer;
+use crate::i18n::i18n;
+use crate::settings::Settings;
+use gio::{Cancellable, FileExt, IOErrorEnum};
+use news_flash::models::Enclosure;
+use std::fs;
+use std::path::PathBuf;
+use url::Url;
+use webkit2gtk::{URISchemeRequest, URISchemeRequestExt};
+
+/// Scheme the offline copies of enclosures are served from, remote pages may not load `file://` urls.
+pub static OFFLINE_SCHEME: &str = "newsflash-offline";
+// links of the enclosure list that are handled by the app instead of the browser
+pub static DOWNLOAD_PREFIX: &str = "newsflash-download:";
+pub static REMOVE_DOWNLOAD_PREFIX: &str = "newsflash-remove-download:";
+static ENCLOSURE_DIR: &str = "enclosures";
+static AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "aac", "ogg", "oga", "opus", "flac", "wav"];
+static VIDEO_EXTENSIONS: &[&str] = &["mp4", "m4v", "webm", "ogv", "mov"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MediaKind {
+    Audio,
+    Video,
+    Other,
+}
+
+/// Lists the attachments of an article (podcast episodes, videos, ...) below its header,
+/// audio and video get an embedded player that `enclosures.js` adds speed control to.
+pub struct Enclosures;
+
+impl Enclosures {
+    pub fn html(enclosures: &[Enclosure], settings: &Settings) -> String {
+        if enclosures.is_empty() {
+            return String::new();
+        }
+
+        let mut html = String::from("<section class=\"nf-enclosures\">");
+        for enclosure in enclosures {
+            let url = enclosure.url.as_str();
+            let title = enclosure
+                .title
+                .as_deref()
+                .filter(|title| !title.trim().is_empty())
+                .map(|title| title.to_owned())
+                .or_else(|| Self::file_name(url))
+                .unwrap_or_else(|| url.to_owned());
+            let offline = Self::offline_path(url).exists();
+
+            html.push_str("<div class=\"nf-enclosure\">");
+            html.push_str(&format!(
+                "<a class=\"nf-enclosure-title\" href=\"{}\">{}</a>",
+                HtmlSanitizer::escape(url),
+                HtmlSanitizer::escape(&title)
+            ));
+
+            let tag = match Self::media_kind(url, enclosure.mime_type.as_deref()) {
+                MediaKind::Audio => Some("audio"),
+                MediaKind::Video => Some("video"),
+                MediaKind::Other => None,
+            };
+            if let Some(tag) = tag {
+                let source = if offline {
+                    format!("{}:///{}", OFFLINE_SCHEME, Self::offline_name(url))
+                } else {
+                    url.to_owned()
+                };
+                let position = settings.get_article_view_enclosure_position(url).unwrap_or(0.0);
+                // nothing is fetched before the reader presses play
+                html.push_str(&format!(
+                    "<{tag} class=\"nf-enclosure-media\" controls preload=\"none\" src=\"{}\" \
+                     data-nf-url=\"{}\" data-nf-position=\"{}\"></{tag}>",
+                    HtmlSanitizer::escape(&source),
+                    HtmlSanitizer::escape(url),
+                    position,
+                    tag = tag
+                ));
+            }
+
+            html.push_str("<div class=\"nf-enclosure-actions\">");
+            if tag.is_some() {
+                html.push_str(&format!(
+                    "<button class=\"nf-enclosure-speed\" type=\"button\" title=\"{}\"></button>",
+                    i18n("Playback Speed")
+                ));
+            }
+            if offline {
+                html.push_str(&format!(
+                    "<span>{}</span><a href=\"{}{}\">{}</a>",
+                    i18n("Available offline"),
+                    REMOVE_DOWNLOAD_PREFIX,
+                    HtmlSanitizer::escape(url),
+                    i18n("Remove Download")
+                ));
+            } else {
+                html.push_str(&format!(
+                    "<a href=\"{}{}\">{}</a>",
+                    DOWNLOAD_PREFIX,
+                    HtmlSanitizer::escape(url),
+                    i18n("Download")
+                ));
+            }
+            html.push_str("</div></div>");
+        }
+        html.push_str("</section>");
+        html
+    }
+
+    /// Feeds don't always set a mime type, so fall back to the file extension.
+    pub fn media_kind(url: &str, mime_type: Option<&str>) -> MediaKind {
+        if let Some(mime_type) = mime_type {
+            let mime_type = mime_type.to_lowercase();
+            if mime_type.starts_with("audio/") {
+                return MediaKind::Audio;
+            }
+            if mime_type.starts_with("video/") {
+                return MediaKind::Video;
+            }
+        }
+
+        let extension = Self::extension(url).map(|extension| extension.to_lowercase());
+        match extension.as_deref() {
+            Some(extension) if AUDIO_EXTENSIONS.contains(&extension) => MediaKind::Audio,
+            Some(extension) if VIDEO_EXTENSIONS.contains(&extension) => MediaKind::Video,
+            _ => MediaKind::Other,
+        }
+    }
+
+    pub fn offline_dir() -> PathBuf {
+        crate::app::DATA_DIR.join(ENCLOSURE_DIR)
+    }
+
+    pub fn offline_path(url: &str) -> PathBuf {
+        Self::offline_dir().join(Self::offline_name(url))
+    }
+
+    /// Hash of the url that keeps the extension, so the media type can still be sniffed.
+    fn offline_name(url: &str) -> String {
+        // FNV-1a, unlike the std hasher it is stable across releases
+        let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        match Self::extension(url) {
+            Some(extension) => format!("{:016x}.{}", hash, extension),
+            None => format!("{:016x}", hash),
+        }
+    }
+
+    /// Serve the offline copies of the `OFFLINE_SCHEME`.
+    pub fn serve_offline(request: &URISchemeRequest) {
+        let name = request
+            .get_path()
+            .map(|path| path.trim_start_matches('/').to_owned())
+            .unwrap_or_default();
+        // only plain file names, never anything outside of the enclosure directory
+        let valid = !name.starts_with('.') && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.');
+        let path = Self::offline_dir().join(&name);
+
+        let cancellable: Option<&Cancellable> = None;
+        let stream = if valid {
+            fs::metadata(&path).ok().and_then(|metadata| {
+                gio::File::new_for_path(&path)
+                    .read(cancellable)
+                    .ok()
+                    .map(|stream| (stream, metadata.len()))
+            })
+        } else {
+            None
+        };
+        match stream {
+            Some((stream, length)) => request.finish(&stream, length as i64, None),
+            None => request.finish_error(&mut glib::Error::new(
+                IOErrorEnum::NotFound,
+                &format!("No offline copy '{}'", name),
+            )),
+        }
+    }
+
+    fn file_name(url: &str) -> Option<String> {
+        Url::parse(url)
+            .ok()?
+            .path_segments()?
+            .filter(|segment| !segment.is_empty())
+            .last()
+            .map(|segment| segment.to_owned())
+    }
+
+    fn extension(url: &str) -> Option<String> {
+        let name = Self::file_name(url)?;
+        let dot = name.rfind('.')?;
+        let extension = &name[dot + 1..];
+        if dot == 0 || extension.is_empty() || !extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        Some(extension.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Enclosures, MediaKind};
+
+    #[test]
+    fn media_kind() {
+        assert_eq!(
+            Enclosures::media_kind("https://example.com/feed/episode", Some("audio/mpeg")),
+            MediaKind::Audio
+        );
+        assert_eq!(
+            Enclosures::media_kind("https://example.com/episode.MP4?source=rss", None),
+            MediaKind::Video
+        );
+        assert_eq!(
+            Enclosures::media_kind("https://example.com/show-notes.pdf", Some("application/pdf")),
+            MediaKind::Other
+        );
+    }
+
+    #[test]
+    fn offline_name() {
+        let name = Enclosures::offline_name("https://example.com/episode-1.mp3?token=a");
+        assert!(name.ends_with(".mp3"));
+        assert_eq!(
+            name,
+            Enclosures::offline_name("https://example.com/episode-1.mp3?token=a")
+        );
+        assert_ne!(
+            name,
+            Enclosures::offline_name("https://example.com/episode-1.mp3?token=b")
+        );
+        assert!(!Enclosures::offline_name("https://example.com/").contains('.'));
+    }
+}
@@ -14,6 +14,8 @@ pub enum ArticleViewErrorKind {
     NoActiveWebView,
     #[fail(display = "Executed JS didn't return any value")]
     NoValueFromJS,
+    #[fail(display = "Failed to write user theme")]
+    UserTheme,
     #[fail(display = "Unknown Error")]
     Unknown,
 }
@@ -0,0 +1,228 @@
+use super::models::InternalState;
+use crate::i18n::{i18n, ni18n_f};
+use crate::util::BuilderHelper;
+use gdk::{keys::constants, ModifierType};
+use glib::{clone, object::Cast};
+use gtk::{
+    Button, ButtonExt, EditableExt, EntryExt, Inhibit, Label, LabelExt, Revealer, RevealerExt, SearchEntry,
+    SearchEntryExt, Stack, StackExt, StyleContextExt, ToggleButton, ToggleButtonExt, WidgetExt,
+};
+use parking_lot::RwLock;
+use std::sync::Arc;
+use webkit2gtk::{FindController, FindControllerExt, FindOptions, WebView, WebViewExt};
+
+const MAX_MATCH_COUNT: u32 = 1000;
+
+#[derive(Clone, Debug)]
+pub struct FindBar {
+    revealer: Revealer,
+    entry: SearchEntry,
+    count_label: Label,
+    case_button: ToggleButton,
+    wrap_button: ToggleButton,
+    stack: Stack,
+    internal_state: Arc<RwLock<InternalState>>,
+}
+
+impl FindBar {
+    pub fn new(stack: &Stack, internal_state: &Arc<RwLock<InternalState>>) -> Self {
+        let builder = BuilderHelper::new("article_view_find");
+        let revealer = builder.get::<Revealer>("revealer");
+        let entry = builder.get::<SearchEntry>("entry");
+        let count_label = builder.get::<Label>("count_label");
+        let case_button = builder.get::<ToggleButton>("case_button");
+        let wrap_button = builder.get::<ToggleButton>("wrap_button");
+        let prev_button = builder.get::<Button>("prev_button");
+        let next_button = builder.get::<Button>("next_button");
+        let close_button = builder.get::<Button>("close_button");
+
+        let find_bar = FindBar {
+            revealer,
+            entry,
+            count_label,
+            case_button,
+            wrap_button,
+            stack: stack.clone(),
+            internal_state: internal_state.clone(),
+        };
+
+        for state in &[InternalState::View1, InternalState::View2] {
+            let controller = state
+                .to_str()
+                .and_then(|view_name| stack.get_child_by_name(view_name))
+                .and_then(|view| view.downcast::<WebView>().ok())
+                .and_then(|view| view.get_find_controller());
+            if let Some(controller) = controller {
+                controller.connect_counted_matches(clone!(
+                    @weak find_bar.count_label as count_label,
+                    @weak find_bar.entry as entry => @default-panic, move |_controller, count|
+                {
+                    Self::set_match_count(&count_label, &entry, count);
+                }));
+                controller.connect_failed_to_find_text(clone!(
+                    @weak find_bar.count_label as count_label,
+                    @weak find_bar.entry as entry => @default-panic, move |_controller|
+                {
+                    Self::set_match_count(&count_label, &entry, 0);
+                }));
+            }
+        }
+
+        find_bar
+            .entry
+            .connect_search_changed(clone!(@strong find_bar => @default-panic, move |_entry| {
+                find_bar.search();
+            }));
+        find_bar
+            .entry
+            .connect_activate(clone!(@strong find_bar => @default-panic, move |_entry| {
+                find_bar.find_next();
+            }));
+        find_bar
+            .entry
+            .connect_key_press_event(clone!(@strong find_bar => @default-panic, move |_entry, event| {
+                let keyval = event.get_keyval();
+                if keyval == constants::Escape {
+                    find_bar.hide();
+                    return Inhibit(true);
+                }
+                if (keyval == constants::Return || keyval == constants::KP_Enter)
+                    && event.get_state().contains(ModifierType::SHIFT_MASK)
+                {
+                    find_bar.find_previous();
+                    return Inhibit(true);
+                }
+                Inhibit(false)
+            }));
+        find_bar
+            .case_button
+            .connect_toggled(clone!(@strong find_bar => @default-panic, move |_button| {
+                find_bar.search();
+            }));
+        find_bar
+            .wrap_button
+            .connect_toggled(clone!(@strong find_bar => @default-panic, move |_button| {
+                find_bar.search();
+            }));
+        prev_button.connect_clicked(clone!(@strong find_bar => @default-panic, move |_button| {
+            find_bar.find_previous();
+        }));
+        next_button.connect_clicked(clone!(@strong find_bar => @default-panic, move |_button| {
+            find_bar.find_next();
+        }));
+        close_button.connect_clicked(clone!(@strong find_bar => @default-panic, move |_button| {
+            find_bar.hide();
+        }));
+
+        find_bar
+    }
+
+    pub fn widget(&self) -> Revealer {
+        self.revealer.clone()
+    }
+
+    /// Reveal the bar and focus the entry. Text that is already entered gets selected so typing replaces it.
+    pub fn show(&self) {
+        self.revealer.set_reveal_child(true);
+        self.entry.grab_focus();
+        self.entry.select_region(0, -1);
+        self.search();
+    }
+
+    pub fn hide(&self) {
+        if !self.revealer.get_reveal_child() {
+            return;
+        }
+        self.revealer.set_reveal_child(false);
+        if let Some(controller) = self.find_controller() {
+            controller.search_finish();
+        }
+        self.count_label.set_text("");
+        self.entry.get_style_context().remove_class("error");
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.revealer.get_reveal_child()
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.is_visible() && self.entry.has_focus()
+    }
+
+    pub fn find_next(&self) {
+        if !self.is_visible() {
+            self.show();
+            return;
+        }
+        if let Some(controller) = self.find_controller() {
+            if controller.get_search_text().is_some() {
+                controller.search_next();
+            } else {
+                self.search();
+            }
+        }
+    }
+
+    pub fn find_previous(&self) {
+        if !self.is_visible() {
+            self.show();
+            return;
+        }
+        if let Some(controller) = self.find_controller() {
+            if controller.get_search_text().is_some() {
+                controller.search_previous();
+            } else {
+                self.search();
+            }
+        }
+    }
+
+    fn search(&self) {
+        let controller = match self.find_controller() {
+            Some(controller) => controller,
+            None => return,
+        };
+
+        let text = self.entry.get_text();
+        if text.is_empty() {
+            controller.search_finish();
+            self.count_label.set_text("");
+            self.entry.get_style_context().remove_class("error");
+            return;
+        }
+
+        let options = self.find_options();
+        controller.count_matches(&text, options.bits(), MAX_MATCH_COUNT);
+        controller.search(&text, options.bits(), MAX_MATCH_COUNT);
+    }
+
+    fn find_options(&self) -> FindOptions {
+        let mut options = FindOptions::NONE;
+        if !self.case_button.get_active() {
+            options |= FindOptions::CASE_INSENSITIVE;
+        }
+        if self.wrap_button.get_active() {
+            options |= FindOptions::WRAP_AROUND;
+        }
+        options
+    }
+
+    fn find_controller(&self) -> Option<FindController> {
+        self.internal_state
+            .read()
+            .to_str()
+            .and_then(|view_name| self.stack.get_child_by_name(view_name))
+            .and_then(|view| view.downcast::<WebView>().ok())
+            .and_then(|view| view.get_find_controller())
+    }
+
+    fn set_match_count(count_label: &Label, entry: &SearchEntry, count: u32) {
+        if count == 0 {
+            count_label.set_text(&i18n("No matches"));
+            entry.get_style_context().add_class("error");
+        } else {
+            count_label.set_text(&ni18n_f("{} match", "{} matches", count, &[&count.to_string()]));
+            entry.get_style_context().remove_class("error");
+        }
+    }
+}
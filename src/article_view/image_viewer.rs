@@ -0,0 +1,442 @@
+use super::models::InternalState;
+use crate::app::Action;
+use crate::i18n::i18n;
+use crate::util::{BuilderHelper, Util};
+use gdk::{keys::constants, EventMask, ModifierType, ScrollDirection};
+use gdk_pixbuf::{InterpType, Pixbuf};
+use gio::{Cancellable, MemoryInputStream};
+use glib::{clone, object::Cast, Bytes, Sender};
+use gtk::{
+    AdjustmentExt, Box, Button, ButtonExt, DialogExt, EventBox, FileChooserAction, FileChooserDialog, FileChooserExt,
+    Image, ImageExt, Inhibit, Label, LabelExt, ResponseType, Revealer, RevealerExt, ScrolledWindow, ScrolledWindowExt,
+    Stack, StackExt, WidgetExt, Window,
+};
+use log::warn;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use url::Url;
+use webkit2gtk::{DownloadExt, WebContext, WebContextExt, WebResource, WebResourceExt, WebView, WebViewExt};
+
+const MIN_ZOOM: f64 = 0.1;
+const MAX_ZOOM: f64 = 8.0;
+const ZOOM_STEP: f64 = 1.25;
+
+/// Lightbox that shows the images of the visible article on top of the article view.
+/// The image data comes from the resources the webview already loaded.
+#[derive(Clone, Debug)]
+pub struct ImageViewer {
+    revealer: Revealer,
+    viewer_box: Box,
+    image_stack: Stack,
+    scrolled: ScrolledWindow,
+    image: Image,
+    position_label: Label,
+    prev_button: Button,
+    next_button: Button,
+    sender: Sender<Action>,
+    images: Arc<RwLock<Vec<String>>>,
+    index: Arc<RwLock<usize>>,
+    pixbuf: Arc<RwLock<Option<Pixbuf>>>,
+    // `None` fits the image into the viewer
+    zoom: Arc<RwLock<Option<f64>>>,
+    resources: Arc<RwLock<HashMap<String, WebResource>>>,
+    // downloads the images the article never loaded
+    web_context: WebContext,
+    drag_start: Arc<RwLock<Option<(f64, f64, f64, f64)>>>,
+}
+
+impl ImageViewer {
+    pub fn new(stack: &Stack, web_context: &WebContext, sender: &Sender<Action>) -> Self {
+        let builder = BuilderHelper::new("article_view_image_viewer");
+        let event_box = builder.get::<EventBox>("event_box");
+        let zoom_in_button = builder.get::<Button>("zoom_in_button");
+        let zoom_out_button = builder.get::<Button>("zoom_out_button");
+        let zoom_fit_button = builder.get::<Button>("zoom_fit_button");
+        let save_all_button = builder.get::<Button>("save_all_button");
+        let close_button = builder.get::<Button>("close_button");
+
+        let viewer = ImageViewer {
+            revealer: builder.get::<Revealer>("revealer"),
+            viewer_box: builder.get::<Box>("viewer_box"),
+            image_stack: builder.get::<Stack>("image_stack"),
+            scrolled: builder.get::<ScrolledWindow>("scrolled"),
+            image: builder.get::<Image>("image"),
+            position_label: builder.get::<Label>("position_label"),
+            prev_button: builder.get::<Button>("prev_button"),
+            next_button: builder.get::<Button>("next_button"),
+            sender: sender.clone(),
+            images: Arc::new(RwLock::new(Vec::new())),
+            index: Arc::new(RwLock::new(0)),
+            pixbuf: Arc::new(RwLock::new(None)),
+            zoom: Arc::new(RwLock::new(None)),
+            resources: Arc::new(RwLock::new(HashMap::new())),
+            web_context: web_context.clone(),
+            drag_start: Arc::new(RwLock::new(None)),
+        };
+        viewer.revealer.set_no_show_all(true);
+
+        for state in &[InternalState::View1, InternalState::View2] {
+            let view = state
+                .to_str()
+                .and_then(|view_name| stack.get_child_by_name(view_name))
+                .and_then(|view| view.downcast::<WebView>().ok());
+            if let Some(view) = view {
+                view.connect_resource_load_started(clone!(
+                    @weak viewer.resources as resources => @default-panic, move |_view, resource, _request|
+                {
+                    if let Some(uri) = resource.get_uri() {
+                        resources.write().insert(uri.to_string(), resource.clone());
+                    }
+                }));
+            }
+        }
+
+        viewer
+            .prev_button
+            .connect_clicked(clone!(@strong viewer => @default-panic, move |_button| {
+                viewer.previous();
+            }));
+        viewer
+            .next_button
+            .connect_clicked(clone!(@strong viewer => @default-panic, move |_button| {
+                viewer.next();
+            }));
+        zoom_in_button.connect_clicked(clone!(@strong viewer => @default-panic, move |_button| {
+            viewer.zoom_by(ZOOM_STEP);
+        }));
+        zoom_out_button.connect_clicked(clone!(@strong viewer => @default-panic, move |_button| {
+            viewer.zoom_by(1.0 / ZOOM_STEP);
+        }));
+        zoom_fit_button.connect_clicked(clone!(@strong viewer => @default-panic, move |_button| {
+            viewer.zoom_fit();
+        }));
+        save_all_button.connect_clicked(clone!(@strong viewer => @default-panic, move |_button| {
+            viewer.save_all();
+        }));
+        close_button.connect_clicked(clone!(@strong viewer => @default-panic, move |_button| {
+            viewer.hide();
+        }));
+
+        viewer
+            .viewer_box
+            .connect_key_press_event(clone!(@strong viewer => @default-panic, move |_widget, event| {
+                match event.get_keyval() {
+                    constants::Escape => viewer.hide(),
+                    constants::Left => viewer.previous(),
+                    constants::Right => viewer.next(),
+                    constants::plus | constants::KP_Add => viewer.zoom_by(ZOOM_STEP),
+                    constants::minus | constants::KP_Subtract => viewer.zoom_by(1.0 / ZOOM_STEP),
+                    constants::_0 | constants::KP_0 => viewer.zoom_fit(),
+                    _ => return Inhibit(false),
+                }
+                Inhibit(true)
+            }));
+
+        // re-fit the image when the viewer is resized
+        viewer
+            .scrolled
+            .connect_size_allocate(clone!(@strong viewer => @default-panic, move |_scrolled, _allocation| {
+                if viewer.zoom.read().is_none() {
+                    viewer.apply_zoom();
+                }
+            }));
+
+        // zoom with the scroll wheel, pan by dragging
+        event_box.set_events(
+            EventMask::BUTTON_PRESS_MASK
+                | EventMask::BUTTON_RELEASE_MASK
+                | EventMask::POINTER_MOTION_MASK
+                | EventMask::SCROLL_MASK
+                | EventMask::SMOOTH_SCROLL_MASK,
+        );
+        event_box.connect_scroll_event(clone!(@strong viewer => @default-panic, move |_widget, event| {
+            match event.get_direction() {
+                ScrollDirection::Up => viewer.zoom_by(ZOOM_STEP),
+                ScrollDirection::Down => viewer.zoom_by(1.0 / ZOOM_STEP),
+                ScrollDirection::Smooth => {
+                    let (_, y_delta) = event.get_delta();
+                    if y_delta < 0.0 {
+                        viewer.zoom_by(ZOOM_STEP);
+                    } else if y_delta > 0.0 {
+                        viewer.zoom_by(1.0 / ZOOM_STEP);
+                    }
+                }
+                _ => return Inhibit(false),
+            }
+            Inhibit(true)
+        }));
+        event_box.connect_button_press_event(clone!(@strong viewer => @default-panic, move |_widget, event| {
+            if event.get_button() != 1 {
+                return Inhibit(false);
+            }
+            let (x, y) = event.get_root();
+            let h_value = viewer.scrolled.get_hadjustment().map(|adj| adj.get_value()).unwrap_or(0.0);
+            let v_value = viewer.scrolled.get_vadjustment().map(|adj| adj.get_value()).unwrap_or(0.0);
+            viewer.drag_start.write().replace((x, y, h_value, v_value));
+            Inhibit(true)
+        }));
+        event_box.connect_button_release_event(clone!(@strong viewer => @default-panic, move |_widget, _event| {
+            viewer.drag_start.write().take();
+            Inhibit(false)
+        }));
+        event_box.connect_motion_notify_event(clone!(@strong viewer => @default-panic, move |_widget, event| {
+            if event.get_state().contains(ModifierType::BUTTON1_MASK) {
+                if let Some((start_x, start_y, h_value, v_value)) = *viewer.drag_start.read() {
+                    let (x, y) = event.get_root();
+                    if let Some(adjustment) = viewer.scrolled.get_hadjustment() {
+                        adjustment.set_value(h_value - (x - start_x));
+                    }
+                    if let Some(adjustment) = viewer.scrolled.get_vadjustment() {
+                        adjustment.set_value(v_value - (y - start_y));
+                    }
+                }
+            }
+            Inhibit(false)
+        }));
+
+        viewer
+    }
+
+    pub fn widget(&self) -> Revealer {
+        self.revealer.clone()
+    }
+
+    /// Forget the resources of the previous article.
+    pub fn reset(&self) {
+        self.hide();
+        self.resources.write().clear();
+        self.images.write().clear();
+    }
+
+    /// Open the viewer at `uri` with all `images` of the article to page through.
+    pub fn show(&self, images: Vec<String>, uri: &str) {
+        let mut images = images;
+        let index = match images.iter().position(|image| image == uri) {
+            Some(index) => index,
+            None => {
+                images.insert(0, uri.to_owned());
+                0
+            }
+        };
+        *self.images.write() = images;
+        *self.index.write() = index;
+
+        self.revealer.show();
+        self.revealer.set_reveal_child(true);
+        self.viewer_box.grab_focus();
+        self.load_current();
+    }
+
+    pub fn hide(&self) {
+        self.revealer.set_reveal_child(false);
+        self.revealer.hide();
+        self.pixbuf.write().take();
+        self.image.clear();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.revealer.get_reveal_child()
+    }
+
+    pub fn next(&self) {
+        let index = *self.index.read();
+        if index + 1 < self.images.read().len() {
+            *self.index.write() = index + 1;
+            self.load_current();
+        }
+    }
+
+    pub fn previous(&self) {
+        let index = *self.index.read();
+        if index > 0 {
+            *self.index.write() = index - 1;
+            self.load_current();
+        }
+    }
+
+    fn load_current(&self) {
+        let index = *self.index.read();
+        let count = self.images.read().len();
+        let uri = match self.images.read().get(index) {
+            Some(uri) => uri.clone(),
+            None => return,
+        };
+
+        self.position_label.set_text(&format!("{} / {}", index + 1, count));
+        self.prev_button.set_sensitive(index > 0);
+        self.next_button.set_sensitive(index + 1 < count);
+        *self.zoom.write() = None;
+        self.pixbuf.write().take();
+        self.image_stack.set_visible_child_name("spinner");
+
+        let resource = match self.resources.read().get(&uri) {
+            Some(resource) => resource.clone(),
+            None => {
+                self.image_stack.set_visible_child_name("error");
+                return;
+            }
+        };
+
+        let viewer = self.clone();
+        let cancellable: Option<&Cancellable> = None;
+        resource.get_data(cancellable, move |result| {
+            // the user may have moved on while the data was loading
+            if viewer.images.read().get(*viewer.index.read()) != Some(&uri) {
+                return;
+            }
+            match result.ok().and_then(|data| Self::pixbuf_from_data(&data)) {
+                Some(pixbuf) => {
+                    viewer.pixbuf.write().replace(pixbuf);
+                    viewer.image_stack.set_visible_child_name("image");
+                    viewer.apply_zoom();
+                }
+                None => viewer.image_stack.set_visible_child_name("error"),
+            }
+        });
+    }
+
+    fn pixbuf_from_data(data: &[u8]) -> Option<Pixbuf> {
+        let bytes = Bytes::from(data);
+        let stream = MemoryInputStream::from_bytes(&bytes);
+        let cancellable: Option<&Cancellable> = None;
+        Pixbuf::from_stream(&stream, cancellable).ok()
+    }
+
+    fn zoom_by(&self, factor: f64) {
+        let zoom = self.current_zoom() * factor;
+        *self.zoom.write() = Some(zoom.max(MIN_ZOOM).min(MAX_ZOOM));
+        self.apply_zoom();
+    }
+
+    fn zoom_fit(&self) {
+        *self.zoom.write() = None;
+        self.apply_zoom();
+    }
+
+    fn current_zoom(&self) -> f64 {
+        if let Some(zoom) = *self.zoom.read() {
+            return zoom;
+        }
+        let pixbuf = match &*self.pixbuf.read() {
+            Some(pixbuf) => pixbuf.clone(),
+            None => return 1.0,
+        };
+        let allocation = self.scrolled.get_allocation();
+        let fit_width = f64::from(allocation.width) / f64::from(pixbuf.get_width());
+        let fit_height = f64::from(allocation.height) / f64::from(pixbuf.get_height());
+        // never blow up small images just to fill the space
+        fit_width.min(fit_height).min(1.0).max(MIN_ZOOM)
+    }
+
+    fn apply_zoom(&self) {
+        let pixbuf = match &*self.pixbuf.read() {
+            Some(pixbuf) => pixbuf.clone(),
+            None => return,
+        };
+        let zoom = self.current_zoom();
+        let width = (f64::from(pixbuf.get_width()) * zoom).round().max(1.0) as i32;
+        let height = (f64::from(pixbuf.get_height()) * zoom).round().max(1.0) as i32;
+        if width == pixbuf.get_width() && height == pixbuf.get_height() {
+            self.image.set_from_pixbuf(Some(&pixbuf));
+        } else if let Some(scaled) = pixbuf.scale_simple(width, height, InterpType::Bilinear) {
+            self.image.set_from_pixbuf(Some(&scaled));
+        }
+    }
+
+    fn save_all(&self) {
+        let parent = self
+            .revealer
+            .get_toplevel()
+            .and_then(|toplevel| toplevel.downcast::<Window>().ok());
+        let dialog = FileChooserDialog::with_buttons(
+            Some(&i18n("Save All Images")),
+            parent.as_ref(),
+            FileChooserAction::SelectFolder,
+            &[
+                (&i18n("Cancel"), ResponseType::Cancel),
+                (&i18n("Save"), ResponseType::Ok),
+            ],
+        );
+
+        if let ResponseType::Ok = dialog.run() {
+            match dialog.get_filename() {
+                Some(directory) => {
+                    let images = self.images.read().clone();
+                    for (index, uri) in images.iter().enumerate() {
+                        let path = directory.join(Self::file_name(index, uri));
+                        let resource = match self.resources.read().get(uri) {
+                            Some(resource) => resource.clone(),
+                            None => {
+                                // e.g. held back by the data saver
+                                self.download(uri, &path);
+                                continue;
+                            }
+                        };
+                        let sender = self.sender.clone();
+                        let cancellable: Option<&Cancellable> = None;
+                        resource.get_data(cancellable, move |result| {
+                            let saved = result.ok().map(|data| fs::write(&path, data).is_ok()).unwrap_or(false);
+                            if !saved {
+                                Util::send(
+                                    &sender,
+                                    Action::ErrorSimpleMessage(format!("Failed to save image '{}'.", path.display())),
+                                );
+                            }
+                        });
+                    }
+                }
+                None => Util::send(&self.sender, Action::ErrorSimpleMessage("No folder set.".to_owned())),
+            }
+        }
+        dialog.emit_close();
+    }
+
+    fn download(&self, uri: &str, path: &Path) {
+        let destination = glib::filename_to_uri(path, None).ok();
+        let download = self.web_context.download_uri(uri);
+        let (download, destination) = match (download, destination) {
+            (Some(download), Some(destination)) => (download, destination),
+            _ => {
+                warn!("Failed to start download of image '{}'", uri);
+                Util::send(
+                    &self.sender,
+                    Action::ErrorSimpleMessage(format!("Failed to save image '{}'.", path.display())),
+                );
+                return;
+            }
+        };
+        download.set_allow_overwrite(true);
+        download.set_destination(&destination);
+        let path = path.to_owned();
+        download.connect_failed(
+            clone!(@strong self.sender as sender => @default-panic, move |_download, error| {
+                Util::send(
+                    &sender,
+                    Action::ErrorSimpleMessage(format!("Failed to save image '{}': {}", path.display(), error)),
+                );
+            }),
+        );
+    }
+
+    /// Number the files so they keep the order of the article and never collide.
+    fn file_name(index: usize, uri: &str) -> String {
+        let name = Url::parse(uri)
+            .ok()
+            .and_then(|url| {
+                url.path_segments()
+                    .and_then(|segments| segments.filter(|segment| !segment.is_empty()).last())
+                    .map(|segment| segment.to_owned())
+            })
+            .and_then(|segment| {
+                Path::new(&segment)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| name.to_owned())
+            })
+            .unwrap_or_else(|| "image".to_owned());
+        format!("{:02}-{}", index + 1, name)
+    }
+}
@@ -0,0 +1,517 @@
+use super::sanitizer::HtmlSanitizer;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    static ref TAG: Regex =
+        Regex::new(r#"<(/?)([a-zA-Z][a-zA-Z0-9-]*)(?:"[^"]*"|'[^']*'|[^'">])*>"#).expect("invalid tag regex");
+    static ref FORMULA: Regex =
+        Regex::new(r"(?s)\$\$(.+?)\$\$|\\\[(.+?)\\\]|\\\((.+?)\\\)").expect("invalid formula regex");
+    static ref ENTITY: Regex = Regex::new(r"&(amp|lt|gt|quot|nbsp);").expect("invalid entity regex");
+}
+
+// longer or deeper nested formulas are shown as they are
+static MAX_FORMULA_LENGTH: usize = 2_000;
+static MAX_DEPTH: usize = 32;
+// formulas inside of these are shown as they are
+static VERBATIM_ELEMENTS: &[&str] = &["pre", "code", "script", "style", "textarea", "math"];
+
+static GREEK: &[(&str, char)] = &[
+    ("alpha", 'α'),
+    ("beta", 'β'),
+    ("gamma", 'γ'),
+    ("delta", 'δ'),
+    ("epsilon", 'ϵ'),
+    ("varepsilon", 'ε'),
+    ("zeta", 'ζ'),
+    ("eta", 'η'),
+    ("theta", 'θ'),
+    ("vartheta", 'ϑ'),
+    ("iota", 'ι'),
+    ("kappa", 'κ'),
+    ("lambda", 'λ'),
+    ("mu", 'μ'),
+    ("nu", 'ν'),
+    ("xi", 'ξ'),
+    ("pi", 'π'),
+    ("rho", 'ρ'),
+    ("sigma", 'σ'),
+    ("tau", 'τ'),
+    ("upsilon", 'υ'),
+    ("phi", 'ϕ'),
+    ("varphi", 'φ'),
+    ("chi", 'χ'),
+    ("psi", 'ψ'),
+    ("omega", 'ω'),
+    ("Gamma", 'Γ'),
+    ("Delta", 'Δ'),
+    ("Theta", 'Θ'),
+    ("Lambda", 'Λ'),
+    ("Xi", 'Ξ'),
+    ("Pi", 'Π'),
+    ("Sigma", 'Σ'),
+    ("Phi", 'Φ'),
+    ("Psi", 'Ψ'),
+    ("Omega", 'Ω'),
+    ("infty", '∞'),
+    ("partial", '∂'),
+    ("nabla", '∇'),
+    ("hbar", 'ℏ'),
+    ("ell", 'ℓ'),
+    ("emptyset", '∅'),
+];
+
+static OPERATORS: &[(&str, char)] = &[
+    ("times", '×'),
+    ("cdot", '⋅'),
+    ("div", '÷'),
+    ("pm", '±'),
+    ("mp", '∓'),
+    ("ast", '∗'),
+    ("circ", '∘'),
+    ("leq", '≤'),
+    ("le", '≤'),
+    ("geq", '≥'),
+    ("ge", '≥'),
+    ("neq", '≠'),
+    ("ne", '≠'),
+    ("approx", '≈'),
+    ("equiv", '≡'),
+    ("sim", '∼'),
+    ("simeq", '≃'),
+    ("propto", '∝'),
+    ("ll", '≪'),
+    ("gg", '≫'),
+    ("in", '∈'),
+    ("notin", '∉'),
+    ("subset", '⊂'),
+    ("subseteq", '⊆'),
+    ("supset", '⊃'),
+    ("cup", '∪'),
+    ("cap", '∩'),
+    ("setminus", '∖'),
+    ("land", '∧'),
+    ("wedge", '∧'),
+    ("lor", '∨'),
+    ("vee", '∨'),
+    ("neg", '¬'),
+    ("forall", '∀'),
+    ("exists", '∃'),
+    ("to", '→'),
+    ("rightarrow", '→'),
+    ("leftarrow", '←'),
+    ("Rightarrow", '⇒'),
+    ("Leftarrow", '⇐'),
+    ("leftrightarrow", '↔'),
+    ("Leftrightarrow", '⇔'),
+    ("implies", '⟹'),
+    ("iff", '⟺'),
+    ("mapsto", '↦'),
+    ("sum", '∑'),
+    ("prod", '∏'),
+    ("int", '∫'),
+    ("oint", '∮'),
+    ("ldots", '…'),
+    ("cdots", '⋯'),
+    ("vdots", '⋮'),
+    ("dots", '…'),
+    ("langle", '⟨'),
+    ("rangle", '⟩'),
+    ("lfloor", '⌊'),
+    ("rfloor", '⌋'),
+    ("lceil", '⌈'),
+    ("rceil", '⌉'),
+    ("mid", '∣'),
+    ("parallel", '∥'),
+    ("perp", '⊥'),
+    ("angle", '∠'),
+    ("prime", '′'),
+];
+
+static FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh", "log", "ln", "lg",
+    "exp", "lim", "max", "min", "sup", "inf", "det", "dim", "gcd", "deg", "arg", "ker", "Pr",
+];
+
+static DOUBLE_STRUCK: &[(char, char)] = &[('C', 'ℂ'), ('N', 'ℕ'), ('P', 'ℙ'), ('Q', 'ℚ'), ('R', 'ℝ'), ('Z', 'ℤ')];
+
+/// Renders LaTeX formulas of an article as MathML, which WebKit draws by itself.
+/// `$$…$$` and `\[…\]` are shown as blocks, `\(…\)` inline. Single dollar signs are too common
+/// in regular text to be taken as delimiters. Formulas that can't be parsed are left untouched.
+pub struct Math;
+
+impl Math {
+    pub fn render(html: &str) -> String {
+        let mut output = String::with_capacity(html.len());
+        let mut verbatim_depth = 0usize;
+        let mut last = 0;
+
+        for tag in TAG.captures_iter(html) {
+            let whole = tag.get(0).expect("regex match without group 0");
+            output.push_str(&Self::render_text(&html[last..whole.start()], verbatim_depth > 0));
+            output.push_str(whole.as_str());
+            last = whole.end();
+
+            let name = tag[2].to_lowercase();
+            if VERBATIM_ELEMENTS.contains(&name.as_str()) {
+                if tag[1].is_empty() {
+                    verbatim_depth += 1;
+                } else {
+                    verbatim_depth = verbatim_depth.saturating_sub(1);
+                }
+            }
+        }
+        output.push_str(&Self::render_text(&html[last..], verbatim_depth > 0));
+        output
+    }
+
+    fn render_text(text: &str, verbatim: bool) -> String {
+        if verbatim || !(text.contains("$$") || text.contains("\\[") || text.contains("\\(")) {
+            return text.to_owned();
+        }
+        FORMULA
+            .replace_all(text, |captures: &Captures| {
+                let (tex, display) = match (captures.get(1), captures.get(2), captures.get(3)) {
+                    (Some(tex), _, _) | (_, Some(tex), _) => (tex.as_str(), true),
+                    (_, _, Some(tex)) => (tex.as_str(), false),
+                    _ => return captures[0].to_owned(),
+                };
+                if tex.len() > MAX_FORMULA_LENGTH {
+                    return captures[0].to_owned();
+                }
+                let tex = ENTITY.replace_all(tex, |entity: &Captures| match &entity[1] {
+                    "amp" => "&",
+                    "lt" => "<",
+                    "gt" => ">",
+                    "quot" => "\"",
+                    _ => " ",
+                });
+                match Self::to_mathml(&tex) {
+                    Some(mathml) if display => format!("<math display=\"block\">{}</math>", mathml),
+                    Some(mathml) => format!("<math>{}</math>", mathml),
+                    None => captures[0].to_owned(),
+                }
+            })
+            .into_owned()
+    }
+
+    fn to_mathml(tex: &str) -> Option<String> {
+        let mut parser = Parser {
+            chars: tex.chars().collect(),
+            pos: 0,
+            depth: 0,
+        };
+        let row = parser.parse_row(false)?;
+        if parser.pos < parser.chars.len() {
+            return None;
+        }
+        Some(format!("<mrow>{}</mrow>", row))
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    /// Runs `parse` one level deeper, gives up once the formula is nested too deeply.
+    fn nested<F: FnOnce(&mut Self) -> Option<String>>(&mut self, parse: F) -> Option<String> {
+        if self.depth >= MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_row(&mut self, in_group: bool) -> Option<String> {
+        self.nested(|parser| parser.row(in_group))
+    }
+
+    /// A sequence of atoms with their sub- and superscripts, up to the closing brace of a group.
+    fn row(&mut self, in_group: bool) -> Option<String> {
+        let mut row = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None if in_group => return None,
+                None => return Some(row),
+                Some('}') if in_group => {
+                    self.pos += 1;
+                    return Some(row);
+                }
+                Some('}') => return None,
+                Some('^') | Some('_') => {
+                    // a script without a base
+                    let scripted = self.parse_scripts(String::from("<mrow></mrow>"))?;
+                    row.push_str(&scripted);
+                }
+                Some(_) => {
+                    let atom = self.parse_atom()?;
+                    let scripted = self.parse_scripts(atom)?;
+                    row.push_str(&scripted);
+                }
+            }
+        }
+    }
+
+    fn parse_scripts(&mut self, base: String) -> Option<String> {
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('_') if sub.is_none() => {
+                    self.pos += 1;
+                    sub = Some(self.parse_argument()?);
+                }
+                Some('^') if sup.is_none() => {
+                    self.pos += 1;
+                    sup = Some(self.parse_argument()?);
+                }
+                Some('\'') => {
+                    self.pos += 1;
+                    sup = Some(format!("{}<mo>′</mo>", sup.unwrap_or_default()));
+                }
+                _ => break,
+            }
+        }
+        Some(match (sub, sup) {
+            (Some(sub), Some(sup)) => format!("<msubsup>{}{}<mrow>{}</mrow></msubsup>", base, sub, sup),
+            (Some(sub), None) => format!("<msub>{}{}</msub>", base, sub),
+            (None, Some(sup)) => format!("<msup>{}<mrow>{}</mrow></msup>", base, sup),
+            (None, None) => base,
+        })
+    }
+
+    fn parse_argument(&mut self) -> Option<String> {
+        self.nested(|parser| parser.argument())
+    }
+
+    /// The argument of a command or script: a group or a single atom.
+    fn argument(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        match self.peek()? {
+            '{' => {
+                self.pos += 1;
+                Some(format!("<mrow>{}</mrow>", self.parse_row(true)?))
+            }
+            // digits only belong to a script one at a time, x^23 is x²3
+            c if c.is_ascii_digit() => {
+                self.pos += 1;
+                Some(format!("<mn>{}</mn>", c))
+            }
+            _ => self.parse_atom(),
+        }
+    }
+
+    /// The raw text of a group, e.g. the argument of `\text`.
+    fn parse_text_argument(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        if self.peek()? != '{' {
+            return self.next().map(|c| c.to_string());
+        }
+        self.pos += 1;
+        let mut depth = 1;
+        let mut text = String::new();
+        while let Some(c) = self.next() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(text);
+                    }
+                }
+                _ => {}
+            }
+            text.push(c);
+        }
+        None
+    }
+
+    fn parse_atom(&mut self) -> Option<String> {
+        let c = self.next()?;
+        Some(match c {
+            '{' => format!("<mrow>{}</mrow>", self.parse_row(true)?),
+            '\\' => self.parse_command()?,
+            '0'..='9' | '.' => {
+                let mut number = c.to_string();
+                while let Some(next) = self.peek().filter(|c| c.is_ascii_digit() || *c == '.') {
+                    number.push(next);
+                    self.pos += 1;
+                }
+                format!("<mn>{}</mn>", number)
+            }
+            c if c.is_alphabetic() => format!("<mi>{}</mi>", c),
+            '&' => String::from("<mspace width=\"1em\"></mspace>"),
+            '-' => String::from("<mo>−</mo>"),
+            '*' => String::from("<mo>∗</mo>"),
+            '~' => String::from("<mspace width=\"0.33em\"></mspace>"),
+            c => format!("<mo>{}</mo>", HtmlSanitizer::escape(&c.to_string())),
+        })
+    }
+
+    fn parse_command(&mut self) -> Option<String> {
+        let mut name = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphabetic()) {
+            name.push(c);
+            self.pos += 1;
+        }
+        if name.is_empty() {
+            // a single character command: escaped characters and spacing
+            let c = self.next()?;
+            return Some(match c {
+                ',' => String::from("<mspace width=\"0.17em\"></mspace>"),
+                ':' | '>' => String::from("<mspace width=\"0.22em\"></mspace>"),
+                ';' => String::from("<mspace width=\"0.28em\"></mspace>"),
+                ' ' => String::from("<mspace width=\"0.33em\"></mspace>"),
+                '!' => String::new(),
+                '\\' => String::from("<mspace linebreak=\"newline\"></mspace>"),
+                '|' => String::from("<mo>∥</mo>"),
+                c => format!("<mo>{}</mo>", HtmlSanitizer::escape(&c.to_string())),
+            });
+        }
+
+        if let Some((_, c)) = GREEK.iter().find(|(command, _)| *command == name) {
+            return Some(format!("<mi>{}</mi>", c));
+        }
+        if let Some((_, c)) = OPERATORS.iter().find(|(command, _)| *command == name) {
+            return Some(format!("<mo>{}</mo>", c));
+        }
+        if FUNCTIONS.contains(&name.as_str()) {
+            return Some(format!("<mi mathvariant=\"normal\">{}</mi>", name));
+        }
+
+        Some(match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                format!("<mfrac>{}{}</mfrac>", numerator, denominator)
+            }
+            "binom" => {
+                let top = self.parse_argument()?;
+                let bottom = self.parse_argument()?;
+                format!(
+                    "<mrow><mo>(</mo><mfrac linethickness=\"0\">{}{}</mfrac><mo>)</mo></mrow>",
+                    top, bottom
+                )
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                if self.peek() == Some('[') {
+                    self.pos += 1;
+                    let mut index = String::new();
+                    while let Some(c) = self.peek().filter(|c| *c != ']') {
+                        index.push(c);
+                        self.pos += 1;
+                    }
+                    self.next()?;
+                    let index = Math::to_mathml(&index)?;
+                    let radicand = self.parse_argument()?;
+                    format!("<mroot>{}{}</mroot>", radicand, index)
+                } else {
+                    format!("<msqrt>{}</msqrt>", self.parse_argument()?)
+                }
+            }
+            "text" | "textrm" | "textit" | "textbf" | "mbox" => {
+                format!("<mtext>{}</mtext>", HtmlSanitizer::escape(&self.parse_text_argument()?))
+            }
+            "operatorname" | "mathrm" => format!(
+                "<mi mathvariant=\"normal\">{}</mi>",
+                HtmlSanitizer::escape(&self.parse_text_argument()?)
+            ),
+            "mathbf" | "boldsymbol" | "bm" => {
+                format!("<mrow style=\"font-weight: bold\">{}</mrow>", self.parse_argument()?)
+            }
+            "mathbb" => {
+                let letters: String = self
+                    .parse_text_argument()?
+                    .chars()
+                    .map(|c| {
+                        DOUBLE_STRUCK
+                            .iter()
+                            .find(|(letter, _)| *letter == c)
+                            .map(|(_, double_struck)| *double_struck)
+                            .unwrap_or(c)
+                    })
+                    .collect();
+                format!("<mi mathvariant=\"normal\">{}</mi>", HtmlSanitizer::escape(&letters))
+            }
+            "hat" | "bar" | "overline" | "vec" | "dot" | "tilde" => {
+                let accent = match name.as_str() {
+                    "hat" => '^',
+                    "bar" | "overline" => '¯',
+                    "vec" => '→',
+                    "dot" => '˙',
+                    _ => '~',
+                };
+                format!(
+                    "<mover accent=\"true\">{}<mo>{}</mo></mover>",
+                    self.parse_argument()?,
+                    accent
+                )
+            }
+            // sizes of delimiters are left to the renderer
+            "left" | "right" | "big" | "Big" | "bigg" | "Bigg" => {
+                self.skip_whitespace();
+                if self.peek() == Some('.') {
+                    self.pos += 1;
+                    String::new()
+                } else {
+                    self.parse_atom()?
+                }
+            }
+            "quad" => String::from("<mspace width=\"1em\"></mspace>"),
+            "qquad" => String::from("<mspace width=\"2em\"></mspace>"),
+            "displaystyle" | "limits" | "nolimits" => String::new(),
+            _ => format!("<mtext>\\{}</mtext>", name),
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map(char::is_whitespace).unwrap_or(false) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Math;
+
+    #[test]
+    fn render_formulas() {
+        assert_eq!(
+            Math::render(r"<p>Energy \(E = mc^2\) and</p>"),
+            "<p>Energy <math><mrow><mi>E</mi><mo>=</mo><mi>m</mi><msup><mi>c</mi><mrow><mn>2</mn></mrow></msup></mrow></math> and</p>"
+        );
+        assert_eq!(
+            Math::render(r"$$\frac{1}{\sqrt{x}} \leq \alpha_i$$"),
+            "<math display=\"block\"><mrow><mfrac><mrow><mn>1</mn></mrow><mrow><msqrt><mrow><mi>x</mi></mrow></msqrt></mrow></mfrac>\
+             <mo>≤</mo><msub><mi>α</mi><mi>i</mi></msub></mrow></math>"
+        );
+    }
+
+    #[test]
+    fn keep_code_and_broken_formulas() {
+        let html = r"<pre><code>echo $$ \(x\)</code></pre><p>costs $5 or $10, \(\frac{1}{\)</p>";
+        assert_eq!(Math::render(html), html);
+
+        let html = format!(r"\({}x{}\)", "{".repeat(100), "}".repeat(100));
+        assert_eq!(Math::render(&html), html);
+    }
+}
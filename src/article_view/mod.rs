@@ -1,43 +1,93 @@
+mod article_tab;
+mod code_highlighter;
+mod data_saver;
+mod enclosures;
 mod error;
+mod find_bar;
+mod image_viewer;
+mod math;
 mod models;
 mod progress_overlay;
+mod read_aloud;
+mod sanitizer;
+mod search_overlay;
 mod url_overlay;
+mod user_themes;
+mod web_browser;
 
+use self::article_tab::ArticleTab;
+use self::code_highlighter::CodeHighlighter;
+use self::data_saver::DataSaver;
+use self::enclosures::{Enclosures, DOWNLOAD_PREFIX, OFFLINE_SCHEME, REMOVE_DOWNLOAD_PREFIX};
 use self::error::{ArticleViewError, ArticleViewErrorKind};
+use self::find_bar::FindBar;
+use self::image_viewer::ImageViewer;
+use self::math::Math;
 pub use self::models::ArticleTheme;
 use self::models::InternalState;
 use self::progress_overlay::ProgressOverlay;
+use self::read_aloud::ReadAloud;
+use self::sanitizer::HtmlSanitizer;
+use self::search_overlay::SearchOverlay;
 use self::url_overlay::UrlOverlay;
+pub use self::user_themes::UserThemes;
+use self::web_browser::{WebBrowser, BROWSER_PAGE};
 use crate::app::Action;
+use crate::i18n::i18n;
 use crate::main_window_state::MainWindowState;
+use crate::search_query::SearchQuery;
 use crate::settings::Settings;
 use crate::util::{BuilderHelper, DateUtil, FileUtil, GtkUtil, Util, GTK_RESOURCE_FILE_ERROR};
 use crate::Resources;
 use gdk::{
     keys::constants::KP_Add as KP_ADD, keys::constants::KP_Subtract as KP_SUBTRACT, keys::constants::KP_0, Cursor,
-    CursorType, Display, EventMask, ModifierType, ScrollDirection, RGBA,
+    CursorType, Display, EventMask, EventType, ModifierType, ScrollDirection, RGBA,
 };
-use gio::{Cancellable, Settings as GSettings, SettingsExt as GSettingsExt};
+use gio::{Cancellable, Settings as GSettings, SettingsExt as GSettingsExt, SimpleAction};
 use glib::{clone, object::Cast, source::Continue, translate::ToGlib, MainLoop, Sender};
 use gtk::{
-    prelude::WidgetExtManual, Button, ButtonExt, Inhibit, Overlay, OverlayExt, SettingsExt as GtkSettingsExt, Stack,
-    StackExt, TickCallbackId, WidgetExt,
+    prelude::WidgetExtManual, Button, ButtonExt, ContainerExt, GtkWindowExt, HeaderBar, HeaderBarExt, Inhibit, Label,
+    Notebook, NotebookExt, Overlay, OverlayExt, SettingsExt as GtkSettingsExt, Stack, StackExt, TickCallbackId,
+    WidgetExt, Window, WindowType,
 };
 use log::{error, warn};
-use news_flash::models::{FatArticle, Marked, Read};
+use news_flash::models::{ArticleID, Enclosure, FatArticle, Marked, Read};
 use pango::FontDescription;
 use parking_lot::RwLock;
+use serde::Deserialize;
+use std::fs;
 use std::str;
 use std::sync::Arc;
 use url::{Host, Origin};
 use webkit2gtk::{
-    ContextMenuAction, ContextMenuExt, ContextMenuItemExt, HitTestResultExt, NavigationPolicyDecision,
-    NavigationPolicyDecisionExt, PolicyDecisionExt, PolicyDecisionType, Settings as WebkitSettings, SettingsExt,
-    URIRequestExt, WebContext, WebView, WebViewExt,
+    ContextMenuAction, ContextMenuExt, ContextMenuItem, ContextMenuItemExt, DownloadExt, HitTestResultExt, LoadEvent,
+    NavigationPolicyDecision, NavigationPolicyDecisionExt, PolicyDecisionExt, PolicyDecisionType,
+    Settings as WebkitSettings, SettingsExt, URIRequestExt, WebContext, WebContextExt, WebView, WebViewExt,
 };
 
+const PRIMARY_MOUSE_BUTTON: u32 = 1;
 const MIDDLE_MOUSE_BUTTON: u32 = 2;
 const SCROLL_TRANSITION_DURATION: i64 = 500 * 1000;
+// an article counts as finished once the viewport is this close to its end
+const READING_POSITION_END_TOLERANCE: f64 = 16.0;
+// all images of the article in document order, as a json array of urls
+const IMAGE_SOURCES_SCRIPT: &str = "JSON.stringify(Array.prototype.map.call(\
+    document.querySelectorAll('div.nfcontent img'), \
+    function (image) { return image.currentSrc || image.src; }))";
+
+/// What `enclosures.js` reports about the players of an article.
+#[derive(Deserialize)]
+struct PlaybackState {
+    rate: f64,
+    positions: Vec<(String, f64)>,
+}
+
+/// The text `highlights.js` found selected in the article.
+#[derive(Deserialize)]
+struct HighlightSelection {
+    text: String,
+    prefix: String,
+}
 
 #[derive(Clone)]
 struct ScrollAnimationProperties {
@@ -53,10 +103,14 @@ pub struct ArticleView {
     settings: Arc<RwLock<Settings>>,
     sender: Sender<Action>,
     stack: Stack,
+    notebook: Notebook,
+    tabs: Arc<RwLock<Vec<ArticleTab>>>,
     top_overlay: Overlay,
     view_html_button: Button,
     visible_article: Arc<RwLock<Option<FatArticle>>>,
     visible_feed_name: Arc<RwLock<Option<String>>>,
+    // set for tabs and windows, which choose the scraped content on their own
+    prefer_scraped_content: Arc<RwLock<Option<bool>>>,
     widnow_state: Arc<RwLock<MainWindowState>>,
     internal_state: Arc<RwLock<InternalState>>,
     load_changed_signal: Arc<RwLock<Option<usize>>>,
@@ -72,8 +126,21 @@ pub struct ArticleView {
     drag_released_motion_signal: Arc<RwLock<Option<u32>>>,
     drag_buffer_update_signal: Arc<RwLock<Option<u32>>>,
     progress_overlay_delay_signal: Arc<RwLock<Option<u32>>>,
+    pending_scroll_pos: Arc<RwLock<Option<f64>>>,
+    search_script: Arc<RwLock<Option<String>>>,
+    enclosure_script: Arc<RwLock<Option<String>>>,
+    highlight_script: Arc<RwLock<Option<String>>>,
+    visible_enclosures: Arc<RwLock<Vec<Enclosure>>>,
+    images_blocked: Arc<RwLock<bool>>,
+    load_images: Arc<RwLock<bool>>,
     url_overlay_label: Arc<RwLock<UrlOverlay>>,
     progress_overlay_label: Arc<RwLock<ProgressOverlay>>,
+    search_overlay: Arc<RwLock<SearchOverlay>>,
+    find_bar: FindBar,
+    image_viewer: ImageViewer,
+    read_aloud: ReadAloud,
+    browser: WebBrowser,
+    hovered_image: Arc<RwLock<Option<String>>>,
     drag_buffer: Arc<RwLock<[f64; 10]>>,
     drag_ongoing: Arc<RwLock<bool>>,
     drag_y_pos: Arc<RwLock<f64>>,
@@ -88,12 +155,49 @@ impl ArticleView {
         settings: &Arc<RwLock<Settings>>,
         sender: &Sender<Action>,
         state: &Arc<RwLock<MainWindowState>>,
+    ) -> Self {
+        let web_context = WebContext::new();
+        // FIXME: apply appliction wide proxy settings
+        web_context.register_uri_scheme(OFFLINE_SCHEME, Enclosures::serve_offline);
+
+        let article_view = Self::build(settings, sender, state, web_context);
+
+        // the main view is the first page, articles opened in extra tabs follow
+        article_view.notebook.set_show_tabs(false);
+        article_view.notebook.set_show_border(false);
+        article_view.notebook.set_scrollable(true);
+        article_view
+            .notebook
+            .append_page(&article_view.top_overlay, Some(&Label::new(Some(&i18n("Article")))));
+        article_view.notebook.show_all();
+        article_view
+    }
+
+    /// Another view for articles opened in tabs or windows, sharing the web context of this one.
+    fn new_detached(&self) -> Self {
+        let article_view = Self::build(
+            &self.settings,
+            &self.sender,
+            &self.widnow_state,
+            self.web_context.clone(),
+        );
+        article_view.top_overlay.show_all();
+        article_view
+    }
+
+    fn build(
+        settings: &Arc<RwLock<Settings>>,
+        sender: &Sender<Action>,
+        state: &Arc<RwLock<MainWindowState>>,
+        web_context: WebContext,
     ) -> Self {
         let builder = BuilderHelper::new("article_view");
 
         let url_overlay = builder.get::<Overlay>("url_overlay");
         let url_overlay_label = UrlOverlay::new();
         url_overlay.add_overlay(&url_overlay_label.widget());
+        let search_overlay = Arc::new(RwLock::new(SearchOverlay::new()));
+        url_overlay.add_overlay(&search_overlay.read().widget());
 
         let progress_overlay = builder.get::<Overlay>("progress_overlay");
         let progress_overlay_label = ProgressOverlay::new();
@@ -117,9 +221,6 @@ impl ArticleView {
             }),
         );
 
-        let web_context = WebContext::new();
-        // FIXME: apply appliction wide proxy settings
-
         let stack = builder.get::<Stack>("article_view_stack");
         stack.set_visible_child_name("empty");
         let view_1 = Self::new_webview(&web_context);
@@ -129,18 +230,43 @@ impl ArticleView {
         stack.add_named(&view_1, InternalState::View1.to_str().expect("InternalState to str"));
         stack.add_named(&view_2, InternalState::View2.to_str().expect("InternalState to str"));
 
-        let internal_state = InternalState::Empty;
+        let internal_state = Arc::new(RwLock::new(InternalState::Empty));
         let settings = settings.clone();
 
+        let find_bar = FindBar::new(&stack, &internal_state);
+        url_overlay.add_overlay(&find_bar.widget());
+        let image_viewer = ImageViewer::new(&stack, &web_context, sender);
+        url_overlay.add_overlay(&image_viewer.widget());
+        let browser = WebBrowser::new(&stack, &internal_state, settings, sender);
+        stack.add_named(&browser.widget(), BROWSER_PAGE);
+
+        search_overlay.read().prev_button.connect_clicked(clone!(
+            @weak stack,
+            @weak internal_state,
+            @weak search_overlay => @default-panic, move |_button|
+        {
+            Self::select_search_hit(&stack, &internal_state, &search_overlay, "NewsFlashSearch.prev()");
+        }));
+        search_overlay.read().next_button.connect_clicked(clone!(
+            @weak stack,
+            @weak internal_state,
+            @weak search_overlay => @default-panic, move |_button|
+        {
+            Self::select_search_hit(&stack, &internal_state, &search_overlay, "NewsFlashSearch.next()");
+        }));
+
         let article_view = ArticleView {
             settings,
             sender: sender.clone(),
             stack,
+            notebook: Notebook::new(),
+            tabs: Arc::new(RwLock::new(Vec::new())),
             top_overlay: progress_overlay,
             view_html_button,
             visible_article,
             visible_feed_name,
-            internal_state: Arc::new(RwLock::new(internal_state)),
+            prefer_scraped_content: Arc::new(RwLock::new(None)),
+            internal_state,
             widnow_state: state.clone(),
             load_changed_signal: Arc::new(RwLock::new(None)),
             decide_policy_signal: Arc::new(RwLock::new(None)),
@@ -155,8 +281,21 @@ impl ArticleView {
             drag_released_motion_signal: Arc::new(RwLock::new(None)),
             drag_buffer_update_signal: Arc::new(RwLock::new(None)),
             progress_overlay_delay_signal: Arc::new(RwLock::new(None)),
+            pending_scroll_pos: Arc::new(RwLock::new(None)),
+            search_script: Arc::new(RwLock::new(None)),
+            enclosure_script: Arc::new(RwLock::new(None)),
+            highlight_script: Arc::new(RwLock::new(None)),
+            visible_enclosures: Arc::new(RwLock::new(Vec::new())),
+            images_blocked: Arc::new(RwLock::new(false)),
+            load_images: Arc::new(RwLock::new(false)),
             url_overlay_label: Arc::new(RwLock::new(url_overlay_label)),
             progress_overlay_label: Arc::new(RwLock::new(progress_overlay_label)),
+            search_overlay,
+            find_bar,
+            image_viewer,
+            read_aloud: ReadAloud::new(sender),
+            browser,
+            hovered_image: Arc::new(RwLock::new(None)),
             drag_buffer: Arc::new(RwLock::new([0.0; 10])),
             drag_ongoing: Arc::new(RwLock::new(false)),
             drag_y_pos: Arc::new(RwLock::new(0.0)),
@@ -172,28 +311,216 @@ impl ArticleView {
             web_context,
         };
 
+        // images may be loaded or blocked now, depending on the data saver settings
+        DataSaver::connect_metered_changed(clone!(
+            @strong article_view.settings as settings,
+            @strong article_view.visible_article as visible_article,
+            @strong article_view.images_blocked as images_blocked,
+            @strong article_view.load_images as load_images,
+            @strong article_view.sender as sender => move ||
+        {
+            if *load_images.read() {
+                return;
+            }
+            if let Some(article) = &*visible_article.read() {
+                if DataSaver::blocks_images(&settings.read(), &article.feed_id) != *images_blocked.read() {
+                    Util::send(&sender, Action::RedrawArticle);
+                }
+            }
+        }));
+
         article_view.stack.show_all();
         article_view
     }
 
-    pub fn widget(&self) -> gtk::Overlay {
-        self.top_overlay.clone()
+    pub fn widget(&self) -> gtk::Notebook {
+        self.notebook.clone()
+    }
+
+    /// Browse `uri` in place of the article until "Back to Article" is clicked.
+    pub fn open_link(&self, uri: &str) {
+        if self.visible_article.read().is_none() {
+            return;
+        }
+        self.find_bar.hide();
+        self.browser.open(uri);
+        self.stack.set_visible_child_name(BROWSER_PAGE);
+    }
+
+    /// Whether the visible article's feed always shows the full content of its articles.
+    pub fn feed_full_content(&self) -> bool {
+        match &*self.visible_article.read() {
+            Some(article) => self
+                .settings
+                .read()
+                .get_article_view_feed_full_content(&article.feed_id),
+            None => false,
+        }
+    }
+
+    /// Where links of the visible article's feed open, `None` if the feed follows the general setting.
+    pub fn feed_internal_links(&self) -> Option<bool> {
+        let feed_id = &self.visible_article.read().as_ref()?.feed_id;
+        self.settings.read().get_article_view_feed_internal_links(feed_id)
+    }
+
+    /// Show `article` in a tab next to the main view where it stays while other articles are read.
+    pub fn open_tab(&self, article: FatArticle, feed_name: String, enclosures: Vec<Enclosure>) {
+        let tab = self.new_tab(article, feed_name, enclosures);
+        let (tab_label, close_button) = tab.tab_label();
+        let page = tab.widget();
+        self.notebook.append_page(&page, Some(&tab_label));
+        self.notebook.set_tab_reorderable(&page, true);
+        self.notebook.set_show_tabs(true);
+        self.notebook.set_current_page(self.notebook.page_num(&page));
+
+        close_button.connect_clicked(clone!(
+            @weak self.notebook as notebook,
+            @weak self.tabs as tabs,
+            @weak page => @default-panic, move |_button|
+        {
+            Self::close_tab(&tabs, &page);
+            notebook.remove_page(notebook.page_num(&page));
+            notebook.set_show_tabs(notebook.get_n_pages() > 1);
+        }));
+    }
+
+    /// Show `article` in a window of its own.
+    pub fn open_window(&self, article: FatArticle, feed_name: String, enclosures: Vec<Enclosure>) {
+        let tab = self.new_tab(article, feed_name, enclosures);
+
+        let header = HeaderBar::new();
+        header.set_show_close_button(true);
+        header.set_title(Some(tab.title()));
+        header.set_subtitle(Some(tab.feed_name()));
+
+        let window = Window::new(WindowType::Toplevel);
+        window.set_titlebar(Some(&header));
+        window.set_default_size(800, 700);
+        // the application keeps running as long as one of its windows is open
+        if let Ok(main_window) = self.notebook.get_toplevel().downcast::<Window>() {
+            window.set_application(main_window.get_application().as_ref());
+        }
+        let page = tab.widget();
+        window.add(&page);
+        tab.connect_shortcuts(&window);
+        window.connect_destroy(
+            clone!(@weak self.tabs as tabs, @weak page => @default-panic, move |_window| {
+                Self::close_tab(&tabs, &page);
+            }),
+        );
+        window.show_all();
+    }
+
+    fn new_tab(&self, article: FatArticle, feed_name: String, enclosures: Vec<Enclosure>) -> ArticleTab {
+        let view = self.new_detached();
+        let webview = self
+            .stack
+            .get_child_by_name(InternalState::View1.to_str().expect("InternalState to str"))
+            .and_then(|webview| webview.downcast::<WebView>().ok());
+        if let Some(webview) = webview {
+            view.update_background_color(&webview.get_background_color());
+        }
+        let tab = ArticleTab::new(view, article, feed_name, enclosures, &self.sender);
+        self.tabs.write().push(tab.clone());
+        tab
+    }
+
+    fn close_tab(tabs: &Arc<RwLock<Vec<ArticleTab>>>, page: &gtk::Box) {
+        let mut tabs = tabs.write();
+        if let Some(index) = tabs.iter().position(|tab| &tab.widget() == page) {
+            tabs.remove(index).close();
+        }
+    }
+
+    /// The view the reader is using: a focused article window, the current tab or this view.
+    pub fn active(&self) -> ArticleView {
+        let tabs = self.tabs.read();
+        if let Some(tab) = tabs.iter().find(|tab| tab.is_active_window()) {
+            return tab.view().clone();
+        }
+        let current_page = self
+            .notebook
+            .get_current_page()
+            .filter(|page| *page > 0)
+            .and_then(|page| self.notebook.get_nth_page(Some(page)));
+        if let Some(current_page) = current_page {
+            if let Some(tab) = tabs
+                .iter()
+                .find(|tab| tab.widget().upcast::<gtk::Widget>() == current_page)
+            {
+                return tab.view().clone();
+            }
+        }
+        self.clone()
+    }
+
+    /// An article shown in this view or one of its tabs and windows.
+    pub fn find_article(&self, article_id: &ArticleID) -> Option<FatArticle> {
+        std::iter::once(self.clone())
+            .chain(self.tabs.read().iter().map(|tab| tab.view().clone()))
+            .filter_map(|view| view.get_visible_article())
+            .find(|article| &article.article_id == article_id)
+    }
+
+    /// Show the freshly scraped content in the tabs and windows that show `article`.
+    pub fn update_scraped_article(&self, article: &FatArticle) {
+        for tab in self.tabs.read().iter() {
+            tab.update_article(article);
+        }
+    }
+
+    /// Choose between the scraped and the original content, independent of the main view.
+    fn set_prefer_scraped_content(&self, prefer: bool) {
+        self.prefer_scraped_content.write().replace(prefer);
+        self.redraw();
     }
 
-    pub fn show_article(&self, article: FatArticle, feed_name: String) {
+    pub fn show_article(&self, article: FatArticle, feed_name: String, enclosures: Vec<Enclosure>) {
+        self.save_view_state();
+        self.notebook.set_current_page(Some(0));
+        self.read_aloud.article_shown(&article.article_id);
+        *self.visible_enclosures.write() = enclosures;
+        self.search_overlay.write().set_count(0);
+        self.find_bar.hide();
+        self.image_viewer.reset();
+        self.hovered_image.write().take();
+        *self.pending_scroll_pos.write() = self
+            .settings
+            .read()
+            .get_article_view_reading_position(&article.article_id);
+        *self.load_images.write() = false;
         let webview = self.switch_view().expect("Failed to switch webview");
+        webview.set_zoom_level(self.settings.read().get_article_view_feed_zoom(&article.feed_id));
         let html = self.build_article(&article, &feed_name);
         webview.load_html(&html, Self::get_base_url(&article).as_deref());
         self.visible_article.write().replace(article);
         self.visible_feed_name.write().replace(feed_name);
     }
 
+    /// Render the article again, also in all tabs and windows.
     pub fn redraw_article(&self) {
+        self.redraw();
+        for tab in self.tabs.read().iter() {
+            tab.view().redraw();
+        }
+    }
+
+    fn redraw(&self) {
         if let Some(article) = &*self.visible_article.read() {
             if let Some(feed_name) = &*self.visible_feed_name.read() {
+                self.save_playback_state();
                 let html = self.build_article(&article, feed_name);
 
+                // keep the reader's place when the article is rendered again
+                if self.pending_scroll_pos.read().is_none() {
+                    *self.pending_scroll_pos.write() = self.get_scroll_abs().ok().filter(|pos| *pos > 0.0);
+                }
+                let zoom = self.get_zoom_level();
                 let webview = self.switch_view().expect("Failed to switch webview");
+                if let Some(zoom) = zoom {
+                    webview.set_zoom_level(zoom);
+                }
                 webview.load_html(&html, Self::get_base_url(&article).as_deref());
                 return;
             }
@@ -236,13 +563,318 @@ impl ArticleView {
     }
 
     pub fn close_article(&self) {
+        self.save_view_state();
+        self.pending_scroll_pos.write().take();
         self.disconnect_old_view();
+        self.search_overlay.write().set_count(0);
+        self.find_bar.hide();
+        self.image_viewer.reset();
+        self.read_aloud.cancel_continue();
+        self.read_aloud.stop();
+        self.hovered_image.write().take();
         self.visible_article.write().take();
         self.visible_feed_name.write().take();
+        self.visible_enclosures.write().clear();
+        self.browser.close();
         *self.internal_state.write() = InternalState::Empty;
         self.stack.set_visible_child_name("empty");
     }
 
+    /// Remember the zoom level of the visible article's feed and how far the article was read.
+    pub fn save_view_state(&self) {
+        let (article_id, feed_id) = match &*self.visible_article.read() {
+            Some(article) => (article.article_id.clone(), article.feed_id.clone()),
+            None => return,
+        };
+        let webview = match self.visible_webview() {
+            Some(webview) => webview,
+            None => return,
+        };
+
+        if self
+            .settings
+            .write()
+            .set_article_view_feed_zoom(&feed_id, webview.get_zoom_level())
+            .is_err()
+        {
+            warn!("Failed to save zoom level of feed '{}'", feed_id);
+        }
+        self.save_playback_state();
+
+        // the saved position wasn't restored yet, nothing new to remember
+        if self.pending_scroll_pos.read().is_some() {
+            return;
+        }
+
+        let position = match Self::get_reading_progress_static(&webview) {
+            Ok((pos, window_height, upper)) => {
+                if pos <= 0.0 || pos + window_height >= upper - READING_POSITION_END_TOLERANCE {
+                    None
+                } else {
+                    Some(pos)
+                }
+            }
+            Err(_) => return,
+        };
+        self.settings
+            .write()
+            .set_article_view_reading_position(&article_id, position);
+    }
+
+    /// Remember the playback speed and how far the enclosures of the visible article were played.
+    fn save_playback_state(&self) {
+        if self.visible_enclosures.read().is_empty() {
+            return;
+        }
+        let webview = match self.visible_webview() {
+            Some(webview) => webview,
+            None => return,
+        };
+        let state = Self::webview_js_get_string(
+            &webview,
+            "typeof NewsFlashEnclosures === 'undefined' ? '' : NewsFlashEnclosures.state()",
+        )
+        .ok()
+        .and_then(|json| serde_json::from_str::<PlaybackState>(&json).ok());
+        if let Some(state) = state {
+            let positions: Vec<(String, Option<f64>)> = state
+                .positions
+                .into_iter()
+                .map(|(url, position)| (url, Some(position).filter(|position| *position > 0.0)))
+                .collect();
+            if self
+                .settings
+                .write()
+                .set_article_view_playback_state(state.rate, &positions)
+                .is_err()
+            {
+                warn!("Failed to save playback state");
+            }
+        }
+    }
+
+    /// Download an enclosure so it can be played offline, the article is redrawn once it is done.
+    pub fn download_enclosure(&self, url: &str) {
+        if fs::create_dir_all(Enclosures::offline_dir()).is_err() {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage("Failed to create directory for downloads.".to_owned()),
+            );
+            return;
+        }
+        // download next to the final file so half finished downloads are never played
+        let path = Enclosures::offline_path(url);
+        let partial_path = path.with_file_name(format!(
+            "{}.part",
+            path.file_name().and_then(|name| name.to_str()).unwrap_or_default()
+        ));
+        let destination = match glib::filename_to_uri(&partial_path, None) {
+            Ok(destination) => destination,
+            Err(_) => return,
+        };
+        let download = match self.web_context.download_uri(url) {
+            Some(download) => download,
+            None => return,
+        };
+        download.set_allow_overwrite(true);
+        download.set_destination(&destination);
+
+        let failed = Arc::new(RwLock::new(false));
+        let url = url.to_owned();
+        download.connect_failed(clone!(
+            @strong failed,
+            @strong self.sender as sender,
+            @strong url => @default-panic, move |_download, error|
+        {
+            *failed.write() = true;
+            Util::send(
+                &sender,
+                Action::ErrorSimpleMessage(format!("Failed to download '{}': {}", url, error)),
+            );
+        }));
+        download.connect_finished(
+            clone!(@strong self.sender as sender => @default-panic, move |_download| {
+                // 'finished' is emitted after 'failed' as well
+                if *failed.read() {
+                    let _ = fs::remove_file(&partial_path);
+                    return;
+                }
+                if fs::rename(&partial_path, &path).is_err() {
+                    Util::send(
+                        &sender,
+                        Action::ErrorSimpleMessage(format!("Failed to save download of '{}'", url)),
+                    );
+                    return;
+                }
+                Util::send(&sender, Action::RedrawArticle);
+            }),
+        );
+    }
+
+    pub fn remove_enclosure_download(&self, url: &str) {
+        if fs::remove_file(Enclosures::offline_path(url)).is_err() {
+            Util::send(
+                &self.sender,
+                Action::ErrorSimpleMessage(format!("Failed to remove download of '{}'", url)),
+            );
+            return;
+        }
+        self.redraw_article();
+    }
+
+    fn visible_webview(&self) -> Option<WebView> {
+        let view_name = (*self.internal_state.read()).to_str().map(|s| s.to_owned())?;
+        self.stack
+            .get_child_by_name(&view_name)
+            .and_then(|view| view.downcast::<WebView>().ok())
+    }
+
+    fn get_zoom_level(&self) -> Option<f64> {
+        self.visible_webview().map(|webview| webview.get_zoom_level())
+    }
+
+    /// Whether the visible article shows placeholders instead of its images.
+    pub fn images_blocked(&self) -> bool {
+        *self.images_blocked.read()
+    }
+
+    /// Image override of the visible article's feed, see `DataSaver::blocks_images`.
+    pub fn feed_images(&self) -> Option<bool> {
+        let article = self.visible_article.read();
+        let feed_id = &article.as_ref()?.feed_id;
+        self.settings.read().get_article_view_feed_images(feed_id)
+    }
+
+    /// Load the images of the visible article, including after redraws.
+    pub fn load_images(&self) {
+        *self.load_images.write() = true;
+        *self.images_blocked.write() = false;
+        if let Some(webview) = self.visible_webview() {
+            let cancellable: Option<&Cancellable> = None;
+            webview.run_javascript(
+                "typeof NewsFlashImages === 'undefined' || NewsFlashImages.loadAll()",
+                cancellable,
+                |res| {
+                    if res.is_err() {
+                        error!("Loading images failed");
+                    }
+                },
+            );
+        }
+    }
+
+    pub fn show_find_bar(&self) {
+        if self.visible_article.read().is_some() {
+            self.find_bar.show();
+        }
+    }
+
+    pub fn find_next(&self) {
+        if self.visible_article.read().is_some() {
+            self.find_bar.find_next();
+        }
+    }
+
+    pub fn find_previous(&self) {
+        if self.visible_article.read().is_some() {
+            self.find_bar.find_previous();
+        }
+    }
+
+    pub fn hide_find_bar(&self) {
+        self.find_bar.hide();
+    }
+
+    pub fn is_find_focused(&self) -> bool {
+        self.find_bar.is_focused()
+    }
+
+    /// Whether the visible article has the keyboard focus.
+    pub fn is_focused(&self) -> bool {
+        self.visible_webview()
+            .map(|webview| webview.has_focus())
+            .unwrap_or(false)
+    }
+
+    pub fn is_image_viewer_visible(&self) -> bool {
+        self.image_viewer.is_visible()
+    }
+
+    /// Start, pause or resume reading the visible article aloud.
+    pub fn read_aloud_toggle(&self) {
+        let article_id = match &*self.visible_article.read() {
+            Some(article) => article.article_id.clone(),
+            None => return,
+        };
+        if let Some(webview) = self.visible_webview() {
+            self.read_aloud.toggle(&webview, &article_id);
+        }
+    }
+
+    pub fn read_aloud_skip(&self) {
+        self.read_aloud.skip();
+    }
+
+    /// Reading aloud goes on once `article_id` is shown.
+    pub fn read_aloud_continue_with(&self, article_id: ArticleID) {
+        self.read_aloud.continue_with(article_id);
+    }
+
+    /// Ask the article for the selected text, `with_note` asks for a note before the highlight is saved.
+    pub fn highlight_selection(&self, with_note: bool) {
+        let article_id = match &*self.visible_article.read() {
+            Some(article) => article.article_id.clone(),
+            None => return,
+        };
+        let webview = match self.visible_webview() {
+            Some(webview) => webview,
+            None => return,
+        };
+        let sender = self.sender.clone();
+        Self::webview_js_get_string_async(
+            &webview,
+            "typeof NewsFlashHighlights === 'undefined' ? 'null' : NewsFlashHighlights.selection()",
+            move |json| {
+                if let Ok(Some(selection)) = serde_json::from_str::<Option<HighlightSelection>>(&json) {
+                    let action = if with_note {
+                        Action::ShowHighlightNoteDialog(article_id.clone(), selection.text, selection.prefix)
+                    } else {
+                        Action::AddHighlight(article_id.clone(), selection.text, selection.prefix, None)
+                    };
+                    Util::send(&sender, action);
+                }
+            },
+        );
+    }
+
+    /// Mark the stored highlights of the visible articles again, e.g. after one was added or removed.
+    pub fn update_highlights(&self) {
+        self.mark_highlights();
+        for tab in self.tabs.read().iter() {
+            tab.view().mark_highlights();
+        }
+    }
+
+    fn mark_highlights(&self) {
+        let article_id = match &*self.visible_article.read() {
+            Some(article) => article.article_id.clone(),
+            None => return,
+        };
+        let highlights = self.settings.read().get_article_highlights(&article_id);
+        let highlights = serde_json::to_string(&highlights).unwrap_or_else(|_| "[]".to_owned());
+        if let Some(webview) = self.visible_webview() {
+            let cancellable: Option<&Cancellable> = None;
+            webview.run_javascript(
+                &format!(
+                    "typeof NewsFlashHighlights === 'undefined' || NewsFlashHighlights.set({})",
+                    highlights
+                ),
+                cancellable,
+                |_res| {},
+            );
+        }
+    }
+
     pub fn update_background_color(&self, color: &RGBA) {
         if (color.alpha - 1.0).abs() == std::f64::EPSILON {
             let webview_1 = self
@@ -260,10 +892,29 @@ impl ArticleView {
             webview_1.set_background_color(color);
             webview_2.set_background_color(color);
         }
+        for tab in self.tabs.read().iter() {
+            tab.view().update_background_color(color);
+        }
+    }
+
+    /// The action opening a link of the article, either in NewsFlash or in the default browser depending on its feed.
+    fn link_action(settings: &Arc<RwLock<Settings>>, article: &Arc<RwLock<Option<FatArticle>>>, uri: &str) -> Action {
+        let is_web = uri.starts_with("http://") || uri.starts_with("https://");
+        let internal = article
+            .read()
+            .as_ref()
+            .map(|article| WebBrowser::opens_links(&settings.read(), &article.feed_id))
+            .unwrap_or(false);
+        if is_web && internal {
+            Action::OpenLinkInternally(uri.to_owned())
+        } else {
+            Action::OpenUrlInDefaultBrowser(uri.to_owned())
+        }
     }
 
     fn switch_view(&self) -> Result<WebView, ArticleViewError> {
         self.disconnect_old_view();
+        self.browser.close();
         let old_state = (*self.internal_state.read()).clone();
         *self.internal_state.write() = old_state.switch();
         if let Some(new_name) = self.internal_state.read().to_str() {
@@ -369,6 +1020,7 @@ impl ArticleView {
         settings.set_enable_page_cache(false);
         settings.set_enable_plugins(false);
         settings.set_enable_smooth_scrolling(false);
+        // needed for our own scroll and search helpers, the CSP of the article template keeps page scripts out
         settings.set_enable_javascript(true);
         settings.set_javascript_can_access_clipboard(false);
         settings.set_javascript_can_open_windows_automatically(false);
@@ -386,10 +1038,73 @@ impl ArticleView {
     }
 
     fn connect_webview(&self, webview: &WebView) {
+        //----------------------------------
+        // count search hits
+        //----------------------------------
+        self.load_changed_signal.write().replace(
+            webview
+                .connect_load_changed(clone!(
+                    @weak self.search_overlay as search_overlay,
+                    @weak self.search_script as search_script,
+                    @weak self.enclosure_script as enclosure_script,
+                    @weak self.highlight_script as highlight_script,
+                    @weak self.visible_article as visible_article,
+                    @strong self.read_aloud as read_aloud,
+                    @weak self.images_blocked as images_blocked,
+                    @weak self.pending_scroll_pos as pending_scroll_pos => @default-panic, move |closure_webivew, event|
+                {
+                    if event == LoadEvent::Finished {
+                        if let Some(pos) = pending_scroll_pos.write().take() {
+                            Self::set_scroll_pos_static(closure_webivew, pos);
+                        }
+
+                        // the page itself may not run scripts, so our helpers are injected from here
+                        let mut java_script = String::new();
+                        if *images_blocked.read() {
+                            let js_data = Resources::get("article_view/image_placeholders.js")
+                                .expect(GTK_RESOURCE_FILE_ERROR);
+                            java_script.push_str(str::from_utf8(js_data.as_ref()).expect("Failed to load JS from resources"));
+                            java_script.push('\n');
+                        }
+                        if let Some(script) = &*enclosure_script.read() {
+                            java_script.push_str(script);
+                            java_script.push('\n');
+                        }
+                        if let Some(script) = &*highlight_script.read() {
+                            java_script.push_str(script);
+                            java_script.push('\n');
+                        }
+                        for helper in &["article_view/code_blocks.js", "article_view/read_aloud.js"] {
+                            let js_data = Resources::get(helper).expect(GTK_RESOURCE_FILE_ERROR);
+                            java_script.push_str(str::from_utf8(js_data.as_ref()).expect("Failed to load JS from resources"));
+                            java_script.push('\n');
+                        }
+                        match &*search_script.read() {
+                            Some(script) => java_script.push_str(&format!("{}\nNewsFlashSearch.count()", script)),
+                            None => java_script.push('0'),
+                        }
+                        let search_overlay = search_overlay.clone();
+                        let article_id = visible_article.read().as_ref().map(|article| article.article_id.clone());
+                        let read_aloud = read_aloud.clone();
+                        let webview = closure_webivew.clone();
+                        Self::webview_js_get_f64_async(closure_webivew, &java_script, move |count| {
+                            search_overlay.write().set_count(count as i64);
+                            // the helpers are only there once the script ran
+                            if let Some(article_id) = &article_id {
+                                read_aloud.article_loaded(&webview, article_id);
+                            }
+                        });
+                    }
+                }))
+                .to_glib() as usize,
+        );
+
         //----------------------------------
         // open link in external browser
         //----------------------------------
         let policy_sender = self.sender.clone();
+        let policy_settings = self.settings.clone();
+        let policy_article = self.visible_article.clone();
         self.decide_policy_signal.write().replace(
             webview
                 .connect_decide_policy(move |_closure_webivew, decision, decision_type| {
@@ -402,7 +1117,7 @@ impl ArticleView {
                                             if let Some(uri) = uri_req.get_uri() {
                                                 Util::send(
                                                     &policy_sender,
-                                                    Action::OpenUrlInDefaultBrowser(uri.as_str().into()),
+                                                    Self::link_action(&policy_settings, &policy_article, &uri),
                                                 );
                                             }
                                         }
@@ -419,10 +1134,18 @@ impl ArticleView {
                                     if let Some(uri) = request.get_uri() {
                                         if action.is_user_gesture() {
                                             decision.ignore();
-                                            Util::send(
-                                                &policy_sender,
-                                                Action::OpenUrlInDefaultBrowser(uri.as_str().into()),
-                                            );
+                                            let action = if uri.starts_with(DOWNLOAD_PREFIX) {
+                                                Action::DownloadEnclosure(
+                                                    uri.as_str()[DOWNLOAD_PREFIX.len()..].to_owned(),
+                                                )
+                                            } else if uri.starts_with(REMOVE_DOWNLOAD_PREFIX) {
+                                                Action::RemoveEnclosureDownload(
+                                                    uri.as_str()[REMOVE_DOWNLOAD_PREFIX.len()..].to_owned(),
+                                                )
+                                            } else {
+                                                Self::link_action(&policy_settings, &policy_article, &uri)
+                                            };
+                                            Util::send(&policy_sender, action);
                                         }
                                     }
                                 }
@@ -441,9 +1164,17 @@ impl ArticleView {
             webview
                 .connect_mouse_target_changed(clone!(
                     @weak self.url_overlay_label as url_overlay_label,
+                    @weak self.hovered_image as hovered_image,
                     @weak self.pointer_pos as pointer_pos,
                     @weak self.stack as stack => @default-panic, move |_closure_webivew, hit_test, _modifiers|
                 {
+                    // images that are links keep following their link
+                    *hovered_image.write() = if hit_test.context_is_image() && !hit_test.context_is_link() {
+                        hit_test.get_image_uri().map(|uri| uri.as_str().to_owned())
+                    } else {
+                        None
+                    };
+
                     if hit_test.context_is_link() {
                         if let Some(uri) = hit_test.get_link_uri() {
                             let allocation = stack.get_allocation();
@@ -516,9 +1247,10 @@ impl ArticleView {
         //----------------------------------
         // clean up context menu
         //----------------------------------
+        let ctx_menu_sender = self.sender.clone();
         self.ctx_menu_signal.write().replace(
             webview
-                .connect_context_menu(|_closure_webivew, ctx_menu, _event, _hit_test| {
+                .connect_context_menu(move |_closure_webivew, ctx_menu, _event, hit_test| {
                     let menu_items = ctx_menu.get_items();
 
                     for item in menu_items {
@@ -537,6 +1269,20 @@ impl ArticleView {
                         }
                     }
 
+                    if hit_test.context_is_selection() {
+                        for (label, with_note) in &[(i18n("Highlight"), false), (i18n("Highlight with Note…"), true)]
+                        {
+                            let with_note = *with_note;
+                            let highlight_action = SimpleAction::new("highlight", None);
+                            highlight_action.connect_activate(
+                                clone!(@strong ctx_menu_sender => @default-panic, move |_action, _parameter| {
+                                    Util::send(&ctx_menu_sender, Action::HighlightSelection(with_note));
+                                }),
+                            );
+                            ctx_menu.append(&ContextMenuItem::from_gaction(&highlight_action, label, None));
+                        }
+                    }
+
                     if ctx_menu.first().is_none() {
                         return true;
                     }
@@ -592,8 +1338,25 @@ impl ArticleView {
                     @weak self.drag_motion_notify_signal as drag_motion_notify_signal,
                     @weak self.drag_buffer_update_signal as drag_buffer_update_signal,
                     @weak self.scroll_animation_data as scroll_animation_data,
-                    @weak self.drag_buffer as drag_buffer => @default-panic, move |closure_webview, event|
+                    @weak self.drag_buffer as drag_buffer,
+                    @weak self.hovered_image as hovered_image,
+                    @strong self.image_viewer as image_viewer => @default-panic, move |closure_webview, event|
                 {
+                    // open images in the viewer
+                    if event.get_button() == PRIMARY_MOUSE_BUTTON && event.get_event_type() == EventType::ButtonPress {
+                        if let Some(uri) = hovered_image.read().clone() {
+                            Self::webview_js_get_string_async(
+                                closure_webview,
+                                IMAGE_SOURCES_SCRIPT,
+                                clone!(@strong image_viewer => @default-panic, move |json| {
+                                    let images: Vec<String> = serde_json::from_str(&json).unwrap_or_default();
+                                    image_viewer.show(images, &uri);
+                                }),
+                            );
+                            return Inhibit(true);
+                        }
+                    }
+
                     if event.get_button() == MIDDLE_MOUSE_BUTTON {
                         Self::stop_scroll_animation(&closure_webview, &scroll_animation_data);
                         let (_, y) = event.get_position();
@@ -782,15 +1545,88 @@ impl ArticleView {
     }
 
     fn build_article(&self, article: &FatArticle, feed_name: &str) -> String {
-        Self::build_article_static(
+        self.search_script.write().take();
+        let mut html = Self::build_article_static(
             "article",
             article,
             feed_name,
             &self.settings,
             None,
             None,
-            self.widnow_state.read().get_prefer_scraped_content(),
-        )
+            self.prefer_scraped_content
+                .read()
+                .unwrap_or_else(|| self.widnow_state.read().get_prefer_scraped_content()),
+        );
+
+        // enclosures are listed right below the article header
+        self.enclosure_script.write().take();
+        if !self.visible_enclosures.read().is_empty() {
+            let enclosures = Enclosures::html(&self.visible_enclosures.read(), &self.settings.read());
+            html = html.replacen("</header>", &format!("</header>\n{}", enclosures), 1);
+
+            let js_data = Resources::get("article_view/enclosures.js").expect(GTK_RESOURCE_FILE_ERROR);
+            let js_string = str::from_utf8(js_data.as_ref()).expect("Failed to load JS from resources");
+            let rate = self.settings.read().get_article_view_playback_rate();
+            self.enclosure_script
+                .write()
+                .replace(js_string.replacen("$RATE", &rate.to_string(), 1));
+        }
+
+        let images_blocked =
+            !*self.load_images.read() && DataSaver::blocks_images(&self.settings.read(), &article.feed_id);
+        *self.images_blocked.write() = images_blocked;
+        if images_blocked {
+            html = DataSaver::replace_images(&html, &i18n("Load Image"));
+        }
+
+        // saved highlights are marked once the page is loaded
+        let js_data = Resources::get("article_view/highlights.js").expect(GTK_RESOURCE_FILE_ERROR);
+        let js_string = str::from_utf8(js_data.as_ref()).expect("Failed to load JS from resources");
+        let highlights = self.settings.read().get_article_highlights(&article.article_id);
+        let highlights = serde_json::to_string(&highlights).unwrap_or_else(|_| "[]".to_owned());
+        self.highlight_script
+            .write()
+            .replace(js_string.replacen("$HIGHLIGHTS", &highlights, 1));
+
+        let search_terms = self
+            .widnow_state
+            .read()
+            .get_search_term()
+            .as_ref()
+            .and_then(|search_term| SearchQuery::parse(search_term).ok())
+            .map(|query| query.highlight_terms())
+            .unwrap_or_default();
+        if search_terms.is_empty() {
+            return html;
+        }
+
+        // highlight the hits of the active search and allow jumping between them
+        let js_data = Resources::get("article_view/search_hits.js").expect(GTK_RESOURCE_FILE_ERROR);
+        let js_string = str::from_utf8(js_data.as_ref()).expect("Failed to load JS from resources");
+        let terms = serde_json::to_string(&search_terms).unwrap_or_else(|_| "[]".to_owned());
+        self.search_script
+            .write()
+            .replace(js_string.replacen("$TERMS", &terms, 1));
+        html
+    }
+
+    fn select_search_hit(
+        stack: &Stack,
+        internal_state: &Arc<RwLock<InternalState>>,
+        search_overlay: &Arc<RwLock<SearchOverlay>>,
+        java_script: &str,
+    ) {
+        let view = internal_state
+            .read()
+            .to_str()
+            .and_then(|view_name| stack.get_child_by_name(view_name))
+            .and_then(|view| view.downcast::<WebView>().ok());
+        if let Some(view) = view {
+            let search_overlay = search_overlay.clone();
+            Self::webview_js_get_f64_async(&view, java_script, move |position| {
+                search_overlay.read().set_position(position as i64)
+            });
+        }
     }
 
     pub fn build_article_static(
@@ -801,6 +1637,51 @@ impl ArticleView {
         theme_override: Option<ArticleTheme>,
         font_size_override: Option<i32>,
         prefer_scraped_content: bool,
+    ) -> String {
+        let theme = theme_override.unwrap_or_else(|| settings.read().get_article_view_theme());
+        let theme_css = UserThemes::theme_css(&theme);
+        let user_css = UserThemes::stylesheet_css(settings);
+        Self::build_article_with_css(
+            file_name,
+            article,
+            feed_name,
+            settings,
+            &theme,
+            theme_css.as_deref(),
+            user_css.as_deref(),
+            font_size_override,
+            prefer_scraped_content,
+        )
+    }
+
+    /// Feed content may neither run scripts nor load fonts or frames, privacy mode additionally
+    /// blocks everything remote except images from the article's own origin.
+    /// Offline copies of enclosures can always be played.
+    fn content_security_policy(privacy_mode: bool) -> String {
+        let (img_src, media_src) = if privacy_mode {
+            ("'self' data:", format!("{}:", OFFLINE_SCHEME))
+        } else {
+            ("* data:", format!("* data: {}:", OFFLINE_SCHEME))
+        };
+        format!(
+            "default-src 'none'; script-src 'none'; object-src 'none'; style-src 'unsafe-inline'; \
+             font-src data:; img-src {}; media-src {}; frame-src 'none'",
+            img_src, media_src
+        )
+    }
+
+    /// Build the article html with explicit user CSS, e.g. to preview a theme that is still being edited.
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_article_with_css(
+        file_name: &str,
+        article: &FatArticle,
+        feed_name: &str,
+        settings: &Arc<RwLock<Settings>>,
+        theme: &ArticleTheme,
+        theme_css: Option<&str>,
+        user_css: Option<&str>,
+        font_size_override: Option<i32>,
+        prefer_scraped_content: bool,
     ) -> String {
         let template_data = Resources::get(&format!("article_view/{}.html", file_name)).expect(GTK_RESOURCE_FILE_ERROR);
         let template_str = str::from_utf8(template_data.as_ref()).expect(GTK_RESOURCE_FILE_ERROR);
@@ -859,16 +1740,21 @@ impl ArticleView {
         }
 
         // $HTML
-        if prefer_scraped_content {
-            if let Some(html) = &article.scraped_content {
-                template_string = template_string.replacen("$HTML", html, 1);
-            } else if let Some(html) = &article.html {
-                template_string = template_string.replacen("$HTML", html, 1);
-            }
-        } else if let Some(html) = &article.html {
-            template_string = template_string.replacen("$HTML", html, 1);
+        let html = if prefer_scraped_content {
+            article.scraped_content.as_ref().or_else(|| article.html.as_ref())
+        } else {
+            article.html.as_ref()
+        };
+        if let Some(html) = html {
+            let html = HtmlSanitizer::sanitize(html);
+            let html = CodeHighlighter::highlight(&html, &i18n("Copy"), &i18n("Copied"));
+            template_string = template_string.replacen("$HTML", &Math::render(&html), 1);
         }
 
+        // $CSP
+        let content_security_policy = Self::content_security_policy(settings.read().get_article_view_privacy_mode());
+        template_string = template_string.replacen("$CSP", &content_security_policy, 1);
+
         // $UNSELECTABLE
         if settings.read().get_article_view_allow_select() {
             template_string = template_string.replacen("$UNSELECTABLE", "", 2);
@@ -876,8 +1762,8 @@ impl ArticleView {
             template_string = template_string.replacen("$UNSELECTABLE", "unselectable", 2);
         }
 
-        // $AUTHOR / $DATE
-        template_string = template_string.replacen("$AUTHOR", &author_date, 1);
+        // $AUTHOR / $DATE, like title, url and feed name straight from the feed and escaped as text
+        template_string = template_string.replacen("$AUTHOR", &HtmlSanitizer::escape(&author_date), 1);
 
         // $SMALLSIZE x2
         let small_size = font_size - 2;
@@ -885,7 +1771,7 @@ impl ArticleView {
 
         // $TITLE
         if let Some(title) = &article.title {
-            template_string = template_string.replacen("$TITLE", title, 1);
+            template_string = template_string.replacen("$TITLE", &HtmlSanitizer::escape(title), 1);
         }
 
         // $LARGESIZE
@@ -894,23 +1780,18 @@ impl ArticleView {
 
         // $URL
         if let Some(article_url) = &article.url {
-            template_string = template_string.replacen("$URL", article_url.get().as_str(), 1);
+            template_string = template_string.replacen("$URL", &HtmlSanitizer::escape(article_url.get().as_str()), 1);
         }
 
         // $FEED
-        template_string = template_string.replacen("$FEED", feed_name, 1);
+        template_string = template_string.replacen("$FEED", &HtmlSanitizer::escape(feed_name), 1);
 
         // $THEME
-        let theme = if let Some(theme_override) = &theme_override {
-            theme_override
-                .to_str(settings.read().get_prefer_dark_theme())
-                .to_owned()
-        } else {
-            settings
-                .read()
-                .get_article_view_theme()
-                .to_str(settings.read().get_prefer_dark_theme())
-                .to_owned()
+        // a user theme whose file is gone falls back to the default theme
+        let prefer_dark_theme = settings.read().get_prefer_dark_theme();
+        let theme = match (theme, theme_css) {
+            (ArticleTheme::User(_), None) => ArticleTheme::Default.to_str(prefer_dark_theme).to_owned(),
+            (theme, _) => theme.to_str(prefer_dark_theme).to_owned(),
         };
         template_string = template_string.replacen("$THEME", &theme, 1);
 
@@ -920,12 +1801,44 @@ impl ArticleView {
         // $FONTSIZE
         template_string = template_string.replacen("$FONTSIZE", &format!("{}", font_size), 1);
 
-        // $CSS
+        // $CSS: user theme and user stylesheet go last so they can override the built-in rules
+        let mut css_string = css_string.to_owned();
+        css_string.push_str(&Self::typography_css(&settings.read()));
+        for css in theme_css.iter().chain(user_css.iter()) {
+            css_string.push('\n');
+            css_string.push_str(css);
+        }
         template_string = template_string.replacen("$CSS", &css_string, 1);
 
         template_string
     }
 
+    fn typography_css(settings: &Settings) -> String {
+        let text_align = if settings.get_article_view_justify() {
+            "justify"
+        } else {
+            "start"
+        };
+        let mut css = format!(
+            "\nbody {{ max-width: {}em; }}\n\
+             body, p {{ line-height: {}em; }}\n\
+             p {{ margin-bottom: {}rem; }}\n\
+             div.nfcontent p, div.nfcontent li {{ text-align: {}; }}\n",
+            settings.get_article_view_content_width(),
+            settings.get_article_view_line_height(),
+            settings.get_article_view_paragraph_spacing(),
+            text_align,
+        );
+        // neither feeds nor articles tell their language, so only content that declares it gets hyphenated
+        if settings.get_article_view_hyphenate() {
+            css.push_str(
+                "div.nfcontent [lang] p, div.nfcontent [lang] li, div.nfcontent p[lang], div.nfcontent li[lang] \
+                 { -webkit-hyphens: auto; hyphens: auto; }\n",
+            );
+        }
+        css
+    }
+
     fn set_scroll_pos_static(view: &WebView, pos: f64) {
         let cancellable: Option<&Cancellable> = None;
         view.run_javascript(&format!("window.scrollTo(0,{});", pos), cancellable, |res| match res {
@@ -938,6 +1851,18 @@ impl ArticleView {
         Self::webview_js_get_f64(view, "window.scrollY").expect("Failed to get scroll position from webview.")
     }
 
+    /// Scroll position, viewport height and document height without panicking on a view that
+    /// is still loading.
+    fn get_reading_progress_static(view: &WebView) -> Result<(f64, f64, f64), ArticleViewError> {
+        let pos = Self::webview_js_get_f64(view, "window.scrollY")?;
+        let window_height = Self::webview_js_get_f64(view, "window.innerHeight")?;
+        let upper = Self::webview_js_get_f64(
+            view,
+            "Math.max(document.body.scrollHeight, document.documentElement.scrollHeight)",
+        )?;
+        Ok((pos, window_height, upper))
+    }
+
     fn get_scroll_window_height_static(view: &WebView) -> f64 {
         Self::webview_js_get_f64(view, "window.innerHeight").expect("Failed to get window height from webview.")
     }
@@ -987,6 +1912,60 @@ impl ArticleView {
         output
     }
 
+    fn webview_js_get_string(view: &WebView, java_script: &str) -> Result<String, ArticleViewError> {
+        let wait_loop = Arc::new(MainLoop::new(None, false));
+        let value: Arc<RwLock<Option<String>>> = Arc::new(RwLock::new(None));
+        let cancellable: Option<&Cancellable> = None;
+        view.run_javascript(
+            java_script,
+            cancellable,
+            clone!(@weak wait_loop, @weak value => @default-panic, move |res| {
+                match res {
+                    Ok(result) => {
+                        if let (Some(context), Some(new_value)) = (result.get_global_context(), result.get_value()) {
+                            *value.write() = new_value.to_string(&context);
+                        }
+                    }
+                    Err(_) => error!("Running javascript failed"),
+                }
+                wait_loop.quit();
+            }),
+        );
+
+        wait_loop.run();
+
+        let output = value.write().take();
+        output.ok_or_else(|| ArticleViewErrorKind::NoValueFromJS.into())
+    }
+
+    fn webview_js_get_f64_async<F: Fn(f64) + 'static>(view: &WebView, java_script: &str, callback: F) {
+        let cancellable: Option<&Cancellable> = None;
+        view.run_javascript(java_script, cancellable, move |res| match res {
+            Ok(result) => {
+                if let (Some(context), Some(value)) = (result.get_global_context(), result.get_value()) {
+                    if let Some(value) = value.to_number(&context) {
+                        callback(value);
+                    }
+                }
+            }
+            Err(_) => error!("Running javascript failed"),
+        });
+    }
+
+    fn webview_js_get_string_async<F: Fn(String) + 'static>(view: &WebView, java_script: &str, callback: F) {
+        let cancellable: Option<&Cancellable> = None;
+        view.run_javascript(java_script, cancellable, move |res| match res {
+            Ok(result) => {
+                if let (Some(context), Some(value)) = (result.get_global_context(), result.get_value()) {
+                    if let Some(value) = value.to_string(&context) {
+                        callback(value);
+                    }
+                }
+            }
+            Err(_) => error!("Running javascript failed"),
+        });
+    }
+
     fn set_scroll_abs(&self, scroll: f64) -> Result<(), ArticleViewError> {
         let view_name = (*self.internal_state.read()).to_str().map(|s| s.to_owned());
         if let Some(view_name) = view_name {
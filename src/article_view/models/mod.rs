@@ -35,6 +35,8 @@ pub enum ArticleTheme {
     Midnight,
     Parchment,
     Gruvbox,
+    /// A theme file in the user's theme directory, identified by its file stem.
+    User(String),
 }
 
 impl ArticleTheme {
@@ -51,6 +53,7 @@ impl ArticleTheme {
             ArticleTheme::Midnight => "theme midnight",
             ArticleTheme::Parchment => "theme parchment",
             ArticleTheme::Gruvbox => "theme gruvbox",
+            ArticleTheme::User(_) => "theme user",
         }
     }
 
@@ -61,6 +64,7 @@ impl ArticleTheme {
             ArticleTheme::Midnight => "Midnight",
             ArticleTheme::Parchment => "Parchment",
             ArticleTheme::Gruvbox => "Gruvbox",
+            ArticleTheme::User(id) => id,
         }
     }
 }
@@ -0,0 +1,238 @@
+use super::ArticleView;
+use crate::app::Action;
+use crate::util::{GtkUtil, Util};
+use gio::Cancellable;
+use glib::{source::Continue, translate::ToGlib, Sender};
+use log::warn;
+use news_flash::models::ArticleID;
+use parking_lot::RwLock;
+use std::process::{Child, Command};
+use std::sync::Arc;
+use webkit2gtk::{WebView, WebViewExt};
+
+static SPEAKER: &str = "spd-say";
+static POLL_INTERVAL: u32 = 200;
+
+/// Reads the visible article aloud one paragraph at a time through speech-dispatcher.
+/// `read_aloud.js` splits the article into paragraphs and highlights the one being spoken.
+#[derive(Clone, Debug)]
+pub struct ReadAloud {
+    sender: Sender<Action>,
+    webview: Arc<RwLock<Option<WebView>>>,
+    article_id: Arc<RwLock<Option<ArticleID>>>,
+    paragraphs: Arc<RwLock<Vec<String>>>,
+    index: Arc<RwLock<usize>>,
+    playing: Arc<RwLock<bool>>,
+    speaker: Arc<RwLock<Option<Child>>>,
+    speaker_watch_signal: Arc<RwLock<Option<u32>>>,
+    continue_with: Arc<RwLock<Option<ArticleID>>>,
+    start_on_load: Arc<RwLock<bool>>,
+}
+
+impl ReadAloud {
+    pub fn new(sender: &Sender<Action>) -> Self {
+        ReadAloud {
+            sender: sender.clone(),
+            webview: Arc::new(RwLock::new(None)),
+            article_id: Arc::new(RwLock::new(None)),
+            paragraphs: Arc::new(RwLock::new(Vec::new())),
+            index: Arc::new(RwLock::new(0)),
+            playing: Arc::new(RwLock::new(false)),
+            speaker: Arc::new(RwLock::new(None)),
+            speaker_watch_signal: Arc::new(RwLock::new(None)),
+            continue_with: Arc::new(RwLock::new(None)),
+            start_on_load: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    /// Play or pause, reading starts at the top of the article if it wasn't read aloud yet.
+    pub fn toggle(&self, webview: &WebView, article_id: &ArticleID) {
+        if *self.playing.read() {
+            self.pause();
+        } else if self.article_id.read().as_ref() == Some(article_id) && !self.paragraphs.read().is_empty() {
+            *self.playing.write() = true;
+            self.speak_current();
+            Util::send(&self.sender, Action::ReadAloudState(true));
+        } else {
+            self.start(webview, article_id);
+        }
+    }
+
+    pub fn skip(&self) {
+        if self.paragraphs.read().is_empty() {
+            return;
+        }
+        self.stop_speaking();
+        *self.index.write() += 1;
+        if *self.playing.read() {
+            self.speak_current();
+        } else {
+            self.highlight(*self.index.read() as i64);
+        }
+    }
+
+    pub fn stop(&self) {
+        self.stop_speaking();
+        self.highlight(-1);
+        self.paragraphs.write().clear();
+        *self.index.write() = 0;
+        *self.playing.write() = false;
+        self.webview.write().take();
+        self.article_id.write().take();
+        Util::send(&self.sender, Action::ReadAloudState(false));
+    }
+
+    /// Keep reading once `article_id` is shown next.
+    pub fn continue_with(&self, article_id: ArticleID) {
+        self.continue_with.write().replace(article_id);
+    }
+
+    pub fn cancel_continue(&self) {
+        self.continue_with.write().take();
+        *self.start_on_load.write() = false;
+    }
+
+    /// Any other article than the one reading continues with was picked by the reader and ends reading aloud.
+    pub fn article_shown(&self, article_id: &ArticleID) {
+        self.stop();
+        let target = self.continue_with.write().take();
+        *self.start_on_load.write() = target.as_ref() == Some(article_id);
+    }
+
+    /// The article was (re)rendered, pick up where reading left off.
+    pub fn article_loaded(&self, webview: &WebView, article_id: &ArticleID) {
+        let start = *self.start_on_load.read();
+        if start {
+            *self.start_on_load.write() = false;
+            self.start(webview, article_id);
+        } else if self.article_id.read().as_ref() == Some(article_id) {
+            self.webview.write().replace(webview.clone());
+            self.highlight(*self.index.read() as i64);
+        }
+    }
+
+    fn start(&self, webview: &WebView, article_id: &ArticleID) {
+        self.stop();
+        self.webview.write().replace(webview.clone());
+        self.article_id.write().replace(article_id.clone());
+
+        let read_aloud = self.clone();
+        let article_id = article_id.clone();
+        ArticleView::webview_js_get_string_async(
+            webview,
+            "typeof NewsFlashReadAloud === 'undefined' ? '[]' : NewsFlashReadAloud.paragraphs()",
+            move |json| {
+                // another article may have been opened in the meantime
+                if read_aloud.article_id.read().as_ref() != Some(&article_id) {
+                    return;
+                }
+                let paragraphs: Vec<String> = serde_json::from_str::<Vec<String>>(&json)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|paragraph| paragraph.trim().to_owned())
+                    .filter(|paragraph| !paragraph.is_empty())
+                    .collect();
+                if paragraphs.is_empty() {
+                    warn!("Nothing to read aloud in article '{}'", article_id);
+                    return;
+                }
+                *read_aloud.paragraphs.write() = paragraphs;
+                *read_aloud.playing.write() = true;
+                read_aloud.speak_current();
+                Util::send(&read_aloud.sender, Action::ReadAloudState(true));
+            },
+        );
+    }
+
+    fn pause(&self) {
+        self.stop_speaking();
+        *self.playing.write() = false;
+        Util::send(&self.sender, Action::ReadAloudState(false));
+    }
+
+    fn speak_current(&self) {
+        let index = *self.index.read();
+        let paragraph = self.paragraphs.read().get(index).cloned();
+        let paragraph = match paragraph {
+            Some(paragraph) => paragraph,
+            None => {
+                self.finish();
+                return;
+            }
+        };
+        self.highlight(index as i64);
+
+        let child = Command::new(SPEAKER)
+            .args(&["--wait", "--application-name", "NewsFlash", "--", &paragraph])
+            .spawn();
+        match child {
+            Ok(child) => {
+                self.speaker.write().replace(child);
+            }
+            Err(error) => {
+                warn!("Failed to run '{}': {}", SPEAKER, error);
+                self.stop();
+                Util::send(
+                    &self.sender,
+                    Action::ErrorSimpleMessage("Failed to read aloud. Is speech-dispatcher installed?".to_owned()),
+                );
+                return;
+            }
+        }
+
+        let read_aloud = self.clone();
+        self.speaker_watch_signal.write().replace(
+            gtk::timeout_add(POLL_INTERVAL, move || {
+                let done = match read_aloud.speaker.write().as_mut().map(|child| child.try_wait()) {
+                    Some(Ok(None)) => false,
+                    Some(Ok(Some(_))) | Some(Err(_)) | None => true,
+                };
+                if !done {
+                    return Continue(true);
+                }
+                read_aloud.speaker_watch_signal.write().take();
+                read_aloud.speaker.write().take();
+                *read_aloud.index.write() += 1;
+                read_aloud.speak_current();
+                Continue(false)
+            })
+            .to_glib(),
+        );
+    }
+
+    /// The last paragraph was spoken.
+    fn finish(&self) {
+        let article_id = self.article_id.read().clone();
+        self.stop();
+        if let Some(article_id) = article_id {
+            Util::send(&self.sender, Action::ReadAloudFinished(article_id));
+        }
+    }
+
+    fn stop_speaking(&self) {
+        GtkUtil::remove_source(self.speaker_watch_signal.write().take());
+        if let Some(mut child) = self.speaker.write().take() {
+            // speech-dispatcher keeps speaking the message of a client that went away, cancel it first
+            let cancelled = Command::new(SPEAKER).arg("--cancel").status();
+            if let Err(error) = cancelled {
+                warn!("Failed to cancel speech: {}", error);
+            }
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    fn highlight(&self, index: i64) {
+        if let Some(webview) = &*self.webview.read() {
+            let cancellable: Option<&Cancellable> = None;
+            webview.run_javascript(
+                &format!(
+                    "typeof NewsFlashReadAloud === 'undefined' || NewsFlashReadAloud.highlight({})",
+                    index
+                ),
+                cancellable,
+                |_res| {},
+            );
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use ammonia::Builder;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use url::Url;
+
+// allowed on top of ammonia's defaults, frames and plugins stay out
+static EXTRA_TAGS: &[&str] = &["audio", "picture", "section", "source", "tfoot", "track", "video"];
+// dropped together with everything they contain
+static REMOVED_ELEMENTS: &[&str] = &["applet", "iframe", "object", "script", "style"];
+static GENERIC_ATTRIBUTES: &[&str] = &["class", "dir", "id", "lang", "style", "title"];
+static EXTRA_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("audio", &["controls", "loop", "src"]),
+    ("img", &["sizes", "srcset"]),
+    ("ol", &["reversed", "type"]),
+    ("source", &["media", "sizes", "src", "srcset", "type"]),
+    ("track", &["kind", "label", "src", "srclang"]),
+    (
+        "video",
+        &["controls", "height", "loop", "muted", "poster", "src", "width"],
+    ),
+];
+// `data:` is only kept for images, see `HtmlSanitizer::filter_attribute`
+static URL_SCHEMES: &[&str] = &["data", "http", "https", "mailto"];
+// css that can load something or run code
+static UNSAFE_CSS: &[&str] = &["url(", "image(", "image-set(", "expression(", "@import", "\\"];
+
+lazy_static! {
+    static ref BUILDER: Builder<'static> = HtmlSanitizer::builder();
+    static ref IMAGE: Regex = Regex::new(r#"<img\b(?:"[^"]*"|[^">])*>"#).expect("invalid image regex");
+    static ref ATTRIBUTE: Regex = Regex::new(r#"\s([a-z-]+)="([^"]*)""#).expect("invalid attribute regex");
+}
+
+/// Strips everything from feed supplied html that could run code or phone home:
+/// scripts, plugins, frames, event handlers, urls other than http(s) and mailto,
+/// css that loads resources, tracking pixels and remote fonts.
+pub struct HtmlSanitizer;
+
+impl HtmlSanitizer {
+    pub fn sanitize(html: &str) -> String {
+        let html = BUILDER.clean(html).to_string();
+        IMAGE
+            .replace_all(&html, |captures: &Captures| {
+                let mut width = None;
+                let mut height = None;
+                for attribute in ATTRIBUTE.captures_iter(&captures[0]) {
+                    match &attribute[1] {
+                        "width" => width = Some(attribute[2].trim().trim_end_matches("px").to_owned()),
+                        "height" => height = Some(attribute[2].trim().trim_end_matches("px").to_owned()),
+                        _ => {}
+                    }
+                }
+                if Self::is_tracking_pixel(width.as_deref(), height.as_deref()) {
+                    String::new()
+                } else {
+                    captures[0].to_owned()
+                }
+            })
+            .into_owned()
+    }
+
+    fn builder() -> Builder<'static> {
+        let mut builder = Builder::default();
+        builder
+            .add_tags(EXTRA_TAGS)
+            .clean_content_tags(REMOVED_ELEMENTS.iter().copied().collect::<HashSet<_>>())
+            .add_generic_attributes(GENERIC_ATTRIBUTES)
+            .url_schemes(URL_SCHEMES.iter().copied().collect::<HashSet<_>>())
+            .attribute_filter(Self::filter_attribute);
+        for (tag, attributes) in EXTRA_ATTRIBUTES {
+            builder.add_tag_attributes(tag, *attributes);
+        }
+        builder
+    }
+
+    fn filter_attribute<'u>(element: &str, attribute: &str, value: &'u str) -> Option<Cow<'u, str>> {
+        match attribute {
+            "style" => {
+                let style = value
+                    .split(';')
+                    .filter(|declaration| {
+                        let declaration = declaration.to_lowercase();
+                        !UNSAFE_CSS.iter().any(|unsafe_css| declaration.contains(unsafe_css))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(";");
+                if style.trim().is_empty() {
+                    None
+                } else {
+                    Some(style.into())
+                }
+            }
+            // ammonia doesn't look into srcset, every candidate has to be a safe url
+            "srcset" => {
+                let safe = value
+                    .split(',')
+                    .filter_map(|candidate| candidate.split_whitespace().next())
+                    .all(|url| Self::is_image_url(url) && !url.trim_start().to_lowercase().starts_with("data:"));
+                if safe {
+                    Some(value.into())
+                } else {
+                    None
+                }
+            }
+            "src" if element == "img" || element == "source" => {
+                if Self::is_image_url(value) {
+                    Some(value.into())
+                } else {
+                    None
+                }
+            }
+            "href" | "src" | "poster" | "cite" => {
+                if value.trim_start().to_lowercase().starts_with("data:") {
+                    None
+                } else {
+                    Some(value.into())
+                }
+            }
+            _ => Some(value.into()),
+        }
+    }
+
+    /// http(s), relative urls and inline images.
+    fn is_image_url(value: &str) -> bool {
+        match Url::parse(value.trim()) {
+            Ok(url) => match url.scheme() {
+                "http" | "https" => true,
+                "data" => url.path().to_lowercase().starts_with("image/"),
+                _ => false,
+            },
+            Err(url::ParseError::RelativeUrlWithoutBase) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Escape text for use in element content and quoted attribute values.
+    pub fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    /// Images that declare a size of at most 1x1 pixels only exist to track the reader.
+    fn is_tracking_pixel(width: Option<&str>, height: Option<&str>) -> bool {
+        let tiny = |size: Option<&str>| size.and_then(|size| size.parse::<f64>().ok()).map(|size| size <= 1.0);
+        match (tiny(width), tiny(height)) {
+            (Some(width), Some(height)) => width && height,
+            (Some(tiny), None) | (None, Some(tiny)) => tiny,
+            (None, None) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HtmlSanitizer;
+
+    #[test]
+    fn removes_scripts_and_handlers() {
+        let html = r#"<p onclick="steal()">Hi<script type="text/javascript">alert("x > y")</script></p>"#;
+        assert_eq!(HtmlSanitizer::sanitize(html), "<p>Hi</p>");
+
+        let html = r#"<a href=" JavaScript:alert(1)" title='t'>link</a><img src=x OnError=alert(1)/>"#;
+        assert_eq!(
+            HtmlSanitizer::sanitize(html),
+            r#"<a title="t" rel="noopener noreferrer">link</a><img src="x">"#
+        );
+
+        let html = r#"<a href="javascript&colon;alert(1)">a</a><a href="java&#115;cript&#58;alert(1)">b</a>"#;
+        assert_eq!(
+            HtmlSanitizer::sanitize(html),
+            r#"<a rel="noopener noreferrer">a</a><a rel="noopener noreferrer">b</a>"#
+        );
+
+        // only the enclosure list may start or remove downloads
+        let html =
+            r#"<a href="newsflash-download:https://a.example/e.mp3">a</a><a href="newsflash-remove-download:x">b</a>"#;
+        assert_eq!(
+            HtmlSanitizer::sanitize(html),
+            r#"<a rel="noopener noreferrer">a</a><a rel="noopener noreferrer">b</a>"#
+        );
+    }
+
+    #[test]
+    fn removes_frames_and_css_urls() {
+        let html = r#"<iframe src="https://video.example/embed"></iframe><p style="color: red; background: URL(https://t.example/p.gif)">x</p>"#;
+        assert_eq!(HtmlSanitizer::sanitize(html), r#"<p style="color: red">x</p>"#);
+
+        let html =
+            r#"<img srcset="https://a.example/a.png 1x, javascript:alert(1) 2x" src="data:image/png;base64,AA==">"#;
+        assert_eq!(
+            HtmlSanitizer::sanitize(html),
+            r#"<img src="data:image/png;base64,AA==">"#
+        );
+    }
+
+    #[test]
+    fn removes_trackers_and_fonts() {
+        let html = r#"<img src="https://tracker.example/p.gif" width="1" height="1"><img src="a.png" width="640">"#;
+        assert_eq!(HtmlSanitizer::sanitize(html), r#"<img src="a.png" width="640">"#);
+
+        let html = r#"<link rel="stylesheet" href="https://fonts.example/css"><style>@font-face { src: url(f.woff); }</style><p>x</p>"#;
+        assert_eq!(HtmlSanitizer::sanitize(html), "<p>x</p>");
+    }
+}
@@ -0,0 +1,52 @@
+use crate::util::BuilderHelper;
+use gtk::{Box, Button, Label, LabelExt, WidgetExt};
+
+#[derive(Clone, Debug)]
+pub struct SearchOverlay {
+    parent: Box,
+    label: Label,
+    pub prev_button: Button,
+    pub next_button: Button,
+    count: i64,
+}
+
+impl SearchOverlay {
+    pub fn new() -> Self {
+        let builder = BuilderHelper::new("article_view_search");
+        let parent = builder.get::<Box>("box");
+        let label = builder.get::<Label>("label");
+        let prev_button = builder.get::<Button>("prev_button");
+        let next_button = builder.get::<Button>("next_button");
+
+        SearchOverlay {
+            parent,
+            label,
+            prev_button,
+            next_button,
+            count: 0,
+        }
+    }
+
+    /// Show the number of search hits in the article. The overlay is hidden if there are none.
+    pub fn set_count(&mut self, count: i64) {
+        self.count = count;
+        self.label.set_label(&count.to_string());
+        self.reveal(count > 0);
+    }
+
+    pub fn set_position(&self, position: i64) {
+        self.label.set_label(&format!("{} / {}", position, self.count));
+    }
+
+    pub fn reveal(&self, show: bool) {
+        if show {
+            self.parent.show();
+        } else {
+            self.parent.hide();
+        }
+    }
+
+    pub fn widget(&self) -> gtk::Box {
+        self.parent.clone()
+    }
+}
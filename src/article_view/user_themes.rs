@@ -0,0 +1,164 @@
+use super::error::{ArticleViewError, ArticleViewErrorKind};
+use super::models::ArticleTheme;
+use crate::settings::Settings;
+use crate::util::FileUtil;
+use failure::ResultExt;
+use log::warn;
+use parking_lot::RwLock;
+use std::fs;
+use std::path::PathBuf;
+
+static THEME_DIR: &str = "themes";
+static THEME_EXTENSION: &str = "css";
+static USER_STYLESHEET: &str = "user.css";
+static THEME_TEMPLATE: &str = "/* Custom article theme.
+ * The article body carries the classes \"theme user\".
+ * These rules are applied on top of the default stylesheet.
+ * Code blocks take their colors from the --nf-code-* variables. */
+
+body.theme.user {
+    background-color: #ffffff;
+    color: #2e3436;
+    --nf-code-keyword: #a626a4;
+    --nf-code-string: #50a14f;
+    --nf-code-comment: #a0a1a7;
+}
+
+body.theme.user header.post h1,
+body.theme.user header.post h1 a {
+    color: #1c71d8;
+}
+
+body.theme.user div.nfcontent a {
+    color: #1c71d8;
+}
+";
+
+/// Article themes and the global stylesheet the user keeps in the data directory.
+pub struct UserThemes;
+
+impl UserThemes {
+    pub fn dir() -> PathBuf {
+        crate::app::DATA_DIR.join(THEME_DIR)
+    }
+
+    pub fn theme_path(id: &str) -> PathBuf {
+        Self::dir().join(format!("{}.{}", id, THEME_EXTENSION))
+    }
+
+    pub fn stylesheet_path() -> PathBuf {
+        crate::app::DATA_DIR.join(USER_STYLESHEET)
+    }
+
+    /// Ids (file stems) of all themes in the theme directory, sorted alphabetically.
+    pub fn list() -> Vec<String> {
+        let entries = match fs::read_dir(Self::dir()) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut ids: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(THEME_EXTENSION))
+            .filter_map(|path| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| stem.to_owned())
+            })
+            .collect();
+        ids.sort_by_key(|id| id.to_lowercase());
+        ids
+    }
+
+    pub fn load(id: &str) -> Option<String> {
+        if id.is_empty() {
+            return None;
+        }
+        match FileUtil::read_text_file(&Self::theme_path(id)) {
+            Ok(css) => Some(css),
+            Err(_) => {
+                warn!("Failed to load user theme '{}'", id);
+                None
+            }
+        }
+    }
+
+    pub fn save(id: &str, css: &str) -> Result<(), ArticleViewError> {
+        fs::create_dir_all(Self::dir()).context(ArticleViewErrorKind::UserTheme)?;
+        FileUtil::write_text_file(&Self::theme_path(id), css).context(ArticleViewErrorKind::UserTheme)?;
+        Ok(())
+    }
+
+    /// Create a new theme file from the template. Returns the id of the new theme.
+    pub fn create(name: &str) -> Result<String, ArticleViewError> {
+        let base_id = Self::id_from_name(name);
+        let existing = Self::list();
+        let mut id = base_id.clone();
+        let mut counter = 2;
+        while existing.contains(&id) {
+            id = format!("{}-{}", base_id, counter);
+            counter += 1;
+        }
+        Self::save(&id, THEME_TEMPLATE)?;
+        Ok(id)
+    }
+
+    pub fn load_stylesheet() -> String {
+        FileUtil::read_text_file(&Self::stylesheet_path()).unwrap_or_default()
+    }
+
+    pub fn save_stylesheet(css: &str) -> Result<(), ArticleViewError> {
+        fs::create_dir_all(crate::app::DATA_DIR.as_path()).context(ArticleViewErrorKind::UserTheme)?;
+        FileUtil::write_text_file(&Self::stylesheet_path(), css).context(ArticleViewErrorKind::UserTheme)?;
+        Ok(())
+    }
+
+    /// CSS of a user theme. `None` for the built-in themes, which live in the embedded stylesheet.
+    pub fn theme_css(theme: &ArticleTheme) -> Option<String> {
+        match theme {
+            ArticleTheme::User(id) => Self::load(id),
+            _ => None,
+        }
+    }
+
+    /// The global user stylesheet, if it is enabled.
+    pub fn stylesheet_css(settings: &RwLock<Settings>) -> Option<String> {
+        if settings.read().get_article_view_user_stylesheet() {
+            Some(Self::load_stylesheet())
+        } else {
+            None
+        }
+    }
+
+    fn id_from_name(name: &str) -> String {
+        let mut id = String::new();
+        for c in name.trim().chars() {
+            if c.is_alphanumeric() {
+                id.extend(c.to_lowercase());
+            } else if !id.is_empty() && !id.ends_with('-') {
+                id.push('-');
+            }
+        }
+        let id = id.trim_end_matches('-').to_owned();
+        if id.is_empty() {
+            "theme".to_owned()
+        } else {
+            id
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserThemes;
+
+    #[test]
+    fn theme_id_from_name() {
+        assert_eq!(UserThemes::id_from_name("Solarized Light"), "solarized-light");
+        assert_eq!(UserThemes::id_from_name("  my/theme!! v2 "), "my-theme-v2");
+        assert_eq!(UserThemes::id_from_name("../"), "theme");
+        assert_eq!(UserThemes::id_from_name(""), "theme");
+    }
+}
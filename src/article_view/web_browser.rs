@@ -0,0 +1,281 @@
+use super::models::InternalState;
+use super::ArticleView;
+use crate::app::Action;
+use crate::settings::Settings;
+use crate::util::{BuilderHelper, Util, GTK_RESOURCE_FILE_ERROR};
+use crate::Resources;
+use chrono::Utc;
+use glib::{clone, object::Cast, Sender};
+use gtk::{
+    Box, BoxExt, Button, ButtonExt, ContainerExt, Label, LabelExt, Stack, StackExt, ToggleButton, ToggleButtonExt,
+    WidgetExt,
+};
+use log::warn;
+use news_flash::models::{ArticleID, FatArticle, FeedID, Marked, Read, Url};
+use parking_lot::RwLock;
+use serde::Deserialize;
+use std::str;
+use std::sync::Arc;
+use webkit2gtk::{
+    LoadEvent, NavigationPolicyDecision, NavigationPolicyDecisionExt, PolicyDecisionExt, PolicyDecisionType, WebView,
+    WebViewExt,
+};
+
+pub static BROWSER_PAGE: &str = "browser";
+
+/// What `reader_mode.js` extracted from a web page.
+#[derive(Deserialize)]
+struct ReaderContent {
+    title: String,
+    content: String,
+}
+
+/// Browses the links of an article inside NewsFlash. Every link that is opened starts a new history,
+/// "Back to Article" returns to the article that was shown before.
+#[derive(Clone, Debug)]
+pub struct WebBrowser {
+    widget: Box,
+    webview: Arc<RwLock<Option<WebView>>>,
+    uri_label: Label,
+    back_button: Button,
+    forward_button: Button,
+    reader_button: ToggleButton,
+    /// the uri of the page, the reader mode rendering doesn't have one of its own
+    page_uri: Arc<RwLock<Option<String>>>,
+    reader_mode: Arc<RwLock<bool>>,
+    loading_reader_mode: Arc<RwLock<bool>>,
+    settings: Arc<RwLock<Settings>>,
+    sender: Sender<Action>,
+}
+
+impl WebBrowser {
+    pub fn new(
+        stack: &Stack,
+        internal_state: &Arc<RwLock<InternalState>>,
+        settings: &Arc<RwLock<Settings>>,
+        sender: &Sender<Action>,
+    ) -> Self {
+        let builder = BuilderHelper::new("article_view_browser");
+        let widget = builder.get::<Box>("browser_box");
+        let article_button = builder.get::<Button>("article_button");
+        let back_button = builder.get::<Button>("back_button");
+        let forward_button = builder.get::<Button>("forward_button");
+        let reload_button = builder.get::<Button>("reload_button");
+        let uri_label = builder.get::<Label>("uri_label");
+        let reader_button = builder.get::<ToggleButton>("reader_button");
+        let external_button = builder.get::<Button>("external_button");
+
+        let browser = WebBrowser {
+            widget,
+            webview: Arc::new(RwLock::new(None)),
+            uri_label,
+            back_button,
+            forward_button,
+            reader_button,
+            page_uri: Arc::new(RwLock::new(None)),
+            reader_mode: Arc::new(RwLock::new(false)),
+            loading_reader_mode: Arc::new(RwLock::new(false)),
+            settings: settings.clone(),
+            sender: sender.clone(),
+        };
+
+        article_button.connect_clicked(clone!(
+            @weak stack,
+            @weak internal_state,
+            @strong browser => @default-panic, move |_button|
+        {
+            browser.close();
+            if let Some(view_name) = internal_state.read().to_str() {
+                stack.set_visible_child_name(view_name);
+            }
+        }));
+
+        browser.back_button.connect_clicked(
+            clone!(@weak browser.webview as webview => @default-panic, move |_button| {
+                if let Some(webview) = &*webview.read() {
+                    webview.go_back();
+                }
+            }),
+        );
+        browser.forward_button.connect_clicked(
+            clone!(@weak browser.webview as webview => @default-panic, move |_button| {
+                if let Some(webview) = &*webview.read() {
+                    webview.go_forward();
+                }
+            }),
+        );
+        reload_button.connect_clicked(clone!(@strong browser => @default-panic, move |_button| {
+            browser.reload();
+        }));
+        external_button.connect_clicked(clone!(@strong browser => @default-panic, move |_button| {
+            if let Some(uri) = browser.page_uri.read().clone() {
+                Util::send(&browser.sender, Action::OpenUrlInDefaultBrowser(uri));
+            }
+        }));
+        browser
+            .reader_button
+            .connect_toggled(clone!(@strong browser => @default-panic, move |button| {
+                // the button only follows the page
+                if button.get_active() == *browser.reader_mode.read() {
+                    return;
+                }
+                if button.get_active() {
+                    browser.show_reader_mode();
+                } else {
+                    browser.reload();
+                }
+            }));
+
+        browser
+    }
+
+    pub fn widget(&self) -> Box {
+        self.widget.clone()
+    }
+
+    /// Whether links of articles of the feed are opened in NewsFlash.
+    pub fn opens_links(settings: &Settings, feed_id: &FeedID) -> bool {
+        settings
+            .get_article_view_feed_internal_links(feed_id)
+            .unwrap_or_else(|| settings.get_article_view_internal_links())
+    }
+
+    /// Start browsing at `uri` with an empty history.
+    pub fn open(&self, uri: &str) {
+        self.close();
+
+        let webview = WebView::new();
+        webview.set_hexpand(true);
+        webview.set_vexpand(true);
+        self.connect_webview(&webview);
+        self.widget.pack_start(&webview, true, true, 0);
+        webview.show();
+        webview.load_uri(uri);
+        self.page_uri.write().replace(uri.to_owned());
+        self.uri_label.set_text(uri);
+        self.webview.write().replace(webview);
+    }
+
+    /// Stop browsing and free the web view.
+    pub fn close(&self) {
+        if let Some(webview) = self.webview.write().take() {
+            webview.stop_loading();
+            self.widget.remove(&webview);
+        }
+        self.page_uri.write().take();
+        *self.reader_mode.write() = false;
+        self.reader_button.set_active(false);
+        self.back_button.set_sensitive(false);
+        self.forward_button.set_sensitive(false);
+    }
+
+    fn reload(&self) {
+        let uri = self.page_uri.read().clone();
+        if let (Some(webview), Some(uri)) = (&*self.webview.read(), uri) {
+            if *self.reader_mode.read() {
+                // the reader mode rendering replaced the page, load it again
+                webview.load_uri(&uri);
+            } else {
+                webview.reload();
+            }
+        }
+    }
+
+    fn connect_webview(&self, webview: &WebView) {
+        let browser = self.clone();
+        webview.connect_load_changed(move |webview, event| match event {
+            LoadEvent::Started => {
+                if *browser.loading_reader_mode.read() {
+                    *browser.loading_reader_mode.write() = false;
+                } else {
+                    *browser.reader_mode.write() = false;
+                    browser.reader_button.set_active(false);
+                }
+            }
+            LoadEvent::Committed => {
+                if !*browser.reader_mode.read() {
+                    if let Some(uri) = webview.get_uri() {
+                        browser.uri_label.set_text(&uri);
+                        browser.page_uri.write().replace(uri.as_str().to_owned());
+                    }
+                }
+                browser.back_button.set_sensitive(webview.can_go_back());
+                browser.forward_button.set_sensitive(webview.can_go_forward());
+            }
+            _ => {}
+        });
+
+        // pages that want a new window are shown right here, as the next page of the history
+        // so "Back" returns to the page that opened it
+        webview.connect_decide_policy(|webview, decision, decision_type| {
+            if decision_type != PolicyDecisionType::NewWindowAction {
+                return false;
+            }
+            let request = decision
+                .clone()
+                .downcast::<NavigationPolicyDecision>()
+                .ok()
+                .and_then(|decision| decision.get_navigation_action())
+                .and_then(|action| action.get_request());
+            decision.ignore();
+            if let Some(request) = request {
+                webview.load_request(&request);
+            }
+            true
+        });
+    }
+
+    /// Render the main content of the page like an article.
+    fn show_reader_mode(&self) {
+        let webview = match &*self.webview.read() {
+            Some(webview) => webview.clone(),
+            None => return,
+        };
+        let js_data = Resources::get("article_view/reader_mode.js").expect(GTK_RESOURCE_FILE_ERROR);
+        let java_script = str::from_utf8(js_data.as_ref()).expect("Failed to load JS from resources");
+
+        let browser = self.clone();
+        let reader_webview = webview.clone();
+        ArticleView::webview_js_get_string_async(&webview, java_script, move |json| {
+            let page_uri = match browser.page_uri.read().clone() {
+                Some(page_uri) => page_uri,
+                None => return,
+            };
+            let reader_content = match serde_json::from_str::<ReaderContent>(&json) {
+                Ok(reader_content) if !reader_content.content.trim().is_empty() => reader_content,
+                _ => {
+                    warn!("No content found for reader mode of '{}'", page_uri);
+                    browser.reader_button.set_active(false);
+                    return;
+                }
+            };
+
+            let url = Url::parse(&page_uri).ok();
+            let site = url
+                .as_ref()
+                .and_then(|url| url.get().host_str().map(|host| host.to_owned()))
+                .unwrap_or_default();
+            let article = FatArticle {
+                article_id: ArticleID::new("reader_mode"),
+                title: Some(reader_content.title),
+                author: None,
+                feed_id: FeedID::new("reader_mode"),
+                direction: None,
+                date: Utc::now().naive_utc(),
+                marked: Marked::Unmarked,
+                unread: Read::Read,
+                url,
+                summary: None,
+                html: Some(reader_content.content),
+                scraped_content: None,
+                plain_text: None,
+            };
+            let html =
+                ArticleView::build_article_static("article", &article, &site, &browser.settings, None, None, false);
+
+            *browser.reader_mode.write() = true;
+            *browser.loading_reader_mode.write() = true;
+            reader_webview.load_html(&html, Some(&page_uri));
+        });
+    }
+}
@@ -0,0 +1,43 @@
+use crate::i18n::{i18n, i18n_f};
+use crate::sidebar::models::SidebarSelection;
+use crate::util::BuilderHelper;
+use gtk::{Button, Dialog, GtkWindowExt, Label, LabelExt, SpinButton, WidgetExt};
+
+#[derive(Clone, Debug)]
+pub struct CatchUpDialog {
+    pub dialog: Dialog,
+    pub catch_up_button: Button,
+    pub days_spin: SpinButton,
+}
+
+impl CatchUpDialog {
+    pub fn new(parent: &gtk::ApplicationWindow, selection: &SidebarSelection) -> Self {
+        let builder = BuilderHelper::new("catch_up_dialog");
+        let catch_up_button = builder.get::<Button>("catch_up_button");
+        let days_spin = builder.get::<SpinButton>("days_spin");
+        let selection_label = builder.get::<Label>("selection_label");
+        let dialog = builder.get::<Dialog>("catch_up_dialog");
+
+        let text = match selection {
+            SidebarSelection::All => i18n("Mark all unread articles older than the chosen age as read."),
+            SidebarSelection::Podcasts => i18n("Mark all unread podcast episodes older than the chosen age as read."),
+            SidebarSelection::Category(_, name)
+            | SidebarSelection::Feed(_, _, name)
+            | SidebarSelection::Tag(_, name)
+            | SidebarSelection::SavedSearch(_, name) => i18n_f(
+                "Mark all unread articles in '{}' older than the chosen age as read.",
+                &[name],
+            ),
+        };
+        selection_label.set_text(&text);
+
+        dialog.set_transient_for(Some(parent));
+        dialog.show_all();
+
+        CatchUpDialog {
+            dialog,
+            catch_up_button,
+            days_spin,
+        }
+    }
+}
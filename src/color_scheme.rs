@@ -0,0 +1,200 @@
+use gio::{
+    BusType, Cancellable, DBusCallFlags, DBusProxy, DBusProxyExt, DBusProxyFlags, Settings as GSettings,
+    SettingsExt as GSettingsExt, SettingsSchemaSource,
+};
+use glib::{object::ObjectExt, ToVariant, Variant};
+use log::{info, warn};
+use std::rc::Rc;
+
+static PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+static PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+static PORTAL_SETTINGS: &str = "org.freedesktop.portal.Settings";
+static APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+static COLOR_SCHEME_KEY: &str = "color-scheme";
+static GNOME_INTERFACE_SCHEMA: &str = "org.gnome.desktop.interface";
+static GTK_THEME_KEY: &str = "gtk-theme";
+
+/// The dark/light preference of the desktop: the freedesktop appearance portal first,
+/// the GNOME interface settings as fallback.
+pub struct ColorScheme {
+    portal: Option<DBusProxy>,
+    interface_settings: Option<GSettings>,
+}
+
+impl ColorScheme {
+    pub fn new() -> Self {
+        let cancellable: Option<&Cancellable> = None;
+        let portal = match DBusProxy::new_for_bus_sync(
+            BusType::Session,
+            DBusProxyFlags::DO_NOT_LOAD_PROPERTIES,
+            None,
+            PORTAL_NAME,
+            PORTAL_PATH,
+            PORTAL_SETTINGS,
+            cancellable,
+        ) {
+            Ok(proxy) => Some(proxy),
+            Err(error) => {
+                info!("Settings portal not available: {}", error);
+                None
+            }
+        };
+
+        let interface_settings = SettingsSchemaSource::get_default()
+            .and_then(|source| source.lookup(GNOME_INTERFACE_SCHEMA, true))
+            .map(|_| GSettings::new(GNOME_INTERFACE_SCHEMA));
+
+        ColorScheme {
+            portal,
+            interface_settings,
+        }
+    }
+
+    /// Ask for the preference without blocking. `callback` gets `None` if the desktop doesn't express any.
+    pub fn prefers_dark<F: FnOnce(Option<bool>) + 'static>(&self, callback: F) {
+        let interface_settings = self.interface_settings.clone();
+        Self::portal_prefers_dark(self.portal.as_ref(), move |prefers_dark| {
+            callback(prefers_dark.or_else(|| Self::gnome_prefers_dark(interface_settings.as_ref())));
+        });
+    }
+
+    pub fn connect_changed<F: Fn(bool) + 'static>(&self, callback: F) {
+        let callback = Rc::new(callback);
+
+        if let Some(portal) = &self.portal {
+            let interface_settings = self.interface_settings.clone();
+            let callback = callback.clone();
+            let result = portal.connect_local("g-signal", false, move |values| {
+                let signal_name = values.get(2).and_then(|value| value.get::<String>().ok().flatten());
+                let parameters = values.get(3).and_then(|value| value.get::<Variant>().ok().flatten());
+                if signal_name.as_deref() != Some("SettingChanged") {
+                    return None;
+                }
+                if let Some(parameters) = parameters {
+                    let namespace = parameters.get_child_value(0);
+                    let key = parameters.get_child_value(1);
+                    if namespace.get_str() == Some(APPEARANCE_NAMESPACE) && key.get_str() == Some(COLOR_SCHEME_KEY) {
+                        let prefers_dark = Self::variant_to_u32(&parameters.get_child_value(2))
+                            .and_then(Self::portal_value_prefers_dark)
+                            .or_else(|| Self::gnome_prefers_dark(interface_settings.as_ref()));
+                        if let Some(prefers_dark) = prefers_dark {
+                            callback(prefers_dark);
+                        }
+                    }
+                }
+                None
+            });
+            if result.is_err() {
+                warn!("Failed to listen for color-scheme changes of the settings portal");
+            }
+        }
+
+        if let Some(interface_settings) = &self.interface_settings {
+            let portal = self.portal.clone();
+            interface_settings.connect_changed(None, move |interface_settings, key| {
+                if key != COLOR_SCHEME_KEY && key != GTK_THEME_KEY {
+                    return;
+                }
+                // the portal has the final say if it expresses a preference
+                let interface_settings = interface_settings.clone();
+                let callback = callback.clone();
+                Self::portal_prefers_dark(portal.as_ref(), move |portal_prefers_dark| {
+                    if portal_prefers_dark.is_some() {
+                        return;
+                    }
+                    if let Some(prefers_dark) = Self::gnome_prefers_dark(Some(&interface_settings)) {
+                        callback(prefers_dark);
+                    }
+                });
+            });
+        }
+    }
+
+    fn portal_prefers_dark<F: FnOnce(Option<bool>) + 'static>(portal: Option<&DBusProxy>, callback: F) {
+        let portal = match portal {
+            Some(portal) => portal,
+            None => return callback(None),
+        };
+        let cancellable: Option<&Cancellable> = None;
+        let parameters = (APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY).to_variant();
+        portal.call(
+            "Read",
+            Some(&parameters),
+            DBusCallFlags::NONE,
+            1000,
+            cancellable,
+            move |result| {
+                let prefers_dark = result
+                    .ok()
+                    .and_then(|value| Self::variant_to_u32(&value))
+                    .and_then(Self::portal_value_prefers_dark);
+                callback(prefers_dark);
+            },
+        );
+    }
+
+    fn gnome_prefers_dark(interface_settings: Option<&GSettings>) -> Option<bool> {
+        let interface_settings = interface_settings?;
+        let has_color_scheme = interface_settings
+            .get_property_settings_schema()
+            .map(|schema| schema.has_key(COLOR_SCHEME_KEY))
+            .unwrap_or(false);
+        if has_color_scheme {
+            if let Some(color_scheme) = interface_settings.get_string(COLOR_SCHEME_KEY) {
+                match color_scheme.as_str() {
+                    "prefer-dark" => return Some(true),
+                    "prefer-light" => return Some(false),
+                    _ => {}
+                }
+            }
+        }
+        interface_settings
+            .get_string(GTK_THEME_KEY)
+            .map(|theme| Self::gtk_theme_is_dark(&theme))
+    }
+
+    /// The portal wraps the value in (possibly nested) variants and tuples.
+    fn variant_to_u32(variant: &Variant) -> Option<u32> {
+        if let Some(value) = variant.get::<u32>() {
+            return Some(value);
+        }
+        if let Some(inner) = variant.get::<Variant>() {
+            return Self::variant_to_u32(&inner);
+        }
+        if variant.is_container() && variant.n_children() > 0 {
+            return Self::variant_to_u32(&variant.get_child_value(0));
+        }
+        None
+    }
+
+    /// 0: no preference, 1: prefer dark, 2: prefer light
+    fn portal_value_prefers_dark(value: u32) -> Option<bool> {
+        match value {
+            1 => Some(true),
+            2 => Some(false),
+            _ => None,
+        }
+    }
+
+    fn gtk_theme_is_dark(theme: &str) -> bool {
+        let theme = theme.to_lowercase();
+        theme.ends_with("-dark") || theme.ends_with(":dark") || theme.contains("-dark-")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorScheme;
+
+    #[test]
+    fn color_scheme_preferences() {
+        assert_eq!(ColorScheme::portal_value_prefers_dark(0), None);
+        assert_eq!(ColorScheme::portal_value_prefers_dark(1), Some(true));
+        assert_eq!(ColorScheme::portal_value_prefers_dark(2), Some(false));
+        assert!(ColorScheme::gtk_theme_is_dark("Adwaita-dark"));
+        assert!(ColorScheme::gtk_theme_is_dark("Adwaita:dark"));
+        assert!(ColorScheme::gtk_theme_is_dark("Pop-dark-slim"));
+        assert!(!ColorScheme::gtk_theme_is_dark("Adwaita"));
+        assert!(!ColorScheme::gtk_theme_is_dark("Darkish"));
+    }
+}
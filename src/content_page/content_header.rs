@@ -1,14 +1,19 @@
 use super::header_selection::HeaderSelection;
+use crate::add_dialog::WARN_ICON;
 use crate::app::Action;
+use crate::article_list::{ArticleListGrouping, ArticleListSort};
 use crate::i18n::i18n;
 use crate::main_window_state::MainWindowState;
+use crate::search_query::{self, SearchQuery};
+use crate::settings::ArticleListViewSettings;
 use crate::tag_popover::TagPopover;
 use crate::util::{BuilderHelper, GtkUtil, Util};
-use gio::{ActionMapExt, Menu, MenuItem, SimpleAction};
-use glib::{clone, object::Cast, source::Continue, translate::ToGlib, Sender};
+use gio::{ActionExt, ActionMapExt, Menu, MenuItem, SimpleAction};
+use glib::{clone, object::Cast, source::Continue, translate::ToGlib, types::Type, Sender, ToVariant, VariantTy};
 use gtk::{
-    Button, ButtonExt, EntryExt, Inhibit, MenuButton, MenuButtonExt, Popover, PopoverExt, SearchEntry, SearchEntryExt,
-    Stack, StackExt, ToggleButton, ToggleButtonExt, Widget, WidgetExt,
+    prelude::GtkListStoreExtManual, Button, ButtonExt, EditableExt, EntryCompletion, EntryCompletionExt, EntryExt,
+    GtkListStoreExt, Inhibit, ListStore, MenuButton, MenuButtonExt, Popover, PopoverExt, SearchEntry, SearchEntryExt,
+    Stack, StackExt, StyleContextExt, ToggleButton, ToggleButtonExt, TreeModelExt, Widget, WidgetExt,
 };
 use libhandy::{SearchBar, SearchBarExt};
 use news_flash::models::{FatArticle, Marked, PluginCapabilities, Read};
@@ -26,6 +31,7 @@ pub struct ContentHeader {
     online_popover: Popover,
     search_button: ToggleButton,
     search_entry: SearchEntry,
+    search_completion: ListStore,
     mark_all_read_button: Button,
     mark_all_read_stack: Stack,
     all_button: ToggleButton,
@@ -34,6 +40,13 @@ pub struct ContentHeader {
     scrap_content_button: ToggleButton,
     scrap_content_stack: Stack,
     scrap_content_event: RwLock<Option<usize>>,
+    load_images_button: Button,
+    read_aloud_button: Button,
+    read_aloud_stack: Stack,
+    read_aloud_skip_button: Button,
+    feed_images_action: SimpleAction,
+    feed_links_action: SimpleAction,
+    feed_full_content_action: SimpleAction,
     tag_button: MenuButton,
     tag_popover: RwLock<Option<TagPopover>>,
     more_actions_button: MenuButton,
@@ -69,6 +82,7 @@ impl ContentHeader {
         let search_button = builder.get::<ToggleButton>("search_button");
         let search_bar = builder.get::<SearchBar>("search_bar");
         let search_entry = builder.get::<SearchEntry>("search_entry");
+        let save_search_button = builder.get::<Button>("save_search_button");
         let mode_button = builder.get::<MenuButton>("mode_switch_button");
         let mode_switch_stack = builder.get::<Stack>("mode_switch_stack");
         let mark_all_read_button = builder.get::<Button>("mark_all_button");
@@ -79,6 +93,10 @@ impl ContentHeader {
         let mark_article_read_stack = builder.get::<Stack>("mark_article_read_stack");
         let scrap_content_button = builder.get::<ToggleButton>("scrap_content_button");
         let scrap_content_stack = builder.get::<Stack>("scrap_article_stack");
+        let load_images_button = builder.get::<Button>("load_images_button");
+        let read_aloud_button = builder.get::<Button>("read_aloud_button");
+        let read_aloud_stack = builder.get::<Stack>("read_aloud_stack");
+        let read_aloud_skip_button = builder.get::<Button>("read_aloud_skip_button");
 
         mark_all_read_button.connect_clicked(clone!(
             @weak mark_all_read_stack,
@@ -93,6 +111,19 @@ impl ContentHeader {
             Util::send(&sender, Action::SetOfflineMode(false));
         }));
 
+        load_images_button.connect_clicked(clone!(@strong sender => @default-panic, move |button| {
+            button.set_visible(false);
+            Util::send(&sender, Action::LoadArticleImages);
+        }));
+
+        read_aloud_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::ReadAloud);
+        }));
+
+        read_aloud_skip_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::ReadAloudSkip);
+        }));
+
         let linked_button_timeout: Arc<RwLock<Option<u32>>> = Arc::new(RwLock::new(None));
         let header_selection = Arc::new(RwLock::new(HeaderSelection::All));
 
@@ -128,11 +159,12 @@ impl ContentHeader {
         Self::setup_update_button(&update_button, &sender);
         Self::setup_search_button(&search_button, &search_bar);
         Self::setup_search_bar(&search_bar, &search_button, &search_entry);
-        Self::setup_search_entry(&search_entry, &sender);
+        let search_completion = Self::setup_search_entry(&search_entry, &save_search_button, &sender);
 
         Self::setup_menu_button(&menu_button, &sender, features);
         Self::setup_mode_button(&mode_button, &sender);
-        Self::setup_more_actions_button(&more_actions_button, &sender);
+        let (feed_images_action, feed_links_action, feed_full_content_action) =
+            Self::setup_more_actions_button(&more_actions_button, &sender);
 
         let header = ContentHeader {
             sender,
@@ -144,6 +176,7 @@ impl ContentHeader {
             online_popover,
             search_button,
             search_entry,
+            search_completion,
             mark_all_read_button,
             mark_all_read_stack,
             all_button,
@@ -152,6 +185,13 @@ impl ContentHeader {
             scrap_content_button,
             scrap_content_stack,
             scrap_content_event: RwLock::new(None),
+            load_images_button,
+            read_aloud_button,
+            read_aloud_stack,
+            read_aloud_skip_button,
+            feed_images_action,
+            feed_links_action,
+            feed_full_content_action,
             tag_button,
             tag_popover,
             more_actions_button,
@@ -324,10 +364,97 @@ impl ContentHeader {
         );
     }
 
-    fn setup_search_entry(search_entry: &SearchEntry, sender: &Sender<Action>) {
-        search_entry.connect_search_changed(clone!(@strong sender => @default-panic, move |search_entry| {
-            Util::send(&sender, Action::SearchTerm(search_entry.get_text().as_str().into()));
+    fn setup_search_entry(search_entry: &SearchEntry, save_button: &Button, sender: &Sender<Action>) -> ListStore {
+        search_entry.set_tooltip_text(Some(&search_query::syntax_help()));
+        save_button.connect_clicked(clone!(@strong sender => @default-panic, move |_button| {
+            Util::send(&sender, Action::SaveSearchDialog);
         }));
+        search_entry.connect_search_changed(clone!(
+            @weak save_button,
+            @strong sender => @default-panic, move |search_entry|
+        {
+            let search_term = search_entry.get_text().as_str().to_owned();
+            let parsed = SearchQuery::parse(&search_term);
+            save_button.set_sensitive(parsed.is_ok() && !search_term.trim().is_empty());
+            match parsed {
+                Ok(_) => {
+                    search_entry.get_style_context().remove_class("error");
+                    let clear_icon = if search_term.is_empty() { None } else { Some("edit-clear-symbolic") };
+                    search_entry.set_property_secondary_icon_name(clear_icon);
+                    search_entry.set_property_secondary_icon_tooltip_text(None);
+                    Util::send(&sender, Action::SearchTerm(search_term));
+                }
+                Err(error) => {
+                    search_entry.get_style_context().add_class("error");
+                    search_entry.set_property_secondary_icon_name(Some(WARN_ICON));
+                    search_entry.set_property_secondary_icon_tooltip_text(Some(&error.message()));
+                }
+            }
+        }));
+
+        // complete "feed:", "category:" and "tag:" filters of the token that is currently being typed
+        let list_store = ListStore::new(&[Type::String]);
+        let completion = EntryCompletion::new();
+        completion.set_model(Some(&list_store));
+        completion.set_text_column(0);
+        completion.set_match_func(
+            clone!(@weak search_entry => @default-return false, move |completion, _key, iter| {
+                let text = search_entry.get_text().as_str().to_lowercase();
+                let token = text[SearchQuery::last_token_start(&text)..].trim_start_matches('-');
+                if !token.contains(':') {
+                    return false;
+                }
+                completion
+                    .get_model()
+                    .and_then(|model| model.get_value(iter, 0).get::<String>().ok().flatten())
+                    .map(|value| value.to_lowercase().starts_with(token) && value.len() > token.len())
+                    .unwrap_or(false)
+            }),
+        );
+        completion.connect_match_selected(
+            clone!(@weak search_entry => @default-return Inhibit(false), move |_completion, model, iter| {
+                let value = match model.get_value(iter, 0).get::<String>().ok().flatten() {
+                    Some(value) => value,
+                    None => return Inhibit(false),
+                };
+                let text = search_entry.get_text().as_str().to_owned();
+                let start = SearchQuery::last_token_start(&text);
+                let negation = if text[start..].starts_with('-') { "-" } else { "" };
+                search_entry.set_text(&format!("{}{}{} ", &text[..start], negation, value));
+                search_entry.set_position(-1);
+                Inhibit(true)
+            }),
+        );
+        search_entry.set_completion(Some(&completion));
+
+        list_store
+    }
+
+    pub fn update_search_completion(&self, news_flash: &Arc<RwLock<Option<NewsFlash>>>) {
+        if let Some(news_flash) = news_flash.read().as_ref() {
+            let mut completions = Vec::new();
+            if let Ok((feeds, _mappings)) = news_flash.get_feeds() {
+                completions.extend(feeds.iter().map(|feed| SearchQuery::completion("feed", &feed.label)));
+            }
+            if let Ok(categories) = news_flash.get_categories() {
+                completions.extend(
+                    categories
+                        .iter()
+                        .map(|category| SearchQuery::completion("category", &category.label)),
+                );
+            }
+            if let Ok(tags) = news_flash.get_tags() {
+                completions.extend(tags.iter().map(|tag| SearchQuery::completion("tag", &tag.label)));
+            }
+            completions.sort();
+            completions.dedup();
+
+            self.search_completion.clear();
+            for completion in completions {
+                let iter = self.search_completion.append();
+                self.search_completion.set(&iter, &[0], &[&completion]);
+            }
+        }
     }
 
     fn setup_menu_button(
@@ -374,6 +501,16 @@ impl ContentHeader {
             Util::send(&sender, Action::ExportOpml);
         }));
 
+        let catch_up_action = SimpleAction::new("catch-up", None);
+        catch_up_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, _parameter| {
+            Util::send(&sender, Action::ShowCatchUpDialog);
+        }));
+
+        let highlights_action = SimpleAction::new("highlights", None);
+        highlights_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, _parameter| {
+            Util::send(&sender, Action::ShowHighlightsDialog);
+        }));
+
         let relogin_action = SimpleAction::new("relogin", None);
         relogin_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, _parameter| {
             Util::send(&sender, Action::RetryLogin);
@@ -392,6 +529,8 @@ impl ContentHeader {
             main_window.add_action(&quit_action);
             main_window.add_action(&import_opml_action);
             main_window.add_action(&export_opml_action);
+            main_window.add_action(&catch_up_action);
+            main_window.add_action(&highlights_action);
             main_window.add_action(&relogin_action);
             main_window.add_action(&reset_account_action);
         }
@@ -415,6 +554,9 @@ impl ContentHeader {
         let main_model = Menu::new();
         main_model.append(Some(&i18n("Settings")), Some("win.settings"));
         main_model.append(Some(&i18n("Discover Feeds")), Some("win.discover"));
+        main_model.append(Some(&i18n("Catch Up…")), Some("win.catch-up"));
+        main_model.append(Some(&i18n("Highlights")), Some("win.highlights"));
+        main_model.append_section(Some(""), &Self::setup_article_list_view_menu(button, sender));
         main_model.append_section(Some(""), &account_model);
         main_model.append_section(Some(""), &im_export_model);
         main_model.append_section(Some(""), &about_model);
@@ -422,6 +564,80 @@ impl ContentHeader {
         button.set_menu_model(Some(&main_model));
     }
 
+    fn setup_article_list_view_menu(button: &MenuButton, sender: &Sender<Action>) -> Menu {
+        let string_type = VariantTy::new("s").expect("Invalid variant type");
+
+        let sort_action = SimpleAction::new_stateful(
+            "article-list-sort",
+            Some(string_type),
+            &ArticleListSort::default().to_str().to_variant(),
+        );
+        sort_action.connect_activate(clone!(@strong sender => @default-panic, move |action, parameter| {
+            if let Some(parameter) = parameter {
+                if let Some(sort) = parameter.get_str().and_then(ArticleListSort::parse) {
+                    action.set_state(parameter);
+                    Util::send(&sender, Action::SetArticleListSort(sort));
+                }
+            }
+        }));
+
+        let grouping_action = SimpleAction::new_stateful(
+            "article-list-grouping",
+            Some(string_type),
+            &ArticleListGrouping::default().to_str().to_variant(),
+        );
+        grouping_action.connect_activate(clone!(@strong sender => @default-panic, move |action, parameter| {
+            if let Some(parameter) = parameter {
+                if let Some(grouping) = parameter.get_str().and_then(ArticleListGrouping::parse) {
+                    action.set_state(parameter);
+                    Util::send(&sender, Action::SetArticleListGrouping(grouping));
+                }
+            }
+        }));
+
+        if let Ok(main_window) = GtkUtil::get_main_window(button) {
+            main_window.add_action(&sort_action);
+            main_window.add_action(&grouping_action);
+        }
+
+        let sort_model = Menu::new();
+        for sort in ArticleListSort::all().iter() {
+            let item = MenuItem::new(Some(&sort.name()), None);
+            item.set_action_and_target_value(Some("win.article-list-sort"), Some(&sort.to_str().to_variant()));
+            sort_model.append_item(&item);
+        }
+
+        let grouping_model = Menu::new();
+        for grouping in ArticleListGrouping::all().iter() {
+            let item = MenuItem::new(Some(&grouping.name()), None);
+            item.set_action_and_target_value(Some("win.article-list-grouping"), Some(&grouping.to_str().to_variant()));
+            grouping_model.append_item(&item);
+        }
+
+        let view_model = Menu::new();
+        view_model.append_submenu(Some(&i18n("Sort Articles")), &sort_model);
+        view_model.append_submenu(Some(&i18n("Group Articles")), &grouping_model);
+        view_model
+    }
+
+    /// Reflect the sort order and grouping of the currently selected view in the menu.
+    pub fn show_article_list_view(&self, view_settings: &ArticleListViewSettings) {
+        if let Ok(main_window) = GtkUtil::get_main_window(&self.offline_button) {
+            if let Some(sort_action) = main_window.lookup_action("article-list-sort") {
+                sort_action
+                    .downcast::<SimpleAction>()
+                    .expect("downcast Action to SimpleAction")
+                    .set_state(&view_settings.sort.to_str().to_variant());
+            }
+            if let Some(grouping_action) = main_window.lookup_action("article-list-grouping") {
+                grouping_action
+                    .downcast::<SimpleAction>()
+                    .expect("downcast Action to SimpleAction")
+                    .set_state(&view_settings.grouping.to_str().to_variant());
+            }
+        }
+    }
+
     fn setup_mode_button(button: &MenuButton, sender: &Sender<Action>) {
         let model = Menu::new();
 
@@ -464,7 +680,10 @@ impl ContentHeader {
         button.set_menu_model(Some(&model));
     }
 
-    fn setup_more_actions_button(button: &MenuButton, sender: &Sender<Action>) {
+    fn setup_more_actions_button(
+        button: &MenuButton,
+        sender: &Sender<Action>,
+    ) -> (SimpleAction, SimpleAction, SimpleAction) {
         let close_article_action = SimpleAction::new("close-article", None);
         close_article_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, _parameter| {
             Util::send(&sender, Action::CloseArticle);
@@ -480,18 +699,158 @@ impl ContentHeader {
             Util::send(&sender, Action::OpenSelectedArticle);
         }));
 
+        let feed_images_action = SimpleAction::new_stateful(
+            "article-feed-images",
+            Some(VariantTy::new("s").expect("Invalid variant type")),
+            &Self::feed_images_to_str(None).to_variant(),
+        );
+        feed_images_action.connect_activate(clone!(@strong sender => @default-panic, move |action, parameter| {
+            if let Some(parameter) = parameter {
+                if let Some(load_images) = parameter.get_str().and_then(Self::feed_images_parse) {
+                    action.set_state(parameter);
+                    Util::send(&sender, Action::SetArticleFeedImages(load_images));
+                }
+            }
+        }));
+
+        let feed_links_action = SimpleAction::new_stateful(
+            "article-feed-links",
+            Some(VariantTy::new("s").expect("Invalid variant type")),
+            &Self::feed_links_to_str(None).to_variant(),
+        );
+        feed_links_action.connect_activate(clone!(@strong sender => @default-panic, move |action, parameter| {
+            if let Some(parameter) = parameter {
+                if let Some(internal) = parameter.get_str().and_then(Self::feed_links_parse) {
+                    action.set_state(parameter);
+                    Util::send(&sender, Action::SetArticleFeedLinks(internal));
+                }
+            }
+        }));
+
+        let feed_full_content_action =
+            SimpleAction::new_stateful("article-feed-full-content", None, &false.to_variant());
+        feed_full_content_action.connect_activate(clone!(@strong sender => @default-panic, move |action, _parameter| {
+            let full_content = !action
+                .get_state()
+                .and_then(|state| state.get::<bool>())
+                .unwrap_or(false);
+            action.set_state(&full_content.to_variant());
+            Util::send(&sender, Action::SetArticleFeedFullContent(full_content));
+        }));
+
         if let Ok(main_window) = GtkUtil::get_main_window(button) {
             main_window.add_action(&close_article_action);
             main_window.add_action(&open_article_action);
             main_window.add_action(&export_article_action);
+            main_window.add_action(&feed_images_action);
+            main_window.add_action(&feed_links_action);
+            main_window.add_action(&feed_full_content_action);
+        }
+
+        let feed_images_model = Menu::new();
+        for (label, load_images) in &[
+            (i18n("Use Data Saver Setting"), None),
+            (i18n("Always Load"), Some(true)),
+            (i18n("Never Load"), Some(false)),
+        ] {
+            let item = MenuItem::new(Some(label), None);
+            item.set_action_and_target_value(
+                Some("win.article-feed-images"),
+                Some(&Self::feed_images_to_str(*load_images).to_variant()),
+            );
+            feed_images_model.append_item(&item);
+        }
+
+        let feed_links_model = Menu::new();
+        for (label, internal) in &[
+            (i18n("Use General Setting"), None),
+            (i18n("Open in NewsFlash"), Some(true)),
+            (i18n("Open in Browser"), Some(false)),
+        ] {
+            let item = MenuItem::new(Some(label), None);
+            item.set_action_and_target_value(
+                Some("win.article-feed-links"),
+                Some(&Self::feed_links_to_str(*internal).to_variant()),
+            );
+            feed_links_model.append_item(&item);
         }
 
         let model = Menu::new();
         model.append(Some(&i18n("Export Article")), Some("win.export-article"));
         model.append(Some(&i18n("Open in browser")), Some("win.open-article-in-browser"));
+        model.append_submenu(Some(&i18n("Images of this Feed")), &feed_images_model);
+        model.append_submenu(Some(&i18n("Links of this Feed")), &feed_links_model);
+        model.append(
+            Some(&i18n("Always Show Full Content of this Feed")),
+            Some("win.article-feed-full-content"),
+        );
         model.append(Some(&i18n("Close Article")), Some("win.close-article"));
         button.set_menu_model(Some(&model));
         button.set_sensitive(false);
+        (feed_images_action, feed_links_action, feed_full_content_action)
+    }
+
+    fn feed_images_to_str(load_images: Option<bool>) -> &'static str {
+        match load_images {
+            None => "default",
+            Some(true) => "always",
+            Some(false) => "never",
+        }
+    }
+
+    fn feed_images_parse(value: &str) -> Option<Option<bool>> {
+        match value {
+            "default" => Some(None),
+            "always" => Some(Some(true)),
+            "never" => Some(Some(false)),
+            _ => None,
+        }
+    }
+
+    fn feed_links_to_str(internal: Option<bool>) -> &'static str {
+        match internal {
+            None => "default",
+            Some(true) => "internal",
+            Some(false) => "browser",
+        }
+    }
+
+    fn feed_links_parse(value: &str) -> Option<Option<bool>> {
+        match value {
+            "default" => Some(None),
+            "internal" => Some(Some(true)),
+            "browser" => Some(Some(false)),
+            _ => None,
+        }
+    }
+
+    /// Reflect whether the visible article's feed always shows full content in the menu.
+    pub fn set_feed_full_content(&self, full_content: bool) {
+        self.feed_full_content_action.set_state(&full_content.to_variant());
+    }
+
+    /// Reflect the link setting of the visible article's feed in the menu.
+    pub fn set_feed_internal_links(&self, internal: Option<bool>) {
+        self.feed_links_action
+            .set_state(&Self::feed_links_to_str(internal).to_variant());
+    }
+
+    /// Reflect the image setting of the visible article's feed in the menu.
+    pub fn set_feed_images(&self, load_images: Option<bool>) {
+        self.feed_images_action
+            .set_state(&Self::feed_images_to_str(load_images).to_variant());
+    }
+
+    /// Offer loading the images of an article that shows placeholders.
+    pub fn set_images_blocked(&self, blocked: bool) {
+        self.load_images_button.set_visible(blocked);
+    }
+
+    /// Switch the read aloud button between play and pause.
+    pub fn set_read_aloud_playing(&self, playing: bool) {
+        self.read_aloud_stack
+            .set_visible_child_name(if playing { "pause" } else { "play" });
+        self.read_aloud_skip_button.set_sensitive(playing);
     }
 
     fn unread_button_state(article: Option<&FatArticle>) -> (&str, bool) {
@@ -592,6 +951,11 @@ impl ContentHeader {
         );
 
         self.more_actions_button.set_sensitive(sensitive);
+        self.read_aloud_button.set_sensitive(sensitive);
+        if !sensitive {
+            self.load_images_button.set_visible(false);
+            self.set_read_aloud_playing(false);
+        }
 
         if !self.state.read().get_offline() {
             let mut tag_support = false;
@@ -23,6 +23,8 @@ pub enum ContentPageErrorKind {
     SidebarModels,
     #[fail(display = "Error computing sidebar selection")]
     SidebarSelection,
+    #[fail(display = "Invalid search query")]
+    SearchQuery,
     #[fail(display = "Unknown Error")]
     Unknown,
 }
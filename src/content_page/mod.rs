@@ -7,30 +7,78 @@ pub use self::header_selection::HeaderSelection;
 
 use self::error::{ContentPageError, ContentPageErrorKind};
 use crate::app::Action;
-use crate::article_list::{ArticleList, ArticleListModel};
+use crate::article_list::{ArticleList, ArticleListGrouping, ArticleListModel, ArticleListSort};
 use crate::article_view::ArticleView;
 use crate::main_window_state::MainWindowState;
-use crate::settings::Settings;
+use crate::search_query::{SearchCandidate, SearchQuery, SearchTerm};
+use crate::settings::{SavedSearch, Settings};
 use crate::sidebar::models::SidebarSelection;
 use crate::sidebar::{FeedListTree, SideBar, TagListModel};
 use crate::undo_bar::{UndoActionModel, UndoBar};
 use crate::util::{BuilderHelper, Util, CHANNEL_ERROR};
+use chrono::NaiveDateTime;
 use failure::ResultExt;
 use futures::channel::oneshot;
 use futures::executor::ThreadPool;
 use futures_util::future::FutureExt;
 use glib::{clone, Sender};
 use gtk::{Box, BoxExt, WidgetExt};
+use lazy_static::lazy_static;
 use libhandy::Leaflet;
 use log::warn;
 use news_flash::models::{
-    Article, ArticleFilter, Category, CategoryType, Marked, PluginCapabilities, PluginID, Read, NEWSFLASH_TOPLEVEL,
+    Article, ArticleFilter, ArticleID, ArticleOrder, Category, CategoryID, CategoryType, FeedID, Marked,
+    PluginCapabilities, PluginID, Read, Tag, NEWSFLASH_TOPLEVEL,
 };
-use news_flash::NewsFlash;
+use news_flash::{NewsFlash, NewsFlashError};
 use parking_lot::RwLock;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+static PAGE_SIZE: i64 = 200;
+
+lazy_static! {
+    static ref ENCLOSURE_ARTICLES: RwLock<EnclosureArticles> = RwLock::new(EnclosureArticles::default());
+    static ref POST_FILTER_POSITION: RwLock<Option<PostFilterPosition>> = RwLock::new(None);
+}
+
+/// The database doesn't know which articles have enclosures,
+/// so every article is only looked at once and the list is brought up to date after syncs.
+#[derive(Default)]
+struct EnclosureArticles {
+    checked: HashSet<ArticleID>,
+    with_enclosures: Vec<ArticleID>,
+    up_to_date: bool,
+}
+
+/// Where the last page of a search that is filtered after reading the database ended,
+/// so the next page continues from there instead of filtering all articles before it again.
+struct PostFilterPosition {
+    filter: ArticleFilter,
+    search_query: SearchQuery,
+    matches: i64,
+    database_offset: i64,
+}
+
+impl PostFilterPosition {
+    fn continues(&self, filter: &ArticleFilter, search_query: &SearchQuery, offset: i64) -> bool {
+        self.matches == offset
+            && self.search_query == *search_query
+            && self.filter.order == filter.order
+            && self.filter.unread == filter.unread
+            && self.filter.marked == filter.marked
+            && self.filter.feed == filter.feed
+            && self.filter.feed_blacklist == filter.feed_blacklist
+            && self.filter.category == filter.category
+            && self.filter.category_blacklist == filter.category_blacklist
+            && self.filter.tag == filter.tag
+            && self.filter.ids == filter.ids
+            && self.filter.newer_than == filter.newer_than
+            && self.filter.older_than == filter.older_than
+            && self.filter.search_term == filter.search_term
+    }
+}
+
 pub struct ContentPage {
     pub sidebar: Arc<RwLock<SideBar>>,
     pub article_list: Arc<RwLock<ArticleList>>,
@@ -86,7 +134,11 @@ impl ContentPage {
         self.article_view.close_article();
         self.state.write().set_prefer_scraped_content(false);
 
-        let list_model = ArticleListModel::new(&self.settings.read().get_article_list_order());
+        let list_model = ArticleListModel::new(
+            &self.settings.read().get_article_list_order(),
+            ArticleListSort::default(),
+            ArticleListGrouping::default(),
+        );
         self.article_list.write().update(list_model, &self.state);
 
         let feed_tree_model = FeedListTree::new();
@@ -132,27 +184,38 @@ impl ContentPage {
 
         let news_flash = news_flash.clone();
         let window_state = self.state.clone();
-        let current_undo_action = undo_bar.get_current_action();
+        let pending_undo_actions = undo_bar.get_pending_actions();
         let processing_undo_actions = undo_bar.processing_actions();
         let settings = self.settings.clone();
         let thread_future = async move {
             if let Some(news_flash) = news_flash.read().as_ref() {
-                let limit = if window_state.write().reset_article_list() {
-                    MainWindowState::page_size()
-                } else if relevant_articles_loaded as i64 >= MainWindowState::page_size() {
-                    relevant_articles_loaded as i64
+                let page_size = settings.read().get_article_list_page_size();
+                let view_settings = settings
+                    .read()
+                    .get_article_list_view(window_state.read().get_sidebar_selection());
+                let reset = window_state.write().reset_article_list();
+                let limit = if !view_settings.paged() {
+                    None
+                } else if reset || (relevant_articles_loaded as i64) < page_size {
+                    Some(page_size)
                 } else {
-                    MainWindowState::page_size()
+                    Some(relevant_articles_loaded as i64)
                 };
-                let mut list_model = ArticleListModel::new(&settings.read().get_article_list_order());
+                let mut list_model = ArticleListModel::new(
+                    &settings.read().get_article_list_order(),
+                    view_settings.sort,
+                    view_settings.grouping,
+                );
                 let mut articles = match Self::load_articles(
                     news_flash,
                     &window_state,
                     &settings,
-                    &current_undo_action,
+                    &pending_undo_actions,
                     &processing_undo_actions,
                     limit,
                     None,
+                    None,
+                    None,
                 ) {
                     Ok(articles) => articles,
                     Err(error) => {
@@ -208,6 +271,15 @@ impl ContentPage {
         undo_bar: &UndoBar,
         threadpool: ThreadPool,
     ) {
+        let view_settings = self
+            .settings
+            .read()
+            .get_article_list_view(window_state.read().get_sidebar_selection());
+        // lists that aren't paged are loaded completely already
+        if !view_settings.paged() {
+            return;
+        }
+
         let (sender, receiver) = oneshot::channel::<Result<ArticleListModel, ContentPageErrorKind>>();
 
         let relevant_articles_loaded = self
@@ -215,23 +287,29 @@ impl ContentPage {
             .read()
             .get_relevant_article_count(window_state.read().get_header_selection());
 
-        let current_undo_action = undo_bar.get_current_action();
+        let pending_undo_actions = undo_bar.get_pending_actions();
         let processing_undo_actions = undo_bar.processing_actions();
         let settings = self.settings.clone();
         let news_flash = news_flash_handle.clone();
         let window_state = window_state.clone();
         let thread_future = async move {
-            let mut list_model = ArticleListModel::new(&settings.read().get_article_list_order());
+            let mut list_model = ArticleListModel::new(
+                &settings.read().get_article_list_order(),
+                view_settings.sort,
+                view_settings.grouping,
+            );
 
             if let Some(news_flash) = news_flash.read().as_ref() {
                 let mut articles = match Self::load_articles(
                     news_flash,
                     &window_state,
                     &settings,
-                    &current_undo_action,
+                    &pending_undo_actions,
                     &processing_undo_actions,
-                    MainWindowState::page_size(),
+                    Some(settings.read().get_article_list_page_size()),
                     Some(relevant_articles_loaded as i64),
+                    None,
+                    None,
                 ) {
                     Ok(articles) => articles,
                     Err(error) => {
@@ -278,15 +356,167 @@ impl ContentPage {
         Util::glib_spawn_future(glib_future);
     }
 
+    /// Find the unread articles of the current list above or below `article_id` and mark them as read.
+    pub fn mark_articles_beyond_read(
+        &self,
+        article_id: ArticleID,
+        above: bool,
+        news_flash: &Arc<RwLock<Option<NewsFlash>>>,
+        undo_bar: &UndoBar,
+        threadpool: ThreadPool,
+    ) {
+        let sender = self.sender.clone();
+        self.unread_articles_beyond(
+            article_id,
+            above,
+            news_flash,
+            undo_bar,
+            threadpool,
+            move |article_ids| {
+                Util::send(&sender, Action::SetListArticlesRead(article_ids, Read::Read));
+            },
+        );
+    }
+
+    /// Find the first unread article of the current list below `article_id` to keep reading aloud with.
+    pub fn continue_read_aloud(
+        &self,
+        article_id: ArticleID,
+        news_flash: &Arc<RwLock<Option<NewsFlash>>>,
+        undo_bar: &UndoBar,
+        threadpool: ThreadPool,
+    ) {
+        let sender = self.sender.clone();
+        let finished = article_id.clone();
+        self.unread_articles_beyond(
+            article_id,
+            false,
+            news_flash,
+            undo_bar,
+            threadpool,
+            move |article_ids| {
+                if let Some(next) = article_ids.into_iter().next() {
+                    Util::send(&sender, Action::ReadAloudContinue(finished, next));
+                }
+            },
+        );
+    }
+
+    /// Lists sorted by date are only loaded page by page, so instead of the loaded rows the database is asked for
+    /// all articles of the list that are newer or older than the article. All other lists are loaded completely.
+    fn unread_articles_beyond<F: FnOnce(Vec<ArticleID>) + 'static>(
+        &self,
+        article_id: ArticleID,
+        above: bool,
+        news_flash: &Arc<RwLock<Option<NewsFlash>>>,
+        undo_bar: &UndoBar,
+        threadpool: ThreadPool,
+        callback: F,
+    ) {
+        let view_settings = self
+            .settings
+            .read()
+            .get_article_list_view(self.state.read().get_sidebar_selection());
+        if !view_settings.paged() {
+            let article_ids = if above {
+                self.article_list.read().unread_above(&article_id)
+            } else {
+                self.article_list.read().unread_below(&article_id)
+            };
+            callback(article_ids);
+            return;
+        }
+
+        let (sender, receiver) = oneshot::channel::<Result<Vec<ArticleID>, ContentPageErrorKind>>();
+
+        let news_flash = news_flash.clone();
+        let window_state = self.state.clone();
+        let pending_undo_actions = undo_bar.get_pending_actions();
+        let processing_undo_actions = undo_bar.processing_actions();
+        let settings = self.settings.clone();
+        let thread_future = async move {
+            if let Some(news_flash) = news_flash.read().as_ref() {
+                let date = match news_flash.get_article(&article_id) {
+                    Ok(article) => article.date,
+                    Err(_error) => {
+                        sender.send(Err(ContentPageErrorKind::DataBase)).expect(CHANNEL_ERROR);
+                        return;
+                    }
+                };
+                let newer = match settings.read().get_article_list_order() {
+                    ArticleOrder::NewestFirst => above,
+                    ArticleOrder::OldestFirst => !above,
+                };
+                let (newer_than, older_than) = if newer { (Some(date), None) } else { (None, Some(date)) };
+                let result = Self::load_articles(
+                    news_flash,
+                    &window_state,
+                    &settings,
+                    &pending_undo_actions,
+                    &processing_undo_actions,
+                    None,
+                    None,
+                    newer_than,
+                    older_than,
+                )
+                .map(|articles| {
+                    articles
+                        .into_iter()
+                        .filter(|article| article.unread == Read::Unread)
+                        .map(|article| article.article_id)
+                        .collect()
+                })
+                .map_err(|error| error.kind());
+                sender.send(result).expect(CHANNEL_ERROR);
+            }
+        };
+
+        let glib_future = receiver.map(move |res| match res {
+            Ok(Ok(article_ids)) => callback(article_ids),
+            Ok(Err(error)) => warn!("Failed to load the unread articles of the list: {}", error),
+            Err(error) => warn!("Sender error: {}", error),
+        });
+
+        threadpool.spawn_ok(thread_future);
+        Util::glib_spawn_future(glib_future);
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn load_articles(
         news_flash: &NewsFlash,
         window_state: &RwLock<MainWindowState>,
         settings: &Arc<RwLock<Settings>>,
-        current_undo_action: &Option<UndoActionModel>,
+        pending_undo_actions: &[UndoActionModel],
         processing_undo_actions: &Arc<RwLock<HashSet<UndoActionModel>>>,
-        limit: i64,
+        limit: Option<i64>,
         offset: Option<i64>,
+        newer_than: Option<NaiveDateTime>,
+        older_than: Option<NaiveDateTime>,
     ) -> Result<Vec<Article>, ContentPageError> {
+        let search_query = match window_state.read().get_search_term() {
+            Some(search_term) => Some(SearchQuery::parse(search_term).map_err(|_| ContentPageErrorKind::SearchQuery)?),
+            None => None,
+        };
+        let search_query = match window_state.read().get_sidebar_selection() {
+            SidebarSelection::SavedSearch(id, _title) => {
+                let saved_query = settings
+                    .read()
+                    .get_saved_search(*id)
+                    .map(|search| search.search_query());
+                match (search_query, saved_query) {
+                    (Some(mut query), Some(Ok(Some(saved_query)))) => {
+                        if !query.combine(saved_query) {
+                            return Ok(Vec::new());
+                        }
+                        Some(query)
+                    }
+                    (None, Some(Ok(Some(saved_query)))) => Some(saved_query),
+                    // deleted, invalid or self-contradicting searches don't match anything
+                    _ => return Ok(Vec::new()),
+                }
+            }
+            _ => search_query,
+        };
         let unread = match window_state.read().get_header_selection() {
             HeaderSelection::All | HeaderSelection::Marked => None,
             HeaderSelection::Unread => Some(Read::Unread),
@@ -296,24 +526,24 @@ impl ContentPage {
             HeaderSelection::Marked => Some(Marked::Marked),
         };
         let feed = match &window_state.read().get_sidebar_selection() {
-            SidebarSelection::All | SidebarSelection::Category(_, _) | SidebarSelection::Tag(_, _) => None,
             SidebarSelection::Feed(id, _parent_id, _title) => Some(id.clone()),
+            _ => None,
         };
         let category = match &window_state.read().get_sidebar_selection() {
-            SidebarSelection::All | SidebarSelection::Feed(_, _, _) | SidebarSelection::Tag(_, _) => None,
             SidebarSelection::Category(id, _title) => Some(id.clone()),
+            _ => None,
         };
         let tag = match &window_state.read().get_sidebar_selection() {
-            SidebarSelection::All | SidebarSelection::Feed(_, _, _) | SidebarSelection::Category(_, _) => None,
             SidebarSelection::Tag(id, _title) => Some(id.clone()),
+            _ => None,
         };
-        let search_term = window_state.read().get_search_term().clone();
-        let (feed_blacklist, category_blacklist) = {
+        let (feed_blacklist, category_blacklist, pending_read) = {
             let mut undo_actions = Vec::new();
             let mut feed_blacklist = Vec::new();
             let mut category_blacklist = Vec::new();
-            if let Some(current_undo_action) = current_undo_action {
-                undo_actions.push(current_undo_action);
+            let mut pending_read = HashSet::new();
+            for pending_undo_action in pending_undo_actions {
+                undo_actions.push(pending_undo_action);
             }
             let processing_undo_actions = &*processing_undo_actions.read();
             for processing_undo_action in processing_undo_actions {
@@ -325,7 +555,8 @@ impl ContentPage {
                     UndoActionModel::DeleteCategory(category_id, _label) => {
                         category_blacklist.push(category_id.clone())
                     }
-                    UndoActionModel::DeleteTag(_tag_id, _label) => {}
+                    UndoActionModel::MarkReadOnScroll(article_ids) => pending_read.extend(article_ids.iter().cloned()),
+                    _ => {}
                 }
             }
 
@@ -340,12 +571,33 @@ impl ContentPage {
                 Some(category_blacklist)
             };
 
-            (feed_blacklist, category_blacklist)
+            (feed_blacklist, category_blacklist, pending_read)
         };
 
-        let articles = news_flash
-            .get_articles(ArticleFilter {
-                limit: Some(limit),
+        let ids = if *window_state.read().get_sidebar_selection() == SidebarSelection::Podcasts {
+            let ids = Self::enclosure_article_ids(news_flash).context(ContentPageErrorKind::DataBase)?;
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            Some(ids)
+        } else {
+            None
+        };
+        // the database only knows the date order, lists sorted any other way have to be loaded
+        // completely so the first page really holds the first articles of the list
+        let (limit, offset) = if settings
+            .read()
+            .get_article_list_view(window_state.read().get_sidebar_selection())
+            .paged()
+        {
+            (limit, offset)
+        } else {
+            (None, None)
+        };
+        let mut articles = Self::query_articles(
+            news_flash,
+            ArticleFilter {
+                limit,
                 offset,
                 order: Some(settings.read().get_article_list_order()),
                 unread,
@@ -355,14 +607,322 @@ impl ContentPage {
                 category,
                 category_blacklist,
                 tag,
+                ids,
+                newer_than,
+                older_than,
+                search_term: None,
+            },
+            search_query.as_ref(),
+        )
+        .context(ContentPageErrorKind::DataBase)?;
+
+        // articles waiting for the undo bar to time out are already shown as read
+        for article in &mut articles {
+            if pending_read.contains(&article.article_id) {
+                article.unread = Read::Read;
+            }
+        }
+
+        Ok(articles)
+    }
+
+    /// Fetch the articles of `filter` narrowed down by `search_query`.
+    /// A search that contradicts the unread or marked state of the filter doesn't match anything.
+    pub fn query_articles(
+        news_flash: &NewsFlash,
+        filter: ArticleFilter,
+        search_query: Option<&SearchQuery>,
+    ) -> Result<Vec<Article>, NewsFlashError> {
+        let search_query = match search_query {
+            Some(search_query) => search_query,
+            None => return news_flash.get_articles(filter),
+        };
+        let mut filter = match Self::combine_filter(filter, search_query) {
+            Some(filter) => filter,
+            None => return Ok(Vec::new()),
+        };
+        if !search_query.needs_post_filter() {
+            return news_flash.get_articles(filter);
+        }
+
+        // the paging has to happen after filtering
+        let offset = filter.offset.take().unwrap_or(0);
+        let limit = filter.limit.take().map(|limit| limit as usize).unwrap_or(usize::MAX);
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let (skip, start) = match POST_FILTER_POSITION.read().as_ref() {
+            Some(position) if offset > 0 && position.continues(&filter, search_query, offset) => {
+                (0, position.database_offset)
+            }
+            _ => (offset, 0),
+        };
+        let position_filter = Self::page_filter(&filter, 0, 0);
+        let mut skipped = 0;
+        let mut articles = Vec::new();
+        let database_offset = Self::visit_matches(news_flash, filter, search_query, start, |article| {
+            if skipped < skip {
+                skipped += 1;
+            } else {
+                articles.push(article);
+            }
+            articles.len() < limit
+        })?;
+        POST_FILTER_POSITION.write().replace(PostFilterPosition {
+            filter: position_filter,
+            search_query: search_query.clone(),
+            matches: offset + articles.len() as i64,
+            database_offset,
+        });
+        Ok(articles)
+    }
+
+    /// Number of articles `query_articles` would return without limit and offset.
+    /// The articles are counted page by page instead of loading all of them at once.
+    pub fn count_articles(
+        news_flash: &NewsFlash,
+        filter: ArticleFilter,
+        search_query: Option<&SearchQuery>,
+    ) -> Result<i64, NewsFlashError> {
+        let filter = match search_query {
+            Some(search_query) => match Self::combine_filter(filter, search_query) {
+                Some(filter) => filter,
+                None => return Ok(0),
+            },
+            None => filter,
+        };
+
+        let mut count = 0;
+        match search_query {
+            Some(search_query) if search_query.needs_post_filter() => {
+                Self::visit_matches(news_flash, filter, search_query, 0, |_article| {
+                    count += 1;
+                    true
+                })?;
+            }
+            _ => {
+                let mut page_offset = 0;
+                loop {
+                    let page = news_flash.get_articles(Self::page_filter(&filter, PAGE_SIZE, page_offset))?;
+                    count += page.len() as i64;
+                    if (page.len() as i64) < PAGE_SIZE {
+                        break;
+                    }
+                    page_offset += PAGE_SIZE;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Move everything of `search_query` the database understands into `filter`.
+    /// `None` if the two contradict each other, e.g. "is:read" while only showing unread articles.
+    fn combine_filter(mut filter: ArticleFilter, search_query: &SearchQuery) -> Option<ArticleFilter> {
+        filter.unread = match (filter.unread, search_query.unread) {
+            (Some(filter), Some(query)) if filter != query => return None,
+            (filter, query) => filter.or(query),
+        };
+        filter.marked = match (filter.marked, search_query.marked) {
+            (Some(filter), Some(query)) if filter != query => return None,
+            (filter, query) => filter.or(query),
+        };
+        filter.search_term = search_query.full_text_term().or(filter.search_term);
+        filter.newer_than = match (filter.newer_than, search_query.newer_than()) {
+            (Some(filter), Some(query)) => Some(filter.max(query)),
+            (filter, query) => filter.or(query),
+        };
+        filter.older_than = match (filter.older_than, search_query.older_than()) {
+            (Some(filter), Some(query)) => Some(filter.min(query)),
+            (filter, query) => filter.or(query),
+        };
+        Some(filter)
+    }
+
+    /// Hand every article of `filter` from `start` on that passes the post-filter of `search_query` to `visit` in order,
+    /// until `visit` returns `false`. The database is read page by page, so only what is needed gets loaded.
+    /// Returns the database offset after the last article handed to `visit`.
+    fn visit_matches<F: FnMut(Article) -> bool>(
+        news_flash: &NewsFlash,
+        mut filter: ArticleFilter,
+        search_query: &SearchQuery,
+        start: i64,
+        mut visit: F,
+    ) -> Result<i64, NewsFlashError> {
+        let (feeds, mappings) = news_flash.get_feeds()?;
+        let categories = news_flash.get_categories()?;
+        let tags = news_flash.get_tags()?;
+
+        // narrow the query down as far as the database can, the rest is filtered afterwards
+        let matching_feeds = |term: &SearchTerm| -> Vec<FeedID> {
+            feeds
+                .iter()
+                .filter(|feed| term.matches_value(&feed.label))
+                .map(|feed| feed.feed_id.clone())
+                .collect()
+        };
+        for term in &search_query.feed {
+            let feed_ids = matching_feeds(term);
+            if term.negated {
+                filter.feed_blacklist.get_or_insert_with(Vec::new).extend(feed_ids);
+            } else if feed_ids.is_empty() {
+                return Ok(start);
+            } else if feed_ids.len() == 1 && filter.feed.is_none() {
+                filter.feed = feed_ids.into_iter().next();
+            }
+        }
+        // only tags matching one of the tag terms make a difference
+        let tags: Vec<Tag> = tags
+            .into_iter()
+            .filter(|tag| search_query.tag.iter().any(|term| term.matches_value(&tag.label)))
+            .collect();
+        for term in search_query.tag.iter().filter(|term| !term.negated) {
+            let mut matching = tags.iter().filter(|tag| term.matches_value(&tag.label));
+            match (matching.next(), matching.next()) {
+                (None, _) => return Ok(start),
+                (Some(tag), None) if filter.tag.is_none() => filter.tag = Some(tag.tag_id.clone()),
+                _ => {}
+            }
+        }
+
+        let feed_labels: HashMap<FeedID, String> = feeds.into_iter().map(|feed| (feed.feed_id, feed.label)).collect();
+        let category_labels: HashMap<CategoryID, String> = categories
+            .into_iter()
+            .map(|category| (category.category_id, category.label))
+            .collect();
+        let mut feed_categories: HashMap<FeedID, Vec<&str>> = HashMap::new();
+        for mapping in &mappings {
+            if let Some(label) = category_labels.get(&mapping.category_id) {
+                feed_categories.entry(mapping.feed_id.clone()).or_default().push(label);
+            }
+        }
+
+        let mut page_offset = start;
+        loop {
+            let page = news_flash.get_articles(Self::page_filter(&filter, PAGE_SIZE, page_offset))?;
+            let last_page = (page.len() as i64) < PAGE_SIZE;
+
+            let article_tags = Self::article_tags(news_flash, &tags, &page)?;
+            for article in page {
+                page_offset += 1;
+                let candidate = SearchCandidate {
+                    title: article.title.as_deref(),
+                    author: article.author.as_deref(),
+                    summary: article.summary.as_deref(),
+                    feed: feed_labels.get(&article.feed_id).map(|label| label.as_str()),
+                    categories: feed_categories.get(&article.feed_id).cloned().unwrap_or_default(),
+                    tags: article_tags
+                        .get(&article.article_id)
+                        .map(|tags| tags.iter().map(|tag| tag.as_str()).collect())
+                        .unwrap_or_default(),
+                };
+                if search_query.matches(&candidate) && !visit(article) {
+                    return Ok(page_offset);
+                }
+            }
+
+            if last_page {
+                return Ok(page_offset);
+            }
+        }
+    }
+
+    /// Ids of all articles that come with enclosures, e.g. podcast episodes.
+    pub fn enclosure_article_ids(news_flash: &NewsFlash) -> Result<Vec<ArticleID>, NewsFlashError> {
+        let mut enclosure_articles = ENCLOSURE_ARTICLES.write();
+        if !enclosure_articles.up_to_date {
+            let filter = ArticleFilter {
+                limit: None,
+                offset: None,
+                order: None,
+                unread: None,
+                marked: None,
+                feed: None,
+                feed_blacklist: None,
+                category: None,
+                category_blacklist: None,
+                tag: None,
                 ids: None,
                 newer_than: None,
                 older_than: None,
-                search_term,
-            })
-            .context(ContentPageErrorKind::DataBase)?;
+                search_term: None,
+            };
+            for article in news_flash.get_articles(filter)? {
+                if enclosure_articles.checked.contains(&article.article_id) {
+                    continue;
+                }
+                let has_enclosures = !news_flash.get_enclosures(&article.article_id)?.is_empty();
+                enclosure_articles.checked.insert(article.article_id.clone());
+                if has_enclosures {
+                    enclosure_articles.with_enclosures.push(article.article_id);
+                }
+            }
+            enclosure_articles.up_to_date = true;
+        }
+        Ok(enclosure_articles.with_enclosures.clone())
+    }
 
-        Ok(articles)
+    /// New articles may have arrived, look for their enclosures the next time they are needed.
+    pub fn enclosure_articles_outdated() {
+        ENCLOSURE_ARTICLES.write().up_to_date = false;
+    }
+
+    /// Copy of `filter` for a single page of the database.
+    fn page_filter(filter: &ArticleFilter, limit: i64, offset: i64) -> ArticleFilter {
+        ArticleFilter {
+            limit: Some(limit),
+            offset: Some(offset),
+            order: filter.order.clone(),
+            unread: filter.unread,
+            marked: filter.marked,
+            feed: filter.feed.clone(),
+            feed_blacklist: filter.feed_blacklist.clone(),
+            category: filter.category.clone(),
+            category_blacklist: filter.category_blacklist.clone(),
+            tag: filter.tag.clone(),
+            ids: filter.ids.clone(),
+            newer_than: filter.newer_than,
+            older_than: filter.older_than,
+            search_term: filter.search_term.clone(),
+        }
+    }
+
+    /// Labels of `tags` for each of `articles`, only asking the database about these articles.
+    fn article_tags(
+        news_flash: &NewsFlash,
+        tags: &[Tag],
+        articles: &[Article],
+    ) -> Result<HashMap<ArticleID, Vec<String>>, NewsFlashError> {
+        let mut article_tags: HashMap<ArticleID, Vec<String>> = HashMap::new();
+        if tags.is_empty() || articles.is_empty() {
+            return Ok(article_tags);
+        }
+
+        let ids: Vec<ArticleID> = articles.iter().map(|article| article.article_id.clone()).collect();
+        for tag in tags {
+            let tagged_articles = news_flash.get_articles(ArticleFilter {
+                limit: None,
+                offset: None,
+                order: None,
+                unread: None,
+                marked: None,
+                feed: None,
+                feed_blacklist: None,
+                category: None,
+                category_blacklist: None,
+                tag: Some(tag.tag_id.clone()),
+                ids: Some(ids.clone()),
+                newer_than: None,
+                older_than: None,
+                search_term: None,
+            })?;
+            for article in tagged_articles {
+                article_tags
+                    .entry(article.article_id)
+                    .or_default()
+                    .push(tag.label.clone());
+            }
+        }
+        Ok(article_tags)
     }
 
     pub fn update_sidebar(
@@ -372,15 +932,17 @@ impl ContentPage {
         threadpool: ThreadPool,
         features: &Arc<RwLock<Option<PluginCapabilities>>>,
     ) {
-        let (sender, receiver) =
-            oneshot::channel::<Result<(i64, FeedListTree, Option<TagListModel>), ContentPageErrorKind>>();
+        let (sender, receiver) = oneshot::channel::<
+            Result<(i64, FeedListTree, Option<TagListModel>, Vec<(SavedSearch, i64)>), ContentPageErrorKind>,
+        >();
 
         let news_flash = news_flash.clone();
         let state = self.state.clone();
-        let current_undo_action = undo_bar.get_current_action();
+        let pending_undo_actions = undo_bar.get_pending_actions();
         let processing_undo_actions = undo_bar.processing_actions();
         let app_features = features.clone();
         let global_sender = self.sender.clone();
+        let saved_searches = self.settings.read().get_saved_searches();
         let thread_future = async move {
             if let Some(news_flash) = news_flash.read().as_ref() {
                 let mut tree = FeedListTree::new();
@@ -422,11 +984,12 @@ impl ContentPage {
                 let mut pending_delte_feeds = HashSet::new();
                 let mut pending_delete_categories = HashSet::new();
                 let mut pending_delete_tags = HashSet::new();
-                if let Some(current_undo_action) = &current_undo_action {
-                    match current_undo_action {
+                for pending_undo_action in &pending_undo_actions {
+                    match pending_undo_action {
                         UndoActionModel::DeleteFeed(id, _label) => pending_delte_feeds.insert(id),
                         UndoActionModel::DeleteCategory(id, _label) => pending_delete_categories.insert(id),
                         UndoActionModel::DeleteTag(id, _label) => pending_delete_tags.insert(id),
+                        _ => false,
                     };
                 }
                 let processing_undo_actions = &*processing_undo_actions.read();
@@ -435,6 +998,7 @@ impl ContentPage {
                         UndoActionModel::DeleteFeed(id, _label) => pending_delte_feeds.insert(id),
                         UndoActionModel::DeleteCategory(id, _label) => pending_delete_categories.insert(id),
                         UndoActionModel::DeleteTag(id, _label) => pending_delete_tags.insert(id),
+                        _ => false,
                     };
                 }
 
@@ -554,8 +1118,61 @@ impl ContentPage {
                     &pending_delete_categories,
                 );
 
+                // saved searches: articles matching the query, counted like the feeds
+                let (unread, marked) = match state.read().get_header_selection() {
+                    HeaderSelection::All | HeaderSelection::Unread => (Some(Read::Unread), None),
+                    HeaderSelection::Marked => (None, Some(Marked::Marked)),
+                };
+                let mut saved_search_counts = Vec::new();
+                let pending_delete_searches: HashSet<u32> = pending_undo_actions
+                    .iter()
+                    .filter_map(|action| match action {
+                        UndoActionModel::DeleteSavedSearch(id, _label) => Some(*id),
+                        _ => None,
+                    })
+                    .collect();
+                let feed_blacklist: Vec<FeedID> = pending_delte_feeds.iter().map(|id| (*id).clone()).collect();
+                let category_blacklist: Vec<CategoryID> =
+                    pending_delete_categories.iter().map(|id| (*id).clone()).collect();
+                for saved_search in saved_searches {
+                    if pending_delete_searches.contains(&saved_search.id) {
+                        continue;
+                    }
+                    let search_query = match saved_search.search_query() {
+                        Ok(Some(search_query)) => search_query,
+                        _ => {
+                            saved_search_counts.push((saved_search, 0));
+                            continue;
+                        }
+                    };
+                    let filter = ArticleFilter {
+                        limit: None,
+                        offset: None,
+                        order: None,
+                        unread,
+                        marked,
+                        feed: None,
+                        feed_blacklist: Some(feed_blacklist.clone()).filter(|blacklist| !blacklist.is_empty()),
+                        category: None,
+                        category_blacklist: Some(category_blacklist.clone()).filter(|blacklist| !blacklist.is_empty()),
+                        tag: None,
+                        ids: None,
+                        newer_than: None,
+                        older_than: None,
+                        search_term: None,
+                    };
+                    let count = match Self::count_articles(news_flash, filter, Some(&search_query)) {
+                        Ok(count) => count,
+                        Err(_error) => {
+                            sender.send(Err(ContentPageErrorKind::DataBase)).expect(CHANNEL_ERROR);
+                            return;
+                        }
+                    };
+                    saved_search_counts.push((saved_search, count));
+                }
+
                 sender
-                    .send(Ok((total_item_count, tree, tag_list_model)))
+                    .send(Ok((total_item_count, tree, tag_list_model, saved_search_counts)))
                     .expect(CHANNEL_ERROR);
             }
         };
@@ -567,7 +1184,7 @@ impl ContentPage {
                 match res {
                     Ok(res) => {
                         match res {
-                            Ok((total_count, feed_list_model, tag_list_model)) => {
+                            Ok((total_count, feed_list_model, tag_list_model, saved_searches)) => {
                                 sidebar.write().update_feedlist(feed_list_model, &features);
                                 sidebar.write().update_all(total_count);
                                 if let Some(tag_list_model) = tag_list_model {
@@ -580,6 +1197,7 @@ impl ContentPage {
                                 } else {
                                     sidebar.read().hide_taglist();
                                 }
+                                sidebar.read().update_saved_searches(saved_searches);
                             },
                             Err(error) => Util::send(&sender, Action::ErrorSimpleMessage(format!("Failed to update sidebar: '{}'", error)))
                         }
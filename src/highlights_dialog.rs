@@ -0,0 +1,204 @@
+use crate::app::Action;
+use crate::i18n::i18n;
+use crate::settings::{Highlight, Settings};
+use crate::util::{BuilderHelper, FileUtil, Util};
+use glib::{clone, Sender};
+use gtk::{
+    Align, Box, BoxExt, Button, ButtonExt, ContainerExt, Dialog, DialogExt, Entry, EntryExt, FileChooserAction,
+    FileChooserDialog, FileChooserExt, FileFilter, GtkWindowExt, IconSize, Inhibit, Justification, Label, LabelExt,
+    ListBox, ListBoxExt, ListBoxRow, ListBoxRowExt, Orientation, ReliefStyle, ResponseType, StyleContextExt, WidgetExt,
+};
+use news_flash::models::ArticleID;
+use pango::{EllipsizeMode, WrapMode};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Lists the highlights of all articles, newest first, and exports them as Markdown.
+#[derive(Clone, Debug)]
+pub struct HighlightsDialog {
+    pub dialog: Dialog,
+}
+
+impl HighlightsDialog {
+    pub fn new(parent: &gtk::ApplicationWindow, settings: &Arc<RwLock<Settings>>, sender: &Sender<Action>) -> Self {
+        let builder = BuilderHelper::new("highlights_dialog");
+        let export_button = builder.get::<Button>("export_button");
+        let highlights_list = builder.get::<ListBox>("highlights_list");
+        let dialog = builder.get::<Dialog>("highlights_dialog");
+
+        let placeholder = Label::new(Some(&i18n(
+            "Select text in an article and highlight it from the context menu.",
+        )));
+        placeholder.set_line_wrap(true);
+        placeholder.set_max_width_chars(40);
+        placeholder.set_justify(Justification::Center);
+        placeholder.set_margin_top(40);
+        placeholder.set_margin_bottom(40);
+        placeholder.get_style_context().add_class("dim-label");
+        placeholder.show();
+        highlights_list.set_placeholder(Some(&placeholder));
+
+        for highlight in settings.read().get_highlights().iter().rev() {
+            let row = Self::build_row(highlight, &highlights_list, &dialog, settings, sender);
+            highlights_list.insert(&row, -1);
+        }
+
+        export_button.connect_clicked(clone!(
+            @weak dialog,
+            @strong settings,
+            @strong sender => @default-panic, move |_button|
+        {
+            Self::export_markdown(&dialog, &settings, &sender);
+        }));
+
+        dialog.set_transient_for(Some(parent));
+        dialog.show_all();
+
+        HighlightsDialog { dialog }
+    }
+
+    fn build_row(
+        highlight: &Highlight,
+        highlights_list: &ListBox,
+        dialog: &Dialog,
+        settings: &Arc<RwLock<Settings>>,
+        sender: &Sender<Action>,
+    ) -> ListBoxRow {
+        let title_label = Label::new(None);
+        title_label.set_markup(&format!(
+            "<b>{}</b>",
+            glib::markup_escape_text(highlight.article_title.as_deref().unwrap_or("Untitled Article"))
+        ));
+        title_label.set_ellipsize(EllipsizeMode::End);
+        title_label.set_xalign(0.0);
+        title_label.set_hexpand(true);
+
+        let show_button = Button::from_icon_name(Some("go-jump-symbolic"), IconSize::Button);
+        show_button.set_relief(ReliefStyle::None);
+        show_button.set_tooltip_text(Some(&i18n("Show Article")));
+        let delete_button = Button::from_icon_name(Some("user-trash-symbolic"), IconSize::Button);
+        delete_button.set_relief(ReliefStyle::None);
+        delete_button.set_tooltip_text(Some(&i18n("Remove Highlight")));
+
+        let header = Box::new(Orientation::Horizontal, 5);
+        header.pack_start(&title_label, true, true, 0);
+        header.pack_end(&delete_button, false, false, 0);
+        header.pack_end(&show_button, false, false, 0);
+
+        let quote_label = Label::new(Some(&highlight.text));
+        quote_label.set_line_wrap(true);
+        quote_label.set_line_wrap_mode(WrapMode::WordChar);
+        quote_label.set_lines(6);
+        quote_label.set_ellipsize(EllipsizeMode::End);
+        quote_label.set_xalign(0.0);
+        quote_label.set_halign(Align::Start);
+
+        let note_entry = Entry::new();
+        note_entry.set_placeholder_text(Some(&i18n("Add a note…")));
+        note_entry.set_text(highlight.note.as_deref().unwrap_or(""));
+
+        let content = Box::new(Orientation::Vertical, 5);
+        content.set_margin_start(10);
+        content.set_margin_end(10);
+        content.set_margin_top(10);
+        content.set_margin_bottom(10);
+        content.pack_start(&header, false, true, 0);
+        content.pack_start(&quote_label, false, true, 0);
+        content.pack_start(&note_entry, false, true, 0);
+
+        let row = ListBoxRow::new();
+        row.set_activatable(false);
+        row.add(&content);
+
+        let id = highlight.id;
+        let article_id = ArticleID::new(&highlight.article_id);
+        show_button.connect_clicked(clone!(
+            @weak dialog,
+            @strong article_id,
+            @strong sender => @default-panic, move |_button|
+        {
+            Util::send(&sender, Action::ShowArticle(article_id.clone()));
+            dialog.emit_close();
+        }));
+
+        delete_button.connect_clicked(clone!(
+            @weak row,
+            @weak highlights_list,
+            @strong settings,
+            @strong sender => @default-panic, move |_button|
+        {
+            if settings.write().delete_highlight(id).is_err() {
+                Util::send(
+                    &sender,
+                    Action::ErrorSimpleMessage("Failed to remove highlight.".to_owned()),
+                );
+                return;
+            }
+            highlights_list.remove(&row);
+            Util::send(&sender, Action::UpdateHighlights);
+        }));
+
+        note_entry.connect_activate(clone!(
+            @strong settings,
+            @strong sender => @default-panic, move |entry|
+        {
+            Self::save_note(entry, id, &settings, &sender);
+        }));
+        note_entry.connect_focus_out_event(clone!(
+            @strong settings,
+            @strong sender => @default-panic, move |entry, _event|
+        {
+            Self::save_note(entry, id, &settings, &sender);
+            Inhibit(false)
+        }));
+
+        row
+    }
+
+    fn save_note(entry: &Entry, id: u32, settings: &Arc<RwLock<Settings>>, sender: &Sender<Action>) {
+        let note = entry.get_text().trim().to_owned();
+        let note = if note.is_empty() { None } else { Some(note) };
+        if settings.write().set_highlight_note(id, note).is_err() {
+            Util::send(
+                sender,
+                Action::ErrorSimpleMessage("Failed to save highlight note.".to_owned()),
+            );
+            return;
+        }
+        Util::send(sender, Action::UpdateHighlights);
+    }
+
+    fn export_markdown(parent: &Dialog, settings: &Arc<RwLock<Settings>>, sender: &Sender<Action>) {
+        let dialog = FileChooserDialog::with_buttons(
+            Some(&i18n("Export Highlights")),
+            Some(parent),
+            FileChooserAction::Save,
+            &[
+                (&i18n("Cancel"), ResponseType::Cancel),
+                (&i18n("Save"), ResponseType::Ok),
+            ],
+        );
+
+        let filter = FileFilter::new();
+        filter.add_pattern("*.md");
+        filter.add_mime_type("text/markdown");
+        filter.set_name(Some("Markdown"));
+        dialog.add_filter(&filter);
+        dialog.set_filter(&filter);
+        dialog.set_current_name("Highlights.md");
+
+        if let ResponseType::Ok = dialog.run() {
+            if let Some(filename) = dialog.get_filename() {
+                let markdown = Highlight::markdown(&settings.read().get_highlights());
+                if FileUtil::write_text_file(&filename, &markdown).is_err() {
+                    Util::send(
+                        sender,
+                        Action::ErrorSimpleMessage("Failed to write highlights to disc.".to_owned()),
+                    );
+                }
+            }
+        }
+
+        dialog.emit_close();
+    }
+}
@@ -5,12 +5,15 @@ mod add_dialog;
 mod app;
 mod article_list;
 mod article_view;
+mod catch_up_dialog;
 mod color;
+mod color_scheme;
 mod config;
 mod content_page;
 mod discover;
 mod error_bar;
 mod error_dialog;
+mod highlights_dialog;
 mod i18n;
 mod login_screen;
 mod main_window;
@@ -18,6 +21,7 @@ mod main_window_state;
 mod rename_dialog;
 mod reset_page;
 mod responsive;
+mod search_query;
 mod settings;
 mod sidebar;
 mod tag_popover;
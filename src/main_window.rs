@@ -1,5 +1,6 @@
 use crate::about_dialog::APP_NAME;
 use crate::app::{Action, App};
+use crate::color_scheme::ColorScheme;
 use crate::config::{APP_ID, PROFILE};
 use crate::content_page::{ContentHeader, ContentPage, HeaderSelection};
 use crate::error_bar::ErrorBar;
@@ -13,6 +14,7 @@ use crate::undo_bar::{UndoActionModel, UndoBar};
 use crate::util::{BuilderHelper, GtkUtil, Util, CHANNEL_ERROR, GTK_CSS_ERROR, GTK_RESOURCE_FILE_ERROR, RUNTIME_ERROR};
 use crate::welcome_screen::{WelcomeHeaderbar, WelcomePage};
 use crate::Resources;
+use chrono::{Duration, NaiveDateTime, Utc};
 use futures::channel::oneshot;
 use futures::executor::ThreadPool;
 use futures::FutureExt;
@@ -23,9 +25,10 @@ use gtk::{
     Settings as GtkSettings, SettingsExt, Stack, StackExt, StackTransitionType, StyleContext, StyleContextExt,
     WidgetExt,
 };
-use log::{error, warn};
+use log::{error, info, warn};
 use news_flash::models::{
-    ArticleID, FatArticle, Feed, PasswordLogin as PasswordLoginData, PluginCapabilities, PluginID,
+    ArticleFilter, ArticleID, Enclosure, FatArticle, Feed, PasswordLogin as PasswordLoginData, PluginCapabilities,
+    PluginID, Read,
 };
 use news_flash::{NewsFlash, NewsFlashError};
 use parking_lot::RwLock;
@@ -48,6 +51,7 @@ pub struct MainWindow {
     responsive_layout: Arc<ResponsiveLayout>,
     pub state: Arc<RwLock<MainWindowState>>,
     sender: Sender<Action>,
+    _color_scheme: ColorScheme,
 }
 
 impl MainWindow {
@@ -60,6 +64,8 @@ impl MainWindow {
         GtkUtil::register_symbolic_icons();
         let css_provider = Arc::new(RwLock::new(CssProvider::new()));
 
+        let color_scheme = ColorScheme::new();
+
         if let Some(gtk_settings) = GtkSettings::get_default() {
             gtk_settings.set_property_gtk_application_prefer_dark_theme(settings.read().get_prefer_dark_theme());
         }
@@ -80,6 +86,11 @@ impl MainWindow {
         let _login_header = LoginHeaderbar::new(&builder, sender.clone());
         let _welcome_header = WelcomeHeaderbar::new(&builder);
         let content_header = Arc::new(ContentHeader::new(&builder, &state, sender.clone(), features));
+        content_header.show_article_list_view(
+            &settings
+                .read()
+                .get_article_list_view(state.read().get_sidebar_selection()),
+        );
 
         window.set_icon_name(Some(APP_ID));
         window.set_title(APP_NAME);
@@ -119,6 +130,7 @@ impl MainWindow {
                         // Workaround SINGLE selection mode of listbox
                         content_page.sidebar.read().feed_list.read().on_window_hidden();
                         content_page.sidebar.read().tag_list.read().on_window_hidden();
+                        content_page.sidebar.read().saved_search_list.read().on_window_hidden();
                     } else {
                         Util::send(&sender, Action::QueueQuit);
                     }
@@ -158,6 +170,16 @@ impl MainWindow {
             }));
         }
 
+        // the stored preference is used until the desktop answered
+        color_scheme.prefers_dark(clone!(@weak settings => @default-panic, move |prefers_dark| {
+            if let Some(prefers_dark) = prefers_dark {
+                Self::apply_system_color_scheme(&settings, prefers_dark);
+            }
+        }));
+        color_scheme.connect_changed(clone!(@weak settings => @default-panic, move |prefers_dark| {
+            Self::apply_system_color_scheme(&settings, prefers_dark);
+        }));
+
         MainWindow {
             widget: window,
             error_bar,
@@ -172,6 +194,7 @@ impl MainWindow {
             responsive_layout,
             state,
             sender,
+            _color_scheme: color_scheme,
         }
     }
 
@@ -204,6 +227,35 @@ impl MainWindow {
                 return Inhibit(false);
             }
 
+            // check the shift variants first: "<ctl>G" also matches ctrl+shift+G
+            if Self::check_shortcut("find_previous", &settings, event) {
+                content_page.article_view.active().find_previous();
+                return Inhibit(true);
+            }
+
+            if Self::check_shortcut("find_next", &settings, event) {
+                content_page.article_view.active().find_next();
+                return Inhibit(true);
+            }
+
+            // the search shortcut (ctrl+F by default) finds in the article while it has the focus
+            if Self::check_shortcut("find", &settings, event)
+                || (content_page.article_view.active().is_focused() && Self::check_shortcut("search", &settings, event))
+            {
+                content_page.article_view.active().show_find_bar();
+                return Inhibit(true);
+            }
+
+            // ignore remaining shortcuts when typing in the find bar
+            if content_page.article_view.active().is_find_focused() {
+                return Inhibit(false);
+            }
+
+            // the image viewer handles its own keys
+            if content_page.article_view.active().is_image_viewer_visible() {
+                return Inhibit(false);
+            }
+
             if Self::check_shortcut("shortcuts", &settings, event) {
                 Util::send(&sender, Action::ShowShortcutWindow);
                 return Inhibit(true);
@@ -216,6 +268,11 @@ impl MainWindow {
                 return Inhibit(true);
             }
 
+            if Self::check_shortcut("undo", &settings, event) {
+                Util::send(&sender, Action::Undo);
+                return Inhibit(true);
+            }
+
             if Self::check_shortcut("quit", &settings, event) {
                 Util::send(&sender, Action::QueueQuit);
                 return Inhibit(true);
@@ -328,7 +385,7 @@ impl MainWindow {
         }));
     }
 
-    fn check_shortcut(id: &str, settings: &Arc<RwLock<Settings>>, event: &EventKey) -> bool {
+    pub fn check_shortcut(id: &str, settings: &Arc<RwLock<Settings>>, event: &EventKey) -> bool {
         if let Ok(keybinding) = Keybindings::read_keybinding(id, settings) {
             if let Some(keybinding) = keybinding {
                 let (keyval, modifier) = gtk::accelerator_parse(&keybinding);
@@ -347,6 +404,18 @@ impl MainWindow {
         false
     }
 
+    /// Follow the dark/light preference of the desktop if enabled. Storing it happens in the notify handler.
+    fn apply_system_color_scheme(settings: &RwLock<Settings>, prefers_dark: bool) {
+        if !settings.read().get_follow_system_color_scheme() {
+            return;
+        }
+        if let Some(gtk_settings) = GtkSettings::get_default() {
+            if gtk_settings.get_property_gtk_application_prefer_dark_theme() != prefers_dark {
+                gtk_settings.set_property_gtk_application_prefer_dark_theme(prefers_dark);
+            }
+        }
+    }
+
     fn load_css(provider: &Arc<RwLock<CssProvider>>) {
         let screen = gdk::Screen::get_default().expect(GTK_CSS_ERROR);
 
@@ -421,7 +490,7 @@ impl MainWindow {
 
     pub fn show_undo_bar(&self, action: UndoActionModel) {
         let select_all_button = match self.content_page.sidebar.read().get_selection() {
-            SidebarSelection::All => false,
+            SidebarSelection::All | SidebarSelection::Podcasts => false,
             SidebarSelection::Category(selected_id, _label) => match &action {
                 UndoActionModel::DeleteCategory(delete_id, _label) => &selected_id == delete_id,
                 _ => false,
@@ -434,6 +503,10 @@ impl MainWindow {
                 UndoActionModel::DeleteTag(delete_id, _label) => &selected_id == delete_id,
                 _ => false,
             },
+            SidebarSelection::SavedSearch(selected_id, _label) => match &action {
+                UndoActionModel::DeleteSavedSearch(delete_id, _label) => &selected_id == delete_id,
+                _ => false,
+            },
         };
         if select_all_button {
             self.state.write().set_sidebar_selection(SidebarSelection::All);
@@ -527,6 +600,7 @@ impl MainWindow {
     ) {
         self.content_page
             .update_sidebar(news_flash, &self.undo_bar, thread_pool, features);
+        self.content_header.update_search_completion(news_flash);
     }
 
     pub fn update_article_list(&self, news_flash: &Arc<RwLock<Option<NewsFlash>>>, thread_pool: ThreadPool) {
@@ -554,6 +628,7 @@ impl MainWindow {
     ) {
         let mut fat_article: Option<FatArticle> = None;
         let mut feed_vec: Option<Vec<Feed>> = None;
+        let mut enclosures: Vec<Enclosure> = Vec::new();
 
         self.state.write().set_prefer_scraped_content(true);
 
@@ -572,6 +647,11 @@ impl MainWindow {
                     return;
                 }
             };
+            // the article is still worth showing without its enclosures
+            match news_flash.get_enclosures(&article_id) {
+                Ok(article_enclosures) => enclosures = article_enclosures,
+                Err(_error) => warn!("Failed to read enclosures of article '{}'", article_id),
+            };
         }
 
         if let Some(article) = fat_article {
@@ -587,7 +667,17 @@ impl MainWindow {
                     }
                 };
                 self.content_header.show_article(Some(&article), news_flash, features);
-                self.content_page.article_view.show_article(article, feed.label.clone());
+                self.content_page
+                    .article_view
+                    .show_article(article, feed.label.clone(), enclosures);
+                self.content_header
+                    .set_feed_images(self.content_page.article_view.feed_images());
+                self.content_header
+                    .set_feed_internal_links(self.content_page.article_view.feed_internal_links());
+                self.content_header
+                    .set_feed_full_content(self.content_page.article_view.feed_full_content());
+                self.content_header
+                    .set_images_blocked(self.content_page.article_view.images_blocked());
 
                 self.responsive_layout.state.write().major_leaflet_selected = true;
                 self.responsive_layout.process_state_change();
@@ -595,6 +685,64 @@ impl MainWindow {
         }
     }
 
+    pub fn open_article_in_tab(&self, article_id: &ArticleID, news_flash: &Arc<RwLock<Option<NewsFlash>>>) {
+        if let Some((article, feed_name, enclosures)) = self.load_article(article_id, news_flash) {
+            self.content_page.article_view.open_tab(article, feed_name, enclosures);
+            self.responsive_layout.state.write().major_leaflet_selected = true;
+            self.responsive_layout.process_state_change();
+        }
+    }
+
+    pub fn open_article_in_window(&self, article_id: &ArticleID, news_flash: &Arc<RwLock<Option<NewsFlash>>>) {
+        if let Some((article, feed_name, enclosures)) = self.load_article(article_id, news_flash) {
+            self.content_page
+                .article_view
+                .open_window(article, feed_name, enclosures);
+        }
+    }
+
+    /// The article together with the name of its feed and its enclosures.
+    fn load_article(
+        &self,
+        article_id: &ArticleID,
+        news_flash: &Arc<RwLock<Option<NewsFlash>>>,
+    ) -> Option<(FatArticle, String, Vec<Enclosure>)> {
+        let news_flash = news_flash.read();
+        let news_flash = news_flash.as_ref()?;
+        let article = match news_flash.get_fat_article(article_id) {
+            Ok(article) => article,
+            Err(error) => {
+                Util::send(&self.sender, Action::Error("Failed to read article.".to_owned(), error));
+                return None;
+            }
+        };
+        let feeds = match news_flash.get_feeds() {
+            Ok((feeds, _mappings)) => feeds,
+            Err(error) => {
+                Util::send(&self.sender, Action::Error("Failed to read feeds.".to_owned(), error));
+                return None;
+            }
+        };
+        // the article is still worth showing without its enclosures
+        let enclosures = match news_flash.get_enclosures(article_id) {
+            Ok(enclosures) => enclosures,
+            Err(_error) => {
+                warn!("Failed to read enclosures of article '{}'", article_id);
+                Vec::new()
+            }
+        };
+        match feeds.into_iter().find(|feed| feed.feed_id == article.feed_id) {
+            Some(feed) => Some((article, feed.label, enclosures)),
+            None => {
+                Util::send(
+                    &self.sender,
+                    Action::ErrorSimpleMessage(format!("Failed to find feed: '{}'", article.feed_id)),
+                );
+                None
+            }
+        }
+    }
+
     pub fn set_headerbar_selection(&self, new_selection: HeaderSelection) {
         let old_selection = self.state.read().get_header_selection().clone();
         self.state.write().set_header_selection(new_selection.clone());
@@ -628,6 +776,10 @@ impl MainWindow {
         }
 
         Util::send(&self.sender, Action::UpdateArticleList);
+        // update the highlighted search hits of the open article
+        if self.content_page.article_view.get_visible_article().is_some() {
+            Util::send(&self.sender, Action::RedrawArticle);
+        }
     }
 
     pub fn set_sidebar_read(
@@ -636,163 +788,190 @@ impl MainWindow {
         threadpool: ThreadPool,
         settings: Arc<RwLock<Settings>>,
     ) {
-        let sidebar_selection = self.state.read().get_sidebar_selection().clone();
+        let (sender, receiver) = oneshot::channel::<Result<Vec<ArticleID>, NewsFlashError>>();
 
-        match sidebar_selection {
-            SidebarSelection::All => {
-                let (sender, receiver) = oneshot::channel::<Result<(), NewsFlashError>>();
-
-                let news_flash = news_flash.clone();
-                let thread_future = async move {
-                    let news_flash = news_flash.clone();
-                    let future = async move {
-                        if let Some(news_flash) = news_flash.read().as_ref() {
-                            sender
-                                .send(news_flash.set_all_read(&App::build_client(&settings)).await)
-                                .expect(CHANNEL_ERROR);
-                        }
-                    };
-                    Runtime::new().expect(RUNTIME_ERROR).block_on(future);
+        let sidebar_selection = self.state.read().get_sidebar_selection().clone();
+        let news_flash = news_flash.clone();
+        let thread_future = async move {
+            if let Some(news_flash) = news_flash.read().as_ref() {
+                // remember exactly which articles were unread so the action can be undone
+                let article_ids = match Self::unread_article_ids(news_flash, &settings, &sidebar_selection, None) {
+                    Ok(article_ids) => article_ids,
+                    Err(error) => {
+                        sender.send(Err(error)).expect(CHANNEL_ERROR);
+                        return;
+                    }
                 };
 
-                let glib_future = receiver.map(clone!(
-                    @strong self.sender as sender,
-                    @weak self.content_header as content_header => @default-panic, move |res|
-                {
-                    content_header.finish_mark_all_read();
-                    res.map(|result| match result {
-                        Ok(_) => {}
-                        Err(error) => {
-                            let message = "Failed to mark all read".to_owned();
-                            error!("{}", message);
-                            Util::send(&sender, Action::Error(message, error));
+                let client = App::build_client(&settings);
+                let mut runtime = Runtime::new().expect(RUNTIME_ERROR);
+                let result = match sidebar_selection {
+                    SidebarSelection::All => runtime.block_on(news_flash.set_all_read(&client)),
+                    SidebarSelection::Category(category_id, _title) => {
+                        runtime.block_on(news_flash.set_category_read(&[category_id], &client))
+                    }
+                    SidebarSelection::Feed(feed_id, _parent_id, _title) => {
+                        runtime.block_on(news_flash.set_feed_read(&[feed_id], &client))
+                    }
+                    SidebarSelection::Tag(tag_id, _title) => {
+                        runtime.block_on(news_flash.set_tag_read(&[tag_id], &client))
+                    }
+                    SidebarSelection::Podcasts | SidebarSelection::SavedSearch(_, _) => {
+                        if article_ids.is_empty() {
+                            Ok(())
+                        } else {
+                            runtime.block_on(news_flash.set_article_read(&article_ids, Read::Read, &client))
                         }
-                    })
-                    .expect(CHANNEL_ERROR);
-                    Util::send(&sender, Action::UpdateArticleHeader);
-                    Util::send(&sender, Action::UpdateArticleList);
-                    Util::send(&sender, Action::UpdateSidebar);
-                }));
-
-                threadpool.spawn_ok(thread_future);
-                Util::glib_spawn_future(glib_future);
-            }
-            SidebarSelection::Category(category_id, _title) => {
-                let (sender, receiver) = oneshot::channel::<Result<(), NewsFlashError>>();
-
-                let news_flash = news_flash.clone();
-                let category_id_vec = vec![category_id];
-                let thread_future = async move {
-                    if let Some(news_flash) = news_flash.read().as_ref() {
-                        sender
-                            .send(Runtime::new().expect(RUNTIME_ERROR).block_on(
-                                news_flash.set_category_read(&category_id_vec, &App::build_client(&settings)),
-                            ))
-                            .expect(CHANNEL_ERROR);
                     }
                 };
+                sender.send(result.map(|()| article_ids)).expect(CHANNEL_ERROR);
+            }
+        };
 
-                let glib_future = receiver.map(clone!(
-                    @strong self.sender as sender,
-                    @weak self.content_header as content_header => @default-panic, move |res|
-                {
-                    content_header.finish_mark_all_read();
-                    res.map(|result| match result {
-                        Ok(_) => {}
-                        Err(error) => {
-                            let message = "Failed to mark all read".to_owned();
-                            error!("{}", message);
-                            Util::send(&sender, Action::Error(message, error));
-                        }
-                    })
-                    .expect(CHANNEL_ERROR);
-                    Util::send(&sender, Action::UpdateArticleHeader);
-                    Util::send(&sender, Action::UpdateArticleList);
-                    Util::send(&sender, Action::UpdateSidebar);
-                }));
-
-                threadpool.spawn_ok(thread_future);
-                Util::glib_spawn_future(glib_future);
-            }
-            SidebarSelection::Feed(feed_id, _parent_id, _title) => {
-                let (sender, receiver) = oneshot::channel::<Result<(), NewsFlashError>>();
-
-                let news_flash = news_flash.clone();
-                let feed_id_vec = vec![feed_id];
-                let thread_future = async move {
-                    if let Some(news_flash) = news_flash.read().as_ref() {
-                        sender
-                            .send(
-                                Runtime::new()
-                                    .expect(RUNTIME_ERROR)
-                                    .block_on(news_flash.set_feed_read(&feed_id_vec, &App::build_client(&settings))),
-                            )
-                            .expect(CHANNEL_ERROR);
+        let glib_future = receiver.map(clone!(
+            @strong self.sender as sender,
+            @weak self.content_header as content_header => @default-panic, move |res|
+        {
+            content_header.finish_mark_all_read();
+            res.map(|result| match result {
+                Ok(article_ids) => {
+                    if !article_ids.is_empty() {
+                        Util::send(&sender, Action::UndoableAction(UndoActionModel::SetRead(article_ids)));
                     }
-                };
+                }
+                Err(error) => {
+                    let message = "Failed to mark all read".to_owned();
+                    error!("{}", message);
+                    Util::send(&sender, Action::Error(message, error));
+                }
+            })
+            .expect(CHANNEL_ERROR);
+            Util::send(&sender, Action::UpdateArticleHeader);
+            Util::send(&sender, Action::UpdateArticleList);
+            Util::send(&sender, Action::UpdateSidebar);
+        }));
 
-                let glib_future = receiver.map(clone!(
-                    @strong self.sender as sender,
-                    @weak self.content_header as content_header => @default-panic, move |res|
-                {
-                    content_header.finish_mark_all_read();
-                    res.map(|result| match result {
-                        Ok(_) => {}
+        threadpool.spawn_ok(thread_future);
+        Util::glib_spawn_future(glib_future);
+    }
+
+    /// Mark all unread articles of the current sidebar selection that are older than `days` as read.
+    pub fn catch_up(
+        &self,
+        days: i64,
+        news_flash: &Arc<RwLock<Option<NewsFlash>>>,
+        threadpool: ThreadPool,
+        settings: Arc<RwLock<Settings>>,
+    ) {
+        let (sender, receiver) = oneshot::channel::<Result<Vec<ArticleID>, NewsFlashError>>();
+
+        let sidebar_selection = self.state.read().get_sidebar_selection().clone();
+        let older_than = Utc::now().naive_utc() - Duration::days(days);
+
+        let news_flash = news_flash.clone();
+        let thread_future = async move {
+            if let Some(news_flash) = news_flash.read().as_ref() {
+                let article_ids =
+                    match Self::unread_article_ids(news_flash, &settings, &sidebar_selection, Some(older_than)) {
+                        Ok(article_ids) => article_ids,
                         Err(error) => {
-                            let message = "Failed to mark all read".to_owned();
-                            error!("{}", message);
-                            Util::send(&sender, Action::Error(message, error));
+                            sender.send(Err(error)).expect(CHANNEL_ERROR);
+                            return;
                         }
-                    })
-                    .expect(CHANNEL_ERROR);
-                    Util::send(&sender, Action::UpdateArticleHeader);
-                    Util::send(&sender, Action::UpdateArticleList);
-                    Util::send(&sender, Action::UpdateSidebar);
-                }));
-
-                threadpool.spawn_ok(thread_future);
-                Util::glib_spawn_future(glib_future);
-            }
-            SidebarSelection::Tag(tag_id, _title) => {
-                let (sender, receiver) = oneshot::channel::<Result<(), NewsFlashError>>();
-
-                let news_flash = news_flash.clone();
-                let tag_id_vec = vec![tag_id];
-                let thread_future = async move {
-                    if let Some(news_flash) = news_flash.read().as_ref() {
-                        sender
-                            .send(
-                                Runtime::new()
-                                    .expect(RUNTIME_ERROR)
-                                    .block_on(news_flash.set_tag_read(&tag_id_vec, &App::build_client(&settings))),
-                            )
-                            .expect(CHANNEL_ERROR);
-                    }
+                    };
+
+                let result = if article_ids.is_empty() {
+                    Ok(article_ids)
+                } else {
+                    Runtime::new()
+                        .expect(RUNTIME_ERROR)
+                        .block_on(news_flash.set_article_read(&article_ids, Read::Read, &App::build_client(&settings)))
+                        .map(|()| article_ids)
                 };
+                sender.send(result).expect(CHANNEL_ERROR);
+            }
+        };
 
-                let glib_future = receiver.map(clone!(
-                    @strong self.sender as sender,
-                    @weak self.content_header as content_header => @default-panic, move |res|
+        let glib_future = receiver.map(clone!(@strong self.sender as sender => @default-panic, move |res| {
+            res.map(|result| match result {
+                Ok(article_ids) => {
+                    info!("Caught up on {} articles older than {} days", article_ids.len(), days);
+                    if !article_ids.is_empty() {
+                        Util::send(&sender, Action::UndoableAction(UndoActionModel::SetRead(article_ids)));
+                    }
+                }
+                Err(error) => {
+                    let message = "Failed to catch up".to_owned();
+                    error!("{}", message);
+                    Util::send(&sender, Action::Error(message, error));
+                }
+            })
+            .expect(CHANNEL_ERROR);
+            Util::send(&sender, Action::UpdateArticleHeader);
+            Util::send(&sender, Action::UpdateArticleList);
+            Util::send(&sender, Action::UpdateSidebar);
+        }));
+
+        threadpool.spawn_ok(thread_future);
+        Util::glib_spawn_future(glib_future);
+    }
+
+    fn unread_article_ids(
+        news_flash: &NewsFlash,
+        settings: &RwLock<Settings>,
+        selection: &SidebarSelection,
+        older_than: Option<NaiveDateTime>,
+    ) -> Result<Vec<ArticleID>, NewsFlashError> {
+        let (feed, category, tag) = match selection {
+            SidebarSelection::All | SidebarSelection::Podcasts | SidebarSelection::SavedSearch(_, _) => {
+                (None, None, None)
+            }
+            SidebarSelection::Feed(feed_id, _parent_id, _title) => (Some(feed_id.clone()), None, None),
+            SidebarSelection::Category(category_id, _title) => (None, Some(category_id.clone()), None),
+            SidebarSelection::Tag(tag_id, _title) => (None, None, Some(tag_id.clone())),
+        };
+        let search_query = match selection {
+            SidebarSelection::SavedSearch(id, _title) => {
+                match settings
+                    .read()
+                    .get_saved_search(*id)
+                    .map(|search| search.search_query())
                 {
-                    content_header.finish_mark_all_read();
-                    res.map(|result| match result {
-                        Ok(_) => {}
-                        Err(error) => {
-                            let message = "Failed to mark all read".to_owned();
-                            error!("{}", message);
-                            Util::send(&sender, Action::Error(message, error));
-                        }
-                    })
-                    .expect(CHANNEL_ERROR);
-                    Util::send(&sender, Action::UpdateArticleHeader);
-                    Util::send(&sender, Action::UpdateArticleList);
-                    Util::send(&sender, Action::UpdateSidebar);
-                }));
+                    Some(Ok(Some(search_query))) => Some(search_query),
+                    _ => return Ok(Vec::new()),
+                }
+            }
+            _ => None,
+        };
 
-                threadpool.spawn_ok(thread_future);
-                Util::glib_spawn_future(glib_future);
+        let ids = if *selection == SidebarSelection::Podcasts {
+            let ids = ContentPage::enclosure_article_ids(news_flash)?;
+            if ids.is_empty() {
+                return Ok(Vec::new());
             }
-        }
+            Some(ids)
+        } else {
+            None
+        };
+        let filter = ArticleFilter {
+            limit: None,
+            offset: None,
+            order: None,
+            unread: Some(Read::Unread),
+            marked: None,
+            feed,
+            feed_blacklist: None,
+            category,
+            category_blacklist: None,
+            tag,
+            ids,
+            newer_than: None,
+            older_than,
+            search_term: None,
+        };
+        let articles = ContentPage::query_articles(news_flash, filter, search_query.as_ref())?;
+
+        Ok(articles.into_iter().map(|article| article.article_id).collect())
     }
 
     pub fn update_article_header(
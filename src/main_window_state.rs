@@ -11,8 +11,6 @@ pub struct MainWindowState {
     prefer_scraped_content: bool,
 }
 
-const ARTICLE_LIST_PAGE_SIZE: i64 = 20;
-
 impl MainWindowState {
     pub fn new() -> Self {
         MainWindowState {
@@ -25,10 +23,6 @@ impl MainWindowState {
         }
     }
 
-    pub fn page_size() -> i64 {
-        ARTICLE_LIST_PAGE_SIZE
-    }
-
     pub fn reset_article_list(&mut self) -> bool {
         let reset_article_list = self.reset_article_list;
         self.reset_article_list = false;
@@ -1,6 +1,6 @@
 use crate::sidebar::models::SidebarSelection;
 use crate::util::BuilderHelper;
-use gtk::{Button, Dialog, Entry, EntryExt, GtkWindowExt, HeaderBar, HeaderBarExt, WidgetExt};
+use gtk::{Button, ButtonExt, Dialog, Entry, EntryExt, GtkWindowExt, HeaderBar, HeaderBarExt, WidgetExt};
 
 #[derive(Clone, Debug)]
 pub struct RenameDialog {
@@ -18,17 +18,19 @@ impl RenameDialog {
         let dialog = builder.get::<Dialog>("rename_dialog");
 
         match item {
-            SidebarSelection::All => {}
+            SidebarSelection::All | SidebarSelection::Podcasts => {}
             SidebarSelection::Category(_, _) => header.set_title(Some("Rename Category")),
             SidebarSelection::Feed(_, _, _) => header.set_title(Some("Rename Feed")),
             SidebarSelection::Tag(_, _) => header.set_title(Some("Rename Feed")),
+            SidebarSelection::SavedSearch(_, _) => header.set_title(Some("Rename Saved Search")),
         }
 
         rename_entry.set_text(match item {
-            SidebarSelection::All => "",
+            SidebarSelection::All | SidebarSelection::Podcasts => "",
             SidebarSelection::Category(_, name) => name,
             SidebarSelection::Feed(_, _, name) => name,
             SidebarSelection::Tag(_, name) => name,
+            SidebarSelection::SavedSearch(_, name) => name,
         });
 
         dialog.set_transient_for(Some(parent));
@@ -40,4 +42,47 @@ impl RenameDialog {
             rename_entry,
         }
     }
+
+    /// Ask for the name of a new saved search.
+    pub fn save_search(parent: &gtk::ApplicationWindow, query: &str) -> Self {
+        let builder = BuilderHelper::new("rename_dialog");
+        let header = builder.get::<HeaderBar>("headerbar");
+        let rename_button = builder.get::<Button>("rename_button");
+        let rename_entry = builder.get::<Entry>("rename_entry");
+        let dialog = builder.get::<Dialog>("rename_dialog");
+
+        header.set_title(Some("Save Search"));
+        rename_button.set_label("Save");
+        rename_entry.set_text(query);
+
+        dialog.set_transient_for(Some(parent));
+        dialog.show_all();
+
+        RenameDialog {
+            dialog,
+            rename_button,
+            rename_entry,
+        }
+    }
+
+    /// Ask for the note of a new highlight.
+    pub fn highlight_note(parent: &gtk::ApplicationWindow) -> Self {
+        let builder = BuilderHelper::new("rename_dialog");
+        let header = builder.get::<HeaderBar>("headerbar");
+        let rename_button = builder.get::<Button>("rename_button");
+        let rename_entry = builder.get::<Entry>("rename_entry");
+        let dialog = builder.get::<Dialog>("rename_dialog");
+
+        header.set_title(Some("Add Note"));
+        rename_button.set_label("Save");
+
+        dialog.set_transient_for(Some(parent));
+        dialog.show_all();
+
+        RenameDialog {
+            dialog,
+            rename_button,
+            rename_entry,
+        }
+    }
 }
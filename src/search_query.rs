@@ -0,0 +1,501 @@
+use crate::i18n::{i18n, i18n_f};
+use chrono::{NaiveDate, NaiveDateTime};
+use news_flash::models::{Marked, Read};
+
+static DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SearchQueryError {
+    UnterminatedQuote(usize),
+    UnknownFilter(String),
+    EmptyValue(String),
+    UnknownState(String),
+    InvalidDate(String),
+    NegatedDate(String),
+}
+
+impl SearchQueryError {
+    pub fn message(&self) -> String {
+        match self {
+            SearchQueryError::UnterminatedQuote(position) => i18n_f(
+                "Missing closing quote for the one opened at position {}",
+                &[&position.to_string()],
+            ),
+            SearchQueryError::UnknownFilter(filter) => i18n_f("Unknown filter '{}:'", &[filter]),
+            SearchQueryError::EmptyValue(filter) => i18n_f("Filter '{}:' needs a value", &[filter]),
+            SearchQueryError::UnknownState(state) => i18n_f(
+                "Unknown state 'is:{}'. Use 'is:unread', 'is:read', 'is:starred' or 'is:unstarred'",
+                &[state],
+            ),
+            SearchQueryError::InvalidDate(date) => i18n_f("Invalid date '{}'. Use YYYY-MM-DD", &[date]),
+            SearchQueryError::NegatedDate(filter) => i18n_f("Filter '{}:' can't be negated", &[filter]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchTerm {
+    pub value: String,
+    pub negated: bool,
+}
+
+impl SearchTerm {
+    fn matches(&self, haystack: &[Option<&str>]) -> bool {
+        let found = haystack
+            .iter()
+            .filter_map(|text| *text)
+            .any(|text| self.matches_value(text));
+        found != self.negated
+    }
+
+    /// Whether `text` contains the term, ignoring case and negation.
+    pub fn matches_value(&self, text: &str) -> bool {
+        text.to_lowercase().contains(&self.value.to_lowercase())
+    }
+}
+
+/// Everything the post-filter of a query needs to know about a single article.
+#[derive(Default)]
+pub struct SearchCandidate<'a> {
+    pub title: Option<&'a str>,
+    pub author: Option<&'a str>,
+    pub summary: Option<&'a str>,
+    pub feed: Option<&'a str>,
+    pub categories: Vec<&'a str>,
+    pub tags: Vec<&'a str>,
+}
+
+/// A parsed search like `feed:"Planet GNOME" -tag:read-later is:unread after:2020-01-01 "exact phrase"`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    pub text: Vec<SearchTerm>,
+    pub title: Vec<SearchTerm>,
+    pub author: Vec<SearchTerm>,
+    pub feed: Vec<SearchTerm>,
+    pub category: Vec<SearchTerm>,
+    pub tag: Vec<SearchTerm>,
+    pub unread: Option<Read>,
+    pub marked: Option<Marked>,
+    pub before: Option<NaiveDate>,
+    pub after: Option<NaiveDate>,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Result<Self, SearchQueryError> {
+        let mut search_query = SearchQuery::default();
+        let chars: Vec<(usize, char)> = query.char_indices().collect();
+        let mut pos = 0;
+
+        while pos < chars.len() {
+            if chars[pos].1.is_whitespace() {
+                pos += 1;
+                continue;
+            }
+
+            let negated = chars[pos].1 == '-' && chars.get(pos + 1).map(|(_, c)| !c.is_whitespace()).unwrap_or(false);
+            if negated {
+                pos += 1;
+            }
+
+            if chars[pos].1 == '"' {
+                let (phrase, next) = Self::read_quoted(&chars, pos)?;
+                pos = next;
+                if !phrase.is_empty() {
+                    search_query.text.push(SearchTerm { value: phrase, negated });
+                }
+                continue;
+            }
+
+            let start = pos;
+            while pos < chars.len() && !chars[pos].1.is_whitespace() && chars[pos].1 != ':' && chars[pos].1 != '"' {
+                pos += 1;
+            }
+            let word: String = chars[start..pos].iter().map(|(_, c)| c).collect();
+
+            if pos < chars.len() && chars[pos].1 == ':' && !word.is_empty() {
+                pos += 1;
+                let value = if pos < chars.len() && chars[pos].1 == '"' {
+                    let (value, next) = Self::read_quoted(&chars, pos)?;
+                    pos = next;
+                    value
+                } else {
+                    let value_start = pos;
+                    while pos < chars.len() && !chars[pos].1.is_whitespace() {
+                        pos += 1;
+                    }
+                    chars[value_start..pos].iter().map(|(_, c)| c).collect()
+                };
+                search_query.add_filter(&word.to_lowercase(), value, negated)?;
+            } else {
+                // plain word, including any quotes or colons that don't form a filter
+                while pos < chars.len() && !chars[pos].1.is_whitespace() {
+                    pos += 1;
+                }
+                let word: String = chars[start..pos].iter().map(|(_, c)| c).collect();
+                search_query.text.push(SearchTerm { value: word, negated });
+            }
+        }
+
+        Ok(search_query)
+    }
+
+    fn read_quoted(chars: &[(usize, char)], open: usize) -> Result<(String, usize), SearchQueryError> {
+        let mut pos = open + 1;
+        while pos < chars.len() && chars[pos].1 != '"' {
+            pos += 1;
+        }
+        if pos >= chars.len() {
+            return Err(SearchQueryError::UnterminatedQuote(open + 1));
+        }
+        let value = chars[open + 1..pos].iter().map(|(_, c)| c).collect();
+        Ok((value, pos + 1))
+    }
+
+    fn add_filter(&mut self, filter: &str, value: String, negated: bool) -> Result<(), SearchQueryError> {
+        if value.is_empty() {
+            return Err(SearchQueryError::EmptyValue(filter.to_owned()));
+        }
+
+        let term = SearchTerm { value, negated };
+        match filter {
+            "title" => self.title.push(term),
+            "author" => self.author.push(term),
+            "feed" => self.feed.push(term),
+            "category" => self.category.push(term),
+            "tag" => self.tag.push(term),
+            "is" => match term.value.to_lowercase().as_str() {
+                "unread" => self.unread = Some(if negated { Read::Read } else { Read::Unread }),
+                "read" => self.unread = Some(if negated { Read::Unread } else { Read::Read }),
+                "starred" => self.marked = Some(if negated { Marked::Unmarked } else { Marked::Marked }),
+                "unstarred" => self.marked = Some(if negated { Marked::Marked } else { Marked::Unmarked }),
+                _ => return Err(SearchQueryError::UnknownState(term.value)),
+            },
+            "before" | "after" => {
+                if negated {
+                    return Err(SearchQueryError::NegatedDate(filter.to_owned()));
+                }
+                let date = NaiveDate::parse_from_str(&term.value, DATE_FORMAT)
+                    .map_err(|_| SearchQueryError::InvalidDate(term.value.clone()))?;
+                if filter == "before" {
+                    self.before = Some(date);
+                } else {
+                    self.after = Some(date);
+                }
+            }
+            _ => return Err(SearchQueryError::UnknownFilter(filter.to_owned())),
+        }
+        Ok(())
+    }
+
+    /// The part of the query the full text search of the database can handle on its own.
+    pub fn full_text_term(&self) -> Option<String> {
+        let words: Vec<String> = self
+            .text
+            .iter()
+            .filter(|term| !term.negated)
+            .map(|term| Self::quote(&term.value))
+            .collect();
+
+        if words.is_empty() {
+            None
+        } else {
+            Some(words.join(" "))
+        }
+    }
+
+    /// Words and phrases that should be highlighted where the query matched.
+    pub fn highlight_terms(&self) -> Vec<String> {
+        let mut terms: Vec<String> = Vec::new();
+        for term in self.text.iter().chain(self.title.iter()) {
+            if !term.negated && !terms.contains(&term.value) {
+                terms.push(term.value.clone());
+            }
+        }
+        terms
+    }
+
+    pub fn older_than(&self) -> Option<NaiveDateTime> {
+        self.before.map(|date| date.and_hms(0, 0, 0))
+    }
+
+    pub fn newer_than(&self) -> Option<NaiveDateTime> {
+        self.after.map(|date| date.and_hms(0, 0, 0))
+    }
+
+    /// Narrow the query down by the filters of `other`.
+    /// Returns `false` if the two queries contradict each other and can't match any article.
+    pub fn combine(&mut self, other: SearchQuery) -> bool {
+        self.text.extend(other.text);
+        self.title.extend(other.title);
+        self.author.extend(other.author);
+        self.feed.extend(other.feed);
+        self.category.extend(other.category);
+        self.tag.extend(other.tag);
+
+        self.unread = match (self.unread, other.unread) {
+            (Some(this), Some(other)) if this != other => return false,
+            (this, other) => this.or(other),
+        };
+        self.marked = match (self.marked, other.marked) {
+            (Some(this), Some(other)) if this != other => return false,
+            (this, other) => this.or(other),
+        };
+        self.before = match (self.before, other.before) {
+            (Some(this), Some(other)) => Some(this.min(other)),
+            (this, other) => this.or(other),
+        };
+        self.after = match (self.after, other.after) {
+            (Some(this), Some(other)) => Some(this.max(other)),
+            (this, other) => this.or(other),
+        };
+        true
+    }
+
+    /// Whether `matches` has to run over the articles returned by the database.
+    pub fn needs_post_filter(&self) -> bool {
+        !self.title.is_empty()
+            || !self.author.is_empty()
+            || !self.feed.is_empty()
+            || !self.category.is_empty()
+            || !self.tag.is_empty()
+            || self.text.iter().any(|term| term.negated)
+    }
+
+    pub fn matches(&self, candidate: &SearchCandidate) -> bool {
+        let text = [candidate.title, candidate.summary, candidate.author];
+        let categories: Vec<Option<&str>> = candidate.categories.iter().map(|c| Some(*c)).collect();
+        let tags: Vec<Option<&str>> = candidate.tags.iter().map(|t| Some(*t)).collect();
+
+        self.text
+            .iter()
+            .filter(|term| term.negated)
+            .all(|term| term.matches(&text))
+            && self.title.iter().all(|term| term.matches(&[candidate.title]))
+            && self.author.iter().all(|term| term.matches(&[candidate.author]))
+            && self.feed.iter().all(|term| term.matches(&[candidate.feed]))
+            && self.category.iter().all(|term| term.matches(&categories))
+            && self.tag.iter().all(|term| term.matches(&tags))
+    }
+
+    /// Byte offset of the token the cursor at the end of `query` is in.
+    pub fn last_token_start(query: &str) -> usize {
+        let mut in_quotes = false;
+        let mut start = 0;
+        for (index, c) in query.char_indices() {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            } else if c.is_whitespace() && !in_quotes {
+                start = index + c.len_utf8();
+            }
+        }
+        start
+    }
+
+    /// Filter text to complete to, e.g. `feed:"Planet GNOME"`.
+    pub fn completion(filter: &str, value: &str) -> String {
+        format!("{}:{}", filter, Self::quote(value))
+    }
+
+    fn quote(value: &str) -> String {
+        if value.contains(char::is_whitespace) {
+            format!("\"{}\"", value)
+        } else {
+            value.to_owned()
+        }
+    }
+}
+
+/// Byte ranges of `text` that match one of `terms`, ignoring case. Overlapping hits are merged.
+pub fn highlight_ranges(text: &str, terms: &[String]) -> Vec<(usize, usize)> {
+    let terms: Vec<Vec<char>> = terms
+        .iter()
+        .filter(|term| !term.is_empty())
+        .map(|term| term.chars().flat_map(char::to_lowercase).collect())
+        .collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for start in 0..chars.len() {
+        for term in &terms {
+            if let Some(length) = match_length(&chars[start..], term) {
+                let start_byte = chars[start].0;
+                let end_byte = chars.get(start + length).map(|(index, _)| *index).unwrap_or(text.len());
+                match ranges.last_mut() {
+                    Some(last) if last.1 >= start_byte => last.1 = last.1.max(end_byte),
+                    _ => ranges.push((start_byte, end_byte)),
+                }
+            }
+        }
+    }
+    ranges
+}
+
+/// Number of chars at the start of `chars` that match the lowercase `term`.
+fn match_length(chars: &[(usize, char)], term: &[char]) -> Option<usize> {
+    let mut term_index = 0;
+    let mut length = 0;
+    for (_index, c) in chars {
+        if term_index == term.len() {
+            break;
+        }
+        for lower in c.to_lowercase() {
+            if term.get(term_index) != Some(&lower) {
+                return None;
+            }
+            term_index += 1;
+        }
+        length += 1;
+    }
+
+    if term_index == term.len() {
+        Some(length)
+    } else {
+        None
+    }
+}
+
+/// Opening pango tag for the hits of a search, the colors come from the theme.
+pub fn highlight_span(background: &str, foreground: &str) -> String {
+    format!("<span background=\"{}\" foreground=\"{}\">", background, foreground)
+}
+
+/// Pango markup of `text` with all hits of `terms` wrapped in `span`, `None` if nothing matched.
+pub fn highlight_markup(text: &str, terms: &[String], span: &str) -> Option<String> {
+    let ranges = highlight_ranges(text, terms);
+    if ranges.is_empty() {
+        return None;
+    }
+
+    let mut markup = String::new();
+    let mut last_end = 0;
+    for (start, end) in ranges {
+        markup.push_str(&glib::markup_escape_text(&text[last_end..start]));
+        markup.push_str(span);
+        markup.push_str(&glib::markup_escape_text(&text[start..end]));
+        markup.push_str("</span>");
+        last_end = end;
+    }
+    markup.push_str(&glib::markup_escape_text(&text[last_end..]));
+    Some(markup)
+}
+
+pub fn syntax_help() -> String {
+    i18n("Filters: feed: category: tag: author: title: is:unread is:starred before:YYYY-MM-DD after:YYYY-MM-DD. Use \"quotes\" for phrases and - to negate.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_filters_and_phrases() {
+        let query = SearchQuery::parse(r#"gnome feed:"Planet GNOME" -tag:later is:unread "exact phrase""#).unwrap();
+
+        assert_eq!(query.full_text_term(), Some(r#"gnome "exact phrase""#.to_owned()));
+        assert_eq!(
+            query.feed,
+            vec![SearchTerm {
+                value: "Planet GNOME".to_owned(),
+                negated: false
+            }]
+        );
+        assert_eq!(
+            query.tag,
+            vec![SearchTerm {
+                value: "later".to_owned(),
+                negated: true
+            }]
+        );
+        assert_eq!(query.unread, Some(Read::Unread));
+        assert!(query.needs_post_filter());
+    }
+
+    #[test]
+    fn parse_dates() {
+        let query = SearchQuery::parse("after:2020-01-01 before:2020-02-01").unwrap();
+        assert_eq!(query.after, Some(NaiveDate::from_ymd(2020, 1, 1)));
+        assert_eq!(query.before, Some(NaiveDate::from_ymd(2020, 2, 1)));
+        assert!(!query.needs_post_filter());
+
+        assert_eq!(
+            SearchQuery::parse("before:yesterday"),
+            Err(SearchQueryError::InvalidDate("yesterday".to_owned()))
+        );
+        assert_eq!(
+            SearchQuery::parse("-after:2020-01-01"),
+            Err(SearchQueryError::NegatedDate("after".to_owned()))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            SearchQuery::parse("foo:bar"),
+            Err(SearchQueryError::UnknownFilter("foo".to_owned()))
+        );
+        assert_eq!(
+            SearchQuery::parse("feed:"),
+            Err(SearchQueryError::EmptyValue("feed".to_owned()))
+        );
+        assert_eq!(
+            SearchQuery::parse("a \"open"),
+            Err(SearchQueryError::UnterminatedQuote(3))
+        );
+        assert_eq!(
+            SearchQuery::parse("is:new"),
+            Err(SearchQueryError::UnknownState("new".to_owned()))
+        );
+    }
+
+    #[test]
+    fn match_candidate() {
+        let query = SearchQuery::parse("-rust title:gtk feed:planet").unwrap();
+        let mut candidate = SearchCandidate {
+            title: Some("GTK 4 released"),
+            feed: Some("Planet GNOME"),
+            ..SearchCandidate::default()
+        };
+        assert!(query.matches(&candidate));
+
+        candidate.summary = Some("Now with Rust bindings");
+        assert!(!query.matches(&candidate));
+    }
+
+    #[test]
+    fn combine_queries() {
+        let mut query = SearchQuery::parse("gnome is:unread after:2020-01-01").unwrap();
+        assert!(query.combine(SearchQuery::parse("tag:later after:2020-03-01").unwrap()));
+        assert_eq!(query.text.len(), 1);
+        assert_eq!(query.tag.len(), 1);
+        assert_eq!(query.after, Some(NaiveDate::from_ymd(2020, 3, 1)));
+
+        assert!(!query.combine(SearchQuery::parse("is:read").unwrap()));
+    }
+
+    #[test]
+    fn highlight_hits() {
+        let terms = SearchQuery::parse(r#"gtk "rust bindings" -gnome"#)
+            .unwrap()
+            .highlight_terms();
+        assert_eq!(terms, vec!["gtk".to_owned(), "rust bindings".to_owned()]);
+
+        let text = "GTK & Rust Bindings for gtk";
+        assert_eq!(highlight_ranges(text, &terms), vec![(0, 3), (6, 19), (24, 27)]);
+        let span = highlight_span("#f8e45c", "#000000");
+        assert_eq!(
+            highlight_markup("a < gtk", &terms, &span),
+            Some("a &lt; <span background=\"#f8e45c\" foreground=\"#000000\">gtk</span>".to_owned())
+        );
+        assert_eq!(highlight_markup("nothing here", &terms, &span), None);
+
+        let overlapping = vec!["abc".to_owned(), "bcd".to_owned()];
+        assert_eq!(highlight_ranges("xabcdx", &overlapping), vec![(1, 5)]);
+    }
+
+    #[test]
+    fn completion_token() {
+        assert_eq!(SearchQuery::last_token_start("gnome feed:pla"), 6);
+        assert_eq!(SearchQuery::last_token_start("feed:\"Planet GN"), 0);
+        assert_eq!(SearchQuery::completion("feed", "Planet GNOME"), "feed:\"Planet GNOME\"");
+    }
+}
@@ -1,16 +1,51 @@
+use crate::article_list::{ArticleListGrouping, ArticleListSort};
 use news_flash::models::ArticleOrder;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::default::Default;
 
+const DEFAULT_PAGE_SIZE: i64 = 20;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ArticleListSettings {
     pub order: ArticleOrder,
+    #[serde(default = "ArticleListSettings::default_page_size")]
+    pub page_size: i64,
+    #[serde(default)]
+    pub mark_read_on_scroll: bool,
+    #[serde(default)]
+    pub views: HashMap<String, ArticleListViewSettings>,
+}
+
+impl ArticleListSettings {
+    fn default_page_size() -> i64 {
+        DEFAULT_PAGE_SIZE
+    }
 }
 
 impl Default for ArticleListSettings {
     fn default() -> Self {
         ArticleListSettings {
             order: ArticleOrder::NewestFirst,
+            page_size: DEFAULT_PAGE_SIZE,
+            mark_read_on_scroll: false,
+            views: HashMap::new(),
         }
     }
 }
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ArticleListViewSettings {
+    #[serde(default)]
+    pub sort: ArticleListSort,
+    #[serde(default)]
+    pub grouping: ArticleListGrouping,
+}
+
+impl ArticleListViewSettings {
+    /// Only lists in date order can be loaded page by page, the database doesn't know any other order.
+    /// All other lists are loaded completely so the first page really holds the first articles.
+    pub fn paged(&self) -> bool {
+        self.sort == ArticleListSort::Date && self.grouping != ArticleListGrouping::Feed
+    }
+}
@@ -1,7 +1,13 @@
 use crate::article_view::ArticleTheme;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::default::Default;
 
+const DEFAULT_CONTENT_WIDTH: u32 = 50;
+const DEFAULT_LINE_HEIGHT: f64 = 1.8;
+const DEFAULT_PARAGRAPH_SPACING: f64 = 1.0;
+const DEFAULT_PLAYBACK_RATE: f64 = 1.0;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ArticleViewSettings {
     pub theme: ArticleTheme,
@@ -9,6 +15,72 @@ pub struct ArticleViewSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub font: Option<String>,
+    #[serde(default)]
+    pub user_stylesheet: bool,
+    #[serde(default)]
+    pub privacy_mode: bool,
+    #[serde(default)]
+    pub data_saver: bool,
+    #[serde(default = "ArticleViewSettings::default_data_saver_metered")]
+    pub data_saver_metered: bool,
+    /// feed id -> whether images are always (true) or never (false) loaded right away
+    #[serde(default)]
+    pub feed_images: HashMap<String, bool>,
+    #[serde(default = "ArticleViewSettings::default_content_width")]
+    pub content_width: u32,
+    #[serde(default = "ArticleViewSettings::default_line_height")]
+    pub line_height: f64,
+    #[serde(default = "ArticleViewSettings::default_paragraph_spacing")]
+    pub paragraph_spacing: f64,
+    #[serde(default)]
+    pub justify: bool,
+    #[serde(default)]
+    pub hyphenate: bool,
+    /// zoom level per feed id, only levels other than 100% are stored
+    #[serde(default)]
+    pub feed_zoom: HashMap<String, f64>,
+    /// reading positions of older versions, moved to `ReadingPositions` on startup
+    #[serde(default, skip_serializing)]
+    pub reading_positions: HashMap<String, f64>,
+    /// playback position per enclosure url of episodes that were left unfinished
+    #[serde(default)]
+    pub enclosure_positions: HashMap<String, f64>,
+    #[serde(default = "ArticleViewSettings::default_playback_rate")]
+    pub playback_rate: f64,
+    /// go on with the next unread article once reading aloud is done
+    #[serde(default)]
+    pub read_aloud_continue: bool,
+    /// open links of articles in NewsFlash instead of the default browser
+    #[serde(default)]
+    pub internal_links: bool,
+    /// feed id -> whether links are always opened in NewsFlash (true) or in the default browser (false)
+    #[serde(default)]
+    pub feed_internal_links: HashMap<String, bool>,
+    /// ids of feeds whose articles are always shown with their full (scraped) content
+    #[serde(default)]
+    pub feed_full_content: HashSet<String>,
+}
+
+impl ArticleViewSettings {
+    fn default_data_saver_metered() -> bool {
+        true
+    }
+
+    fn default_content_width() -> u32 {
+        DEFAULT_CONTENT_WIDTH
+    }
+
+    fn default_line_height() -> f64 {
+        DEFAULT_LINE_HEIGHT
+    }
+
+    fn default_paragraph_spacing() -> f64 {
+        DEFAULT_PARAGRAPH_SPACING
+    }
+
+    fn default_playback_rate() -> f64 {
+        DEFAULT_PLAYBACK_RATE
+    }
 }
 
 impl Default for ArticleViewSettings {
@@ -17,6 +89,24 @@ impl Default for ArticleViewSettings {
             theme: ArticleTheme::Default,
             allow_select: false,
             font: None,
+            user_stylesheet: false,
+            privacy_mode: false,
+            data_saver: false,
+            data_saver_metered: true,
+            feed_images: HashMap::new(),
+            content_width: DEFAULT_CONTENT_WIDTH,
+            line_height: DEFAULT_LINE_HEIGHT,
+            paragraph_spacing: DEFAULT_PARAGRAPH_SPACING,
+            justify: false,
+            hyphenate: false,
+            feed_zoom: HashMap::new(),
+            reading_positions: HashMap::new(),
+            enclosure_positions: HashMap::new(),
+            playback_rate: DEFAULT_PLAYBACK_RATE,
+            read_aloud_continue: false,
+            internal_links: false,
+            feed_internal_links: HashMap::new(),
+            feed_full_content: HashSet::new(),
         }
     }
 }
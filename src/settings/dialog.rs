@@ -2,26 +2,42 @@ use super::general::SyncInterval;
 use super::keybinding_editor::{KeybindState, KeybindingEditor};
 use super::keybindings::Keybindings;
 use super::theme_chooser::ThemeChooser;
+use super::theme_editor::ThemeEditorTarget;
 use crate::app::Action;
+use crate::article_view::ArticleView;
+use crate::color_scheme::ColorScheme;
+use crate::i18n::i18n;
 use crate::settings::Settings;
 use crate::util::{BuilderHelper, GtkUtil, Util, GTK_BUILDER_ERROR};
 use gdk::{EventMask, EventType};
 use glib::{clone, object::Cast, translate::ToGlib, Sender};
 use gtk::{
-    prelude::GtkWindowExtManual, prelude::WidgetExtManual, DialogExt, EventBox, FontButton, FontButtonExt,
-    FontChooserExt, GtkWindowExt, Inhibit, Label, LabelExt, ListBox, ListBoxExt, ListBoxRowExt, Popover, PopoverExt,
-    Settings as GtkSettings, SettingsExt as GtkSettingsExt, Switch, SwitchExt, Widget, WidgetExt, Window,
+    prelude::GtkWindowExtManual, prelude::WidgetExtManual, Button, ButtonExt, DialogExt, EventBox, FontButton,
+    FontButtonExt, FontChooserExt, GtkWindowExt, Inhibit, Label, LabelExt, ListBox, ListBoxExt, ListBoxRowExt, Popover,
+    PopoverExt, Settings as GtkSettings, SettingsExt as GtkSettingsExt, SpinButton, SpinButtonExt, Switch, SwitchExt,
+    Widget, WidgetExt, Window,
 };
 use libhandy::{ActionRow, PreferencesRowExt};
 use news_flash::models::ArticleOrder;
 use parking_lot::RwLock;
 use std::sync::Arc;
+use webkit2gtk::{WebView, WebViewExt};
+
+const TYPOGRAPHY_SAMPLE: &str =
+    "<p>The quick brown fox jumps over the lazy dog. Typography is the art and technique of \
+arranging type to make written language legible, readable and appealing when displayed.</p>\
+<p>The arrangement of type involves selecting typefaces, point sizes, line lengths, line spacing and letter spacing, \
+and adjusting the space between pairs of letters.</p>";
 
 pub struct SettingsDialog {
     pub widget: Window,
     delete_signal: Arc<RwLock<Option<usize>>>,
     keep_running_switch: Switch,
     keep_running_signal: Arc<RwLock<Option<usize>>>,
+    scrape_after_sync_switch: Switch,
+    scrape_after_sync_signal: Arc<RwLock<Option<usize>>>,
+    follow_system_switch: Switch,
+    follow_system_signal: Arc<RwLock<Option<usize>>>,
     dark_theme_switch: Switch,
     dark_theme_signal: Arc<RwLock<Option<usize>>>,
     gtk_dark_theme_signal: Arc<RwLock<Option<usize>>>,
@@ -41,6 +57,10 @@ pub struct SettingsDialog {
     article_order_event_signal: Arc<RwLock<Option<usize>>>,
     article_order_row: ActionRow,
     article_order_listbox_signal: Arc<RwLock<Option<usize>>>,
+    page_size_spin: SpinButton,
+    page_size_spin_signal: Arc<RwLock<Option<usize>>>,
+    mark_read_on_scroll_switch: Switch,
+    mark_read_on_scroll_signal: Arc<RwLock<Option<usize>>>,
     article_theme_label: Label,
     article_theme_row: ActionRow,
     article_theme_listbox_signal: Arc<RwLock<Option<usize>>>,
@@ -48,6 +68,31 @@ pub struct SettingsDialog {
     article_theme_event_signal: Arc<RwLock<Option<usize>>>,
     allow_selection_switch: Switch,
     allow_selection_switch_signal: Arc<RwLock<Option<usize>>>,
+    user_stylesheet_switch: Switch,
+    user_stylesheet_switch_signal: Arc<RwLock<Option<usize>>>,
+    privacy_mode_switch: Switch,
+    privacy_mode_switch_signal: Arc<RwLock<Option<usize>>>,
+    data_saver_switch: Switch,
+    data_saver_switch_signal: Arc<RwLock<Option<usize>>>,
+    data_saver_metered_switch: Switch,
+    data_saver_metered_switch_signal: Arc<RwLock<Option<usize>>>,
+    read_aloud_continue_switch: Switch,
+    read_aloud_continue_switch_signal: Arc<RwLock<Option<usize>>>,
+    internal_links_switch: Switch,
+    internal_links_switch_signal: Arc<RwLock<Option<usize>>>,
+    edit_stylesheet_button: Button,
+    edit_stylesheet_button_signal: Arc<RwLock<Option<usize>>>,
+    content_width_spin: SpinButton,
+    content_width_spin_signal: Arc<RwLock<Option<usize>>>,
+    line_height_spin: SpinButton,
+    line_height_spin_signal: Arc<RwLock<Option<usize>>>,
+    paragraph_spacing_spin: SpinButton,
+    paragraph_spacing_spin_signal: Arc<RwLock<Option<usize>>>,
+    justify_switch: Switch,
+    justify_switch_signal: Arc<RwLock<Option<usize>>>,
+    hyphenate_switch: Switch,
+    hyphenate_switch_signal: Arc<RwLock<Option<usize>>>,
+    typography_preview: WebView,
     font_row: ActionRow,
     font_button: FontButton,
     font_button_signal: Arc<RwLock<Option<usize>>>,
@@ -70,8 +115,15 @@ impl SettingsDialog {
         let keep_running_switch = builder.get::<Switch>("keep_running_switch");
         keep_running_switch.set_state(settings.read().get_keep_running_in_background());
 
+        let scrape_after_sync_switch = builder.get::<Switch>("scrape_after_sync_switch");
+        scrape_after_sync_switch.set_state(settings.read().get_scrape_after_sync());
+
+        let follow_system_switch = builder.get::<Switch>("follow_system_switch");
+        follow_system_switch.set_state(settings.read().get_follow_system_color_scheme());
+
         let dark_theme_switch = builder.get::<Switch>("dark_theme_switch");
         dark_theme_switch.set_state(settings.read().get_prefer_dark_theme());
+        dark_theme_switch.set_sensitive(!settings.read().get_follow_system_color_scheme());
 
         let sync_label = builder.get::<Label>("sync_label");
         sync_label.set_label(&settings.read().get_sync_interval().to_string());
@@ -94,6 +146,12 @@ impl SettingsDialog {
         let article_order_event = builder.get::<EventBox>("article_order_event");
         article_order_event.set_events(EventMask::BUTTON_PRESS_MASK);
 
+        let page_size_spin = builder.get::<SpinButton>("page_size_spin");
+        page_size_spin.set_value(settings.read().get_article_list_page_size() as f64);
+
+        let mark_read_on_scroll_switch = builder.get::<Switch>("mark_read_on_scroll_switch");
+        mark_read_on_scroll_switch.set_state(settings.read().get_article_list_mark_read_on_scroll());
+
         let article_theme_label = builder.get::<Label>("article_theme_label");
         article_theme_label.set_label(settings.read().get_article_view_theme().name());
 
@@ -104,6 +162,45 @@ impl SettingsDialog {
         let allow_selection_switch = builder.get::<Switch>("allow_selection_switch");
         allow_selection_switch.set_state(settings.read().get_article_view_allow_select());
 
+        let user_stylesheet_switch = builder.get::<Switch>("user_stylesheet_switch");
+        user_stylesheet_switch.set_state(settings.read().get_article_view_user_stylesheet());
+
+        let privacy_mode_switch = builder.get::<Switch>("privacy_mode_switch");
+        privacy_mode_switch.set_state(settings.read().get_article_view_privacy_mode());
+
+        let data_saver_switch = builder.get::<Switch>("data_saver_switch");
+        data_saver_switch.set_state(settings.read().get_article_view_data_saver());
+
+        let data_saver_metered_switch = builder.get::<Switch>("data_saver_metered_switch");
+        data_saver_metered_switch.set_state(settings.read().get_article_view_data_saver_metered());
+        data_saver_metered_switch.set_sensitive(!settings.read().get_article_view_data_saver());
+
+        let read_aloud_continue_switch = builder.get::<Switch>("read_aloud_continue_switch");
+        read_aloud_continue_switch.set_state(settings.read().get_article_view_read_aloud_continue());
+
+        let internal_links_switch = builder.get::<Switch>("internal_links_switch");
+        internal_links_switch.set_state(settings.read().get_article_view_internal_links());
+
+        let edit_stylesheet_button = builder.get::<Button>("edit_stylesheet_button");
+
+        let content_width_spin = builder.get::<SpinButton>("content_width_spin");
+        content_width_spin.set_value(f64::from(settings.read().get_article_view_content_width()));
+
+        let line_height_spin = builder.get::<SpinButton>("line_height_spin");
+        line_height_spin.set_value(settings.read().get_article_view_line_height());
+
+        let paragraph_spacing_spin = builder.get::<SpinButton>("paragraph_spacing_spin");
+        paragraph_spacing_spin.set_value(settings.read().get_article_view_paragraph_spacing());
+
+        let justify_switch = builder.get::<Switch>("justify_switch");
+        justify_switch.set_state(settings.read().get_article_view_justify());
+
+        let hyphenate_switch = builder.get::<Switch>("hyphenate_switch");
+        hyphenate_switch.set_state(settings.read().get_article_view_hyphenate());
+
+        let typography_preview = builder.get::<WebView>("typography_preview");
+        Self::update_typography_preview(&typography_preview, settings);
+
         let font_row = builder.get::<ActionRow>("font_row");
         font_row.set_sensitive(have_custom_font);
 
@@ -121,6 +218,10 @@ impl SettingsDialog {
             delete_signal: Arc::new(RwLock::new(None)),
             keep_running_switch,
             keep_running_signal: Arc::new(RwLock::new(None)),
+            scrape_after_sync_switch,
+            scrape_after_sync_signal: Arc::new(RwLock::new(None)),
+            follow_system_switch,
+            follow_system_signal: Arc::new(RwLock::new(None)),
             dark_theme_switch,
             dark_theme_signal: Arc::new(RwLock::new(None)),
             gtk_dark_theme_signal: Arc::new(RwLock::new(None)),
@@ -140,6 +241,10 @@ impl SettingsDialog {
             article_order_event_signal: Arc::new(RwLock::new(None)),
             article_order_row,
             article_order_listbox_signal: Arc::new(RwLock::new(None)),
+            page_size_spin,
+            page_size_spin_signal: Arc::new(RwLock::new(None)),
+            mark_read_on_scroll_switch,
+            mark_read_on_scroll_signal: Arc::new(RwLock::new(None)),
             article_theme_label,
             article_theme_row,
             article_theme_listbox_signal: Arc::new(RwLock::new(None)),
@@ -147,6 +252,31 @@ impl SettingsDialog {
             article_theme_event_signal: Arc::new(RwLock::new(None)),
             allow_selection_switch,
             allow_selection_switch_signal: Arc::new(RwLock::new(None)),
+            user_stylesheet_switch,
+            user_stylesheet_switch_signal: Arc::new(RwLock::new(None)),
+            privacy_mode_switch,
+            privacy_mode_switch_signal: Arc::new(RwLock::new(None)),
+            data_saver_switch,
+            data_saver_switch_signal: Arc::new(RwLock::new(None)),
+            data_saver_metered_switch,
+            data_saver_metered_switch_signal: Arc::new(RwLock::new(None)),
+            read_aloud_continue_switch,
+            read_aloud_continue_switch_signal: Arc::new(RwLock::new(None)),
+            internal_links_switch,
+            internal_links_switch_signal: Arc::new(RwLock::new(None)),
+            edit_stylesheet_button,
+            edit_stylesheet_button_signal: Arc::new(RwLock::new(None)),
+            content_width_spin,
+            content_width_spin_signal: Arc::new(RwLock::new(None)),
+            line_height_spin,
+            line_height_spin_signal: Arc::new(RwLock::new(None)),
+            paragraph_spacing_spin,
+            paragraph_spacing_spin_signal: Arc::new(RwLock::new(None)),
+            justify_switch,
+            justify_switch_signal: Arc::new(RwLock::new(None)),
+            hyphenate_switch,
+            hyphenate_switch_signal: Arc::new(RwLock::new(None)),
+            typography_preview,
             font_row,
             font_button,
             font_button_signal: Arc::new(RwLock::new(None)),
@@ -171,6 +301,10 @@ impl SettingsDialog {
                     @strong self.delete_signal as delete_signal,
                     @weak self.keep_running_switch as keep_running_switch,
                     @strong self.keep_running_signal as keep_running_signal,
+                    @weak self.scrape_after_sync_switch as scrape_after_sync_switch,
+                    @strong self.scrape_after_sync_signal as scrape_after_sync_signal,
+                    @weak self.follow_system_switch as follow_system_switch,
+                    @strong self.follow_system_signal as follow_system_signal,
                     @weak self.dark_theme_switch as dark_theme_switch,
                     @strong self.dark_theme_signal as dark_theme_signal,
                     @strong self.gtk_dark_theme_signal as gtk_dark_theme_signal,
@@ -186,12 +320,40 @@ impl SettingsDialog {
                     @strong self.article_order_event_signal as article_order_event_signal,
                     @weak self.article_order_row as article_order_row,
                     @strong self.article_order_listbox_signal as article_order_listbox_signal,
+                    @weak self.page_size_spin as page_size_spin,
+                    @strong self.page_size_spin_signal as page_size_spin_signal,
+                    @weak self.mark_read_on_scroll_switch as mark_read_on_scroll_switch,
+                    @strong self.mark_read_on_scroll_signal as mark_read_on_scroll_signal,
                     @weak self.article_theme_event as article_theme_event,
                     @strong self.article_theme_event_signal as article_theme_event_signal,
                     @weak self.article_theme_row as article_theme_row,
                     @strong self.article_theme_listbox_signal as article_theme_listbox_signal,
                     @weak self.allow_selection_switch as allow_selection_switch,
                     @strong self.allow_selection_switch_signal as allow_selection_switch_signal,
+                    @weak self.user_stylesheet_switch as user_stylesheet_switch,
+                    @strong self.user_stylesheet_switch_signal as user_stylesheet_switch_signal,
+                    @weak self.privacy_mode_switch as privacy_mode_switch,
+                    @strong self.privacy_mode_switch_signal as privacy_mode_switch_signal,
+                    @weak self.data_saver_switch as data_saver_switch,
+                    @strong self.data_saver_switch_signal as data_saver_switch_signal,
+                    @weak self.data_saver_metered_switch as data_saver_metered_switch,
+                    @strong self.data_saver_metered_switch_signal as data_saver_metered_switch_signal,
+                    @weak self.read_aloud_continue_switch as read_aloud_continue_switch,
+                    @strong self.read_aloud_continue_switch_signal as read_aloud_continue_switch_signal,
+                    @weak self.internal_links_switch as internal_links_switch,
+                    @strong self.internal_links_switch_signal as internal_links_switch_signal,
+                    @weak self.edit_stylesheet_button as edit_stylesheet_button,
+                    @strong self.edit_stylesheet_button_signal as edit_stylesheet_button_signal,
+                    @weak self.content_width_spin as content_width_spin,
+                    @strong self.content_width_spin_signal as content_width_spin_signal,
+                    @weak self.line_height_spin as line_height_spin,
+                    @strong self.line_height_spin_signal as line_height_spin_signal,
+                    @weak self.paragraph_spacing_spin as paragraph_spacing_spin,
+                    @strong self.paragraph_spacing_spin_signal as paragraph_spacing_spin_signal,
+                    @weak self.justify_switch as justify_switch,
+                    @strong self.justify_switch_signal as justify_switch_signal,
+                    @weak self.hyphenate_switch as hyphenate_switch,
+                    @strong self.hyphenate_switch_signal as hyphenate_switch_signal,
                     @weak self.use_system_font_switch as use_system_font_switch,
                     @strong self.use_system_font_switch_signal as use_system_font_switch_signal,
                     @strong self.keybind_signals as keybind_signals,
@@ -199,13 +361,29 @@ impl SettingsDialog {
                     @strong self.font_button_signal as font_button_signal => @default-panic, move |dialog, _event| {
                         GtkUtil::disconnect_signal(*delete_signal.read(), dialog);
                         GtkUtil::disconnect_signal(*keep_running_signal.read(), &keep_running_switch);
+                        GtkUtil::disconnect_signal(*scrape_after_sync_signal.read(), &scrape_after_sync_switch);
+                        GtkUtil::disconnect_signal(*follow_system_signal.read(), &follow_system_switch);
                         GtkUtil::disconnect_signal(*dark_theme_signal.read(), &dark_theme_switch);
                         GtkUtil::disconnect_signal(*sync_list_signal.read(), &sync_list);
                         GtkUtil::disconnect_signal(*sync_event_signal.read(), &sync_event);
                         GtkUtil::disconnect_signal(*article_order_list_signal.read(), &article_order_list);
                         GtkUtil::disconnect_signal(*article_order_event_signal.read(), &article_order_event);
+                        GtkUtil::disconnect_signal(*page_size_spin_signal.read(), &page_size_spin);
+                        GtkUtil::disconnect_signal(*mark_read_on_scroll_signal.read(), &mark_read_on_scroll_switch);
                         GtkUtil::disconnect_signal(*article_theme_event_signal.read(), &article_theme_event);
                         GtkUtil::disconnect_signal(*allow_selection_switch_signal.read(), &allow_selection_switch);
+                        GtkUtil::disconnect_signal(*user_stylesheet_switch_signal.read(), &user_stylesheet_switch);
+                        GtkUtil::disconnect_signal(*privacy_mode_switch_signal.read(), &privacy_mode_switch);
+                        GtkUtil::disconnect_signal(*data_saver_switch_signal.read(), &data_saver_switch);
+                        GtkUtil::disconnect_signal(*data_saver_metered_switch_signal.read(), &data_saver_metered_switch);
+                        GtkUtil::disconnect_signal(*read_aloud_continue_switch_signal.read(), &read_aloud_continue_switch);
+                        GtkUtil::disconnect_signal(*internal_links_switch_signal.read(), &internal_links_switch);
+                        GtkUtil::disconnect_signal(*edit_stylesheet_button_signal.read(), &edit_stylesheet_button);
+                        GtkUtil::disconnect_signal(*content_width_spin_signal.read(), &content_width_spin);
+                        GtkUtil::disconnect_signal(*line_height_spin_signal.read(), &line_height_spin);
+                        GtkUtil::disconnect_signal(*paragraph_spacing_spin_signal.read(), &paragraph_spacing_spin);
+                        GtkUtil::disconnect_signal(*justify_switch_signal.read(), &justify_switch);
+                        GtkUtil::disconnect_signal(*hyphenate_switch_signal.read(), &hyphenate_switch);
                         GtkUtil::disconnect_signal(*use_system_font_switch_signal.read(), &use_system_font_switch);
                         GtkUtil::disconnect_signal(*font_button_signal.read(), &font_button);
                         if let Some(gtk_settings) = GtkSettings::get_default() {
@@ -232,13 +410,29 @@ impl SettingsDialog {
                         }
                         delete_signal.write().take();
                         keep_running_signal.write().take();
+                        scrape_after_sync_signal.write().take();
+                        follow_system_signal.write().take();
                         dark_theme_signal.write().take();
                         sync_list_signal.write().take();
                         sync_event_signal.write().take();
                         article_order_list_signal.write().take();
                         article_order_event_signal.write().take();
+                        page_size_spin_signal.write().take();
+                        mark_read_on_scroll_signal.write().take();
                         article_theme_event_signal.write().take();
                         allow_selection_switch_signal.write().take();
+                        user_stylesheet_switch_signal.write().take();
+                        privacy_mode_switch_signal.write().take();
+                        data_saver_switch_signal.write().take();
+                        data_saver_metered_switch_signal.write().take();
+                        read_aloud_continue_switch_signal.write().take();
+                        internal_links_switch_signal.write().take();
+                        edit_stylesheet_button_signal.write().take();
+                        content_width_spin_signal.write().take();
+                        line_height_spin_signal.write().take();
+                        paragraph_spacing_spin_signal.write().take();
+                        justify_switch_signal.write().take();
+                        hyphenate_switch_signal.write().take();
                         use_system_font_switch_signal.write().take();
                         font_button_signal.write().take();
 
@@ -269,6 +463,52 @@ impl SettingsDialog {
                 .to_glib() as usize,
         );
 
+        self.scrape_after_sync_signal.write().replace(
+            self.scrape_after_sync_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_scrape_after_sync(is_set).is_err() {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'fetch full content after sync'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.follow_system_signal.write().replace(
+            self.follow_system_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @weak self.dark_theme_switch as dark_theme_switch,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_follow_system_color_scheme(is_set).is_err() {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'follow system style'.".to_owned()),
+                        );
+                        return Inhibit(false);
+                    }
+                    dark_theme_switch.set_sensitive(!is_set);
+                    if is_set {
+                        ColorScheme::new().prefers_dark(|prefers_dark| {
+                            if let (Some(prefers_dark), Some(gtk_settings)) = (prefers_dark, GtkSettings::get_default()) {
+                                if gtk_settings.get_property_gtk_application_prefer_dark_theme() != prefers_dark {
+                                    gtk_settings.set_property_gtk_application_prefer_dark_theme(prefers_dark);
+                                }
+                            }
+                        });
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
         self.dark_theme_signal.write().replace(
             self.dark_theme_switch
                 .connect_state_set(clone!(
@@ -426,6 +666,22 @@ impl SettingsDialog {
             }
         }
 
+        self.page_size_spin_signal.write().replace(
+            self.page_size_spin
+                .connect_value_changed(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |spin|
+                {
+                    if settings.write().set_article_list_page_size(i64::from(spin.get_value_as_int())).is_err() {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'articles per page'.".to_owned()),
+                        );
+                    }
+                }))
+                .to_glib() as usize,
+        );
+
         self.article_theme_event_signal.write().replace(
             self.article_theme_event
                 .connect_button_press_event(clone!(
@@ -494,6 +750,23 @@ impl SettingsDialog {
             }
         }
 
+        self.mark_read_on_scroll_signal.write().replace(
+            self.mark_read_on_scroll_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_list_mark_read_on_scroll(is_set).is_err() {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'mark read on scroll'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
         self.allow_selection_switch_signal.write().replace(
             self.allow_selection_switch
                 .connect_state_set(clone!(
@@ -513,6 +786,231 @@ impl SettingsDialog {
                 .to_glib() as usize,
         );
 
+        self.user_stylesheet_switch_signal.write().replace(
+            self.user_stylesheet_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_view_user_stylesheet(is_set).is_ok() {
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'custom stylesheet'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.privacy_mode_switch_signal.write().replace(
+            self.privacy_mode_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_view_privacy_mode(is_set).is_ok() {
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'privacy mode'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.data_saver_switch_signal.write().replace(
+            self.data_saver_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @weak self.data_saver_metered_switch as data_saver_metered_switch,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    data_saver_metered_switch.set_sensitive(!is_set);
+                    if settings.write().set_article_view_data_saver(is_set).is_ok() {
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'data saver'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.data_saver_metered_switch_signal.write().replace(
+            self.data_saver_metered_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_view_data_saver_metered(is_set).is_ok() {
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'data saver on metered connections'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.read_aloud_continue_switch_signal.write().replace(
+            self.read_aloud_continue_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_view_read_aloud_continue(is_set).is_err() {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'keep reading aloud'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.internal_links_switch_signal.write().replace(
+            self.internal_links_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_view_internal_links(is_set).is_err() {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'open links in NewsFlash'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.edit_stylesheet_button_signal.write().replace(
+            self.edit_stylesheet_button
+                .connect_clicked(clone!(
+                    @weak self.settings as settings,
+                    @strong sender => @default-panic, move |button|
+                {
+                    ThemeChooser::open_editor(button.upcast_ref(), ThemeEditorTarget::Stylesheet, &sender, &settings);
+                }))
+                .to_glib() as usize,
+        );
+
+        self.content_width_spin_signal.write().replace(
+            self.content_width_spin
+                .connect_value_changed(clone!(
+                    @weak self.settings as settings,
+                    @weak self.typography_preview as typography_preview,
+                    @strong sender => @default-panic, move |spin|
+                {
+                    if settings.write().set_article_view_content_width(spin.get_value_as_int() as u32).is_ok() {
+                        Self::update_typography_preview(&typography_preview, &settings);
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'line width'.".to_owned()),
+                        );
+                    }
+                }))
+                .to_glib() as usize,
+        );
+
+        self.line_height_spin_signal.write().replace(
+            self.line_height_spin
+                .connect_value_changed(clone!(
+                    @weak self.settings as settings,
+                    @weak self.typography_preview as typography_preview,
+                    @strong sender => @default-panic, move |spin|
+                {
+                    if settings.write().set_article_view_line_height(spin.get_value()).is_ok() {
+                        Self::update_typography_preview(&typography_preview, &settings);
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'line height'.".to_owned()),
+                        );
+                    }
+                }))
+                .to_glib() as usize,
+        );
+
+        self.paragraph_spacing_spin_signal.write().replace(
+            self.paragraph_spacing_spin
+                .connect_value_changed(clone!(
+                    @weak self.settings as settings,
+                    @weak self.typography_preview as typography_preview,
+                    @strong sender => @default-panic, move |spin|
+                {
+                    if settings.write().set_article_view_paragraph_spacing(spin.get_value()).is_ok() {
+                        Self::update_typography_preview(&typography_preview, &settings);
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'paragraph spacing'.".to_owned()),
+                        );
+                    }
+                }))
+                .to_glib() as usize,
+        );
+
+        self.justify_switch_signal.write().replace(
+            self.justify_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @weak self.typography_preview as typography_preview,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_view_justify(is_set).is_ok() {
+                        Self::update_typography_preview(&typography_preview, &settings);
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'justify text'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
+        self.hyphenate_switch_signal.write().replace(
+            self.hyphenate_switch
+                .connect_state_set(clone!(
+                    @weak self.settings as settings,
+                    @weak self.typography_preview as typography_preview,
+                    @strong sender => @default-panic, move |_switch, is_set|
+                {
+                    if settings.write().set_article_view_hyphenate(is_set).is_ok() {
+                        Self::update_typography_preview(&typography_preview, &settings);
+                        Util::send(&sender, Action::RedrawArticle);
+                    } else {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set setting 'hyphenation'.".to_owned()),
+                        );
+                    }
+                    Inhibit(false)
+                }))
+                .to_glib() as usize,
+        );
+
         self.font_button_signal.write().replace(
             self.font_button
                 .connect_font_set(
@@ -611,6 +1109,7 @@ impl SettingsDialog {
 
         self.setup_keybinding_row("shortcuts", self.settings.read().get_keybind_shortcut(), sender);
         self.setup_keybinding_row("refresh", self.settings.read().get_keybind_refresh(), sender);
+        self.setup_keybinding_row("undo", self.settings.read().get_keybind_undo(), sender);
         self.setup_keybinding_row("search", self.settings.read().get_keybind_search(), sender);
         self.setup_keybinding_row("quit", self.settings.read().get_keybind_quit(), sender);
 
@@ -629,6 +1128,17 @@ impl SettingsDialog {
             self.settings.read().get_keybind_article_view_scrap(),
             sender,
         );
+        self.setup_keybinding_row("find", self.settings.read().get_keybind_article_view_find(), sender);
+        self.setup_keybinding_row(
+            "find_next",
+            self.settings.read().get_keybind_article_view_find_next(),
+            sender,
+        );
+        self.setup_keybinding_row(
+            "find_previous",
+            self.settings.read().get_keybind_article_view_find_prev(),
+            sender,
+        );
     }
 
     fn setup_keybinding_row(&self, id: &str, keybinding: Option<String>, sender: &Sender<Action>) {
@@ -688,6 +1198,13 @@ impl SettingsDialog {
         }
     }
 
+    fn update_typography_preview(preview: &WebView, settings: &Arc<RwLock<Settings>>) {
+        let mut article = ThemeChooser::demo_article(&i18n("Typography"));
+        article.html = Some(TYPOGRAPHY_SAMPLE.to_owned());
+        let html = ArticleView::build_article_static("article", &article, "Feed Name", settings, None, None, false);
+        preview.load_html(&html, None);
+    }
+
     fn keybind_label_text(keybinding: Option<String>, label: &Label) {
         let label_text = match keybinding {
             Some(keybinding) => {
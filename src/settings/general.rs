@@ -46,6 +46,11 @@ pub struct GeneralSettings {
     pub keep_running_in_background: bool,
     pub sync_every: SyncInterval,
     pub prefer_dark_theme: bool,
+    #[serde(default)]
+    pub follow_system_color_scheme: bool,
+    /// fetch the full content of new articles of feeds that always show it right after syncing
+    #[serde(default)]
+    pub scrape_after_sync: bool,
 }
 
 impl Default for GeneralSettings {
@@ -54,6 +59,8 @@ impl Default for GeneralSettings {
             keep_running_in_background: false,
             sync_every: SyncInterval::QuaterHour,
             prefer_dark_theme: false,
+            follow_system_color_scheme: false,
+            scrape_after_sync: false,
         }
     }
 }
@@ -0,0 +1,191 @@
+use super::error::{SettingsError, SettingsErrorKind};
+use failure::ResultExt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+static HIGHLIGHTS_NAME: &str = "highlights.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Highlight {
+    pub id: u32,
+    pub article_id: String,
+    pub article_title: Option<String>,
+    pub article_url: Option<String>,
+    pub text: String,
+    /// The text right in front of the highlight, tells apart repeated occurrences of `text`.
+    pub prefix: String,
+    pub note: Option<String>,
+    /// Unix timestamp
+    pub created: i64,
+}
+
+impl Highlight {
+    /// Highlights grouped by article: a heading per article, followed by the quotes and their notes.
+    pub fn markdown(highlights: &[Highlight]) -> String {
+        let mut article_ids: Vec<&str> = Vec::new();
+        for highlight in highlights {
+            if !article_ids.contains(&highlight.article_id.as_str()) {
+                article_ids.push(&highlight.article_id);
+            }
+        }
+
+        let mut markdown = String::new();
+        for article_id in article_ids {
+            let mut article_highlights = highlights.iter().filter(|highlight| highlight.article_id == article_id);
+            let first = match article_highlights.next() {
+                Some(first) => first,
+                None => continue,
+            };
+            let title = escape_markdown(first.article_title.as_deref().unwrap_or("Untitled Article"));
+            match &first.article_url {
+                Some(url) => markdown.push_str(&format!("## [{}](<{}>)\n", title, escape_markdown(url))),
+                None => markdown.push_str(&format!("## {}\n", title)),
+            }
+
+            for highlight in std::iter::once(first).chain(article_highlights) {
+                markdown.push('\n');
+                for line in highlight.text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                    markdown.push_str(&format!("> {}\n", line));
+                }
+                if let Some(note) = highlight.note.as_deref().map(str::trim).filter(|note| !note.is_empty()) {
+                    markdown.push('\n');
+                    markdown.push_str(note);
+                    markdown.push('\n');
+                }
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+
+    /// A static section listing the quotes and notes, for exports that can't run the highlighting script.
+    pub fn html(highlights: &[Highlight], heading: &str) -> String {
+        let mut html = format!("<section class=\"nf-highlights\"><h2>{}</h2>", escape_html(heading));
+        for highlight in highlights {
+            html.push_str(&format!(
+                "<blockquote><mark class=\"nf-highlight\">{}</mark></blockquote>",
+                escape_html(&highlight.text)
+            ));
+            if let Some(note) = highlight.note.as_deref().map(str::trim).filter(|note| !note.is_empty()) {
+                html.push_str(&format!("<p>{}</p>", escape_html(note)));
+            }
+        }
+        html.push_str("</section>");
+        html
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '(' | ')' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// All highlights, kept apart from the settings file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Highlights {
+    highlights: Vec<Highlight>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Highlights {
+    pub fn open() -> Self {
+        let path = crate::app::DATA_DIR.join(HIGHLIGHTS_NAME);
+        let mut highlights = fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<Self>(&data).ok())
+            .unwrap_or_default();
+        highlights.path = path;
+        highlights
+    }
+
+    pub fn all(&self) -> &[Highlight] {
+        &self.highlights
+    }
+
+    pub fn next_id(&self) -> u32 {
+        self.highlights
+            .iter()
+            .map(|highlight| highlight.id + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn push(&mut self, highlight: Highlight) {
+        self.highlights.push(highlight);
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut Highlight> {
+        self.highlights.iter_mut().find(|highlight| highlight.id == id)
+    }
+
+    pub fn remove(&mut self, id: u32) {
+        self.highlights.retain(|highlight| highlight.id != id);
+    }
+
+    pub fn write(&self) -> Result<(), SettingsError> {
+        fs::create_dir_all(crate::app::DATA_DIR.as_path()).context(SettingsErrorKind::CreateDirectory)?;
+        let data = serde_json::to_string(self).context(SettingsErrorKind::Serialize)?;
+        fs::write(&self.path, data).context(SettingsErrorKind::WriteToDisk)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Highlight;
+
+    #[test]
+    fn markdown_groups_by_article() {
+        let highlights = vec![
+            Highlight {
+                id: 0,
+                article_id: "a".to_owned(),
+                article_title: Some("Article [a]".to_owned()),
+                article_url: Some("https://example.com/a_(1)".to_owned()),
+                text: "first quote".to_owned(),
+                prefix: String::new(),
+                note: Some("a note".to_owned()),
+                created: 0,
+            },
+            Highlight {
+                id: 1,
+                article_id: "b".to_owned(),
+                article_title: Some("Article b".to_owned()),
+                article_url: None,
+                text: "other article".to_owned(),
+                prefix: String::new(),
+                note: None,
+                created: 0,
+            },
+            Highlight {
+                id: 2,
+                article_id: "a".to_owned(),
+                article_title: Some("Article [a]".to_owned()),
+                article_url: Some("https://example.com/a_(1)".to_owned()),
+                text: "two\nlines".to_owned(),
+                prefix: String::new(),
+                note: None,
+                created: 0,
+            },
+        ];
+        assert_eq!(
+            Highlight::markdown(&highlights),
+            "## [Article \\[a\\]](<https://example.com/a_\\(1\\)>)\n\n> first quote\n\na note\n\n> two\n> lines\n\n## Article b\n\n> other article\n\n"
+        );
+    }
+}
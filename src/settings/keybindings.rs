@@ -213,6 +213,7 @@ impl Keybindings {
             "sidebar_set_read" => settings.write().set_keybind_sidebar_set_read(keybinding),
             "shortcuts" => settings.write().set_keybind_shortcut(keybinding),
             "refresh" => settings.write().set_keybind_refresh(keybinding),
+            "undo" => settings.write().set_keybind_undo(keybinding),
             "search" => settings.write().set_keybind_search(keybinding),
             "quit" => settings.write().set_keybind_quit(keybinding),
             "all_articles" => settings.write().set_keybind_all_articles(keybinding),
@@ -221,6 +222,9 @@ impl Keybindings {
             "scroll_up" => settings.write().set_keybind_article_view_up(keybinding),
             "scroll_down" => settings.write().set_keybind_article_view_down(keybinding),
             "scrap_content" => settings.write().set_keybind_article_view_scrap(keybinding),
+            "find" => settings.write().set_keybind_article_view_find(keybinding),
+            "find_next" => settings.write().set_keybind_article_view_find_next(keybinding),
+            "find_previous" => settings.write().set_keybind_article_view_find_prev(keybinding),
             _ => {
                 warn!("unexpected keybind id: {}", id);
                 Err(SettingsErrorKind::InvalidKeybind.into())
@@ -241,6 +245,7 @@ impl Keybindings {
             "sidebar_set_read" => Ok(settings.read().get_keybind_sidebar_set_read()),
             "shortcuts" => Ok(settings.read().get_keybind_shortcut()),
             "refresh" => Ok(settings.read().get_keybind_refresh()),
+            "undo" => Ok(settings.read().get_keybind_undo()),
             "search" => Ok(settings.read().get_keybind_search()),
             "quit" => Ok(settings.read().get_keybind_quit()),
             "all_articles" => Ok(settings.read().get_keybind_all_articles()),
@@ -249,6 +254,9 @@ impl Keybindings {
             "scroll_up" => Ok(settings.read().get_keybind_article_view_up()),
             "scroll_down" => Ok(settings.read().get_keybind_article_view_down()),
             "scrap_content" => Ok(settings.read().get_keybind_article_view_scrap()),
+            "find" => Ok(settings.read().get_keybind_article_view_find()),
+            "find_next" => Ok(settings.read().get_keybind_article_view_find_next()),
+            "find_previous" => Ok(settings.read().get_keybind_article_view_find_prev()),
             _ => {
                 warn!("unexpected keybind id: {}", id);
                 Err(SettingsErrorKind::InvalidKeybind.into())
@@ -284,6 +292,9 @@ pub struct KeybindingsGeneral {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub only_starred: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default = "KeybindingsGeneral::default_undo")]
+    pub undo: Option<String>,
 }
 
 impl KeybindingsGeneral {
@@ -296,8 +307,13 @@ impl KeybindingsGeneral {
             all_articles: Some("<ctl>1".to_owned()),
             only_unread: Some("<ctl>2".to_owned()),
             only_starred: Some("<ctl>3".to_owned()),
+            undo: Self::default_undo(),
         }
     }
+
+    fn default_undo() -> Option<String> {
+        Some("<ctl>Z".to_owned())
+    }
 }
 
 //--------------------------------------------
@@ -315,6 +331,15 @@ pub struct KeybindingsArticleView {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub scrap_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default = "KeybindingsArticleView::default_find")]
+    pub find: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default = "KeybindingsArticleView::default_find_next")]
+    pub find_next: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default = "KeybindingsArticleView::default_find_previous")]
+    pub find_previous: Option<String>,
 }
 
 impl KeybindingsArticleView {
@@ -323,8 +348,23 @@ impl KeybindingsArticleView {
             scroll_up: Some("I".into()),
             scroll_down: Some("U".into()),
             scrap_content: Some("C".into()),
+            find: Self::default_find(),
+            find_next: Self::default_find_next(),
+            find_previous: Self::default_find_previous(),
         }
     }
+
+    fn default_find() -> Option<String> {
+        Some("<ctl><Shift>F".to_owned())
+    }
+
+    fn default_find_next() -> Option<String> {
+        Some("<ctl>G".to_owned())
+    }
+
+    fn default_find_previous() -> Option<String> {
+        Some("<ctl><Shift>G".to_owned())
+    }
 }
 
 //--------------------------------------------
@@ -410,6 +450,7 @@ impl NewsFlashShortcutWindow {
 
         ui_xml = Self::setup_shortcut(&ui_xml, "$SHORTCUT", settings.get_keybind_shortcut());
         ui_xml = Self::setup_shortcut(&ui_xml, "$REFRESH", settings.get_keybind_refresh());
+        ui_xml = Self::setup_shortcut(&ui_xml, "$UNDO", settings.get_keybind_undo());
         ui_xml = Self::setup_shortcut(&ui_xml, "$SEARCH", settings.get_keybind_search());
         ui_xml = Self::setup_shortcut(&ui_xml, "$QUIT", settings.get_keybind_quit());
         ui_xml = Self::setup_shortcut(&ui_xml, "$ALLARTICLES", settings.get_keybind_all_articles());
@@ -423,6 +464,9 @@ impl NewsFlashShortcutWindow {
         ui_xml = Self::setup_shortcut(&ui_xml, "$SCROLLUP", settings.get_keybind_article_view_up());
         ui_xml = Self::setup_shortcut(&ui_xml, "$SCROLLDOWN", settings.get_keybind_article_view_down());
         ui_xml = Self::setup_shortcut(&ui_xml, "$SCRAPCONTENT", settings.get_keybind_article_view_scrap());
+        ui_xml = Self::setup_shortcut(&ui_xml, "$FINDNEXT", settings.get_keybind_article_view_find_next());
+        ui_xml = Self::setup_shortcut(&ui_xml, "$FINDPREVIOUS", settings.get_keybind_article_view_find_prev());
+        ui_xml = Self::setup_shortcut(&ui_xml, "$FIND", settings.get_keybind_article_view_find());
         ui_xml = Self::setup_shortcut(&ui_xml, "$NEXTFEED", settings.get_keybind_feed_list_next());
         ui_xml = Self::setup_shortcut(&ui_xml, "$PREVFEED", settings.get_keybind_feed_list_prev());
         ui_xml = Self::setup_shortcut(
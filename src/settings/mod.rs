@@ -4,21 +4,33 @@ mod article_view;
 mod dialog;
 mod error;
 mod general;
+mod highlight;
 mod keybinding_editor;
 mod keybindings;
+mod reading_positions;
+mod saved_search;
 mod theme_chooser;
+mod theme_editor;
 
 pub use self::advanced::{AdvancedSettings, ProxyModel, ProxyProtocoll};
 use self::error::{SettingsError, SettingsErrorKind};
 use self::general::SyncInterval;
 use crate::article_view::ArticleTheme;
+use crate::content_page::HeaderSelection;
+use crate::sidebar::models::SidebarSelection;
 use article_list::ArticleListSettings;
+pub use article_list::ArticleListViewSettings;
 use article_view::ArticleViewSettings;
+use chrono::Utc;
 pub use dialog::SettingsDialog;
 use failure::ResultExt;
 use general::GeneralSettings;
+pub use highlight::Highlight;
+use highlight::Highlights;
 pub use keybindings::{Keybindings, NewsFlashShortcutWindow};
-use news_flash::models::ArticleOrder;
+use news_flash::models::{ArticleID, ArticleOrder, FatArticle, FeedID};
+use reading_positions::ReadingPositions;
+pub use saved_search::SavedSearch;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -32,6 +44,16 @@ pub struct Settings {
     article_list: ArticleListSettings,
     article_view: ArticleViewSettings,
     keybindings: Keybindings,
+    #[serde(default)]
+    saved_searches: Vec<SavedSearch>,
+    #[serde(default, skip_serializing)]
+    highlights: Vec<Highlight>,
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
+    highlight_store: Highlights,
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
+    reading_positions: ReadingPositions,
     #[serde(skip_serializing)]
     #[serde(skip_deserializing)]
     path: PathBuf,
@@ -44,6 +66,22 @@ impl Settings {
             let data = fs::read_to_string(&path).context(SettingsErrorKind::ReadFromDisk)?;
             let mut settings: Self = serde_json::from_str(&data).context(SettingsErrorKind::InvalidJsonContent)?;
             settings.path = path;
+            settings.reading_positions = ReadingPositions::open();
+            if !settings.article_view.reading_positions.is_empty() {
+                for (article_id, position) in settings.article_view.reading_positions.drain() {
+                    settings.reading_positions.set(&article_id, Some(position));
+                }
+                settings.write_reading_positions()?;
+                settings.write()?;
+            }
+            settings.highlight_store = Highlights::open();
+            if !settings.highlights.is_empty() {
+                for highlight in settings.highlights.drain(..) {
+                    settings.highlight_store.push(highlight);
+                }
+                settings.highlight_store.write()?;
+                settings.write()?;
+            }
             return Ok(settings);
         }
 
@@ -55,6 +93,10 @@ impl Settings {
             article_list: ArticleListSettings::default(),
             article_view: ArticleViewSettings::default(),
             keybindings: Keybindings::default(),
+            saved_searches: Vec::new(),
+            highlights: Vec::new(),
+            highlight_store: Highlights::open(),
+            reading_positions: ReadingPositions::open(),
             path,
         };
         settings.write().context(SettingsErrorKind::WriteToDisk)?;
@@ -97,6 +139,26 @@ impl Settings {
         Ok(())
     }
 
+    pub fn get_follow_system_color_scheme(&self) -> bool {
+        self.general.follow_system_color_scheme
+    }
+
+    pub fn set_follow_system_color_scheme(&mut self, follow: bool) -> Result<(), SettingsError> {
+        self.general.follow_system_color_scheme = follow;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_scrape_after_sync(&self) -> bool {
+        self.general.scrape_after_sync
+    }
+
+    pub fn set_scrape_after_sync(&mut self, scrape: bool) -> Result<(), SettingsError> {
+        self.general.scrape_after_sync = scrape;
+        self.write()?;
+        Ok(())
+    }
+
     pub fn get_article_list_order(&self) -> ArticleOrder {
         self.article_list.order.clone()
     }
@@ -107,6 +169,152 @@ impl Settings {
         Ok(())
     }
 
+    pub fn get_saved_searches(&self) -> Vec<SavedSearch> {
+        self.saved_searches.clone()
+    }
+
+    pub fn get_saved_search(&self, id: u32) -> Option<SavedSearch> {
+        self.saved_searches.iter().find(|search| search.id == id).cloned()
+    }
+
+    pub fn add_saved_search(
+        &mut self,
+        label: String,
+        query: String,
+        header: HeaderSelection,
+    ) -> Result<u32, SettingsError> {
+        let id = self
+            .saved_searches
+            .iter()
+            .map(|search| search.id + 1)
+            .max()
+            .unwrap_or(0);
+        self.saved_searches.push(SavedSearch {
+            id,
+            label,
+            query,
+            header,
+        });
+        self.write()?;
+        Ok(id)
+    }
+
+    pub fn rename_saved_search(&mut self, id: u32, label: String) -> Result<(), SettingsError> {
+        if let Some(search) = self.saved_searches.iter_mut().find(|search| search.id == id) {
+            search.label = label;
+        }
+        self.write()?;
+        Ok(())
+    }
+
+    /// Move the saved search `offset` positions up (negative) or down (positive) the list.
+    pub fn move_saved_search(&mut self, id: u32, offset: i32) -> Result<(), SettingsError> {
+        if let Some(index) = self.saved_searches.iter().position(|search| search.id == id) {
+            let last_index = self.saved_searches.len() as i32 - 1;
+            let new_index = (index as i32 + offset).max(0).min(last_index) as usize;
+            let search = self.saved_searches.remove(index);
+            self.saved_searches.insert(new_index, search);
+        }
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn delete_saved_search(&mut self, id: u32) -> Result<(), SettingsError> {
+        self.saved_searches.retain(|search| search.id != id);
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_highlights(&self) -> Vec<Highlight> {
+        self.highlight_store.all().to_vec()
+    }
+
+    pub fn get_article_highlights(&self, article_id: &ArticleID) -> Vec<Highlight> {
+        self.highlight_store
+            .all()
+            .iter()
+            .filter(|highlight| highlight.article_id == article_id.to_string())
+            .cloned()
+            .collect()
+    }
+
+    pub fn add_highlight(
+        &mut self,
+        article: &FatArticle,
+        text: String,
+        prefix: String,
+        note: Option<String>,
+    ) -> Result<u32, SettingsError> {
+        let id = self.highlight_store.next_id();
+        self.highlight_store.push(Highlight {
+            id,
+            article_id: article.article_id.to_string(),
+            article_title: article.title.clone(),
+            article_url: article.url.as_ref().map(|url| url.get().to_string()),
+            text,
+            prefix,
+            note,
+            created: Utc::now().timestamp(),
+        });
+        self.highlight_store.write()?;
+        Ok(id)
+    }
+
+    pub fn set_highlight_note(&mut self, id: u32, note: Option<String>) -> Result<(), SettingsError> {
+        if let Some(highlight) = self.highlight_store.get_mut(id) {
+            if highlight.note == note {
+                return Ok(());
+            }
+            highlight.note = note;
+        }
+        self.highlight_store.write()?;
+        Ok(())
+    }
+
+    pub fn delete_highlight(&mut self, id: u32) -> Result<(), SettingsError> {
+        self.highlight_store.remove(id);
+        self.highlight_store.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_list_page_size(&self) -> i64 {
+        self.article_list.page_size
+    }
+
+    pub fn set_article_list_page_size(&mut self, page_size: i64) -> Result<(), SettingsError> {
+        self.article_list.page_size = page_size;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_list_mark_read_on_scroll(&self) -> bool {
+        self.article_list.mark_read_on_scroll
+    }
+
+    pub fn set_article_list_mark_read_on_scroll(&mut self, mark_read: bool) -> Result<(), SettingsError> {
+        self.article_list.mark_read_on_scroll = mark_read;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_list_view(&self, view: &SidebarSelection) -> ArticleListViewSettings {
+        self.article_list
+            .views
+            .get(&view.view_key())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_article_list_view(
+        &mut self,
+        view: &SidebarSelection,
+        view_settings: ArticleListViewSettings,
+    ) -> Result<(), SettingsError> {
+        self.article_list.views.insert(view.view_key(), view_settings);
+        self.write()?;
+        Ok(())
+    }
+
     pub fn get_article_view_theme(&self) -> ArticleTheme {
         self.article_view.theme.clone()
     }
@@ -137,6 +345,260 @@ impl Settings {
         Ok(())
     }
 
+    pub fn get_article_view_user_stylesheet(&self) -> bool {
+        self.article_view.user_stylesheet
+    }
+
+    pub fn set_article_view_user_stylesheet(&mut self, enabled: bool) -> Result<(), SettingsError> {
+        self.article_view.user_stylesheet = enabled;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_privacy_mode(&self) -> bool {
+        self.article_view.privacy_mode
+    }
+
+    pub fn set_article_view_privacy_mode(&mut self, enabled: bool) -> Result<(), SettingsError> {
+        self.article_view.privacy_mode = enabled;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_data_saver(&self) -> bool {
+        self.article_view.data_saver
+    }
+
+    pub fn set_article_view_data_saver(&mut self, enabled: bool) -> Result<(), SettingsError> {
+        self.article_view.data_saver = enabled;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_data_saver_metered(&self) -> bool {
+        self.article_view.data_saver_metered
+    }
+
+    pub fn set_article_view_data_saver_metered(&mut self, enabled: bool) -> Result<(), SettingsError> {
+        self.article_view.data_saver_metered = enabled;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_feed_images(&self, feed_id: &FeedID) -> Option<bool> {
+        self.article_view.feed_images.get(&feed_id.to_string()).copied()
+    }
+
+    pub fn set_article_view_feed_images(
+        &mut self,
+        feed_id: &FeedID,
+        load_images: Option<bool>,
+    ) -> Result<(), SettingsError> {
+        match load_images {
+            Some(load_images) => self.article_view.feed_images.insert(feed_id.to_string(), load_images),
+            None => self.article_view.feed_images.remove(&feed_id.to_string()),
+        };
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_content_width(&self) -> u32 {
+        self.article_view.content_width
+    }
+
+    pub fn set_article_view_content_width(&mut self, content_width: u32) -> Result<(), SettingsError> {
+        self.article_view.content_width = content_width;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_line_height(&self) -> f64 {
+        self.article_view.line_height
+    }
+
+    pub fn set_article_view_line_height(&mut self, line_height: f64) -> Result<(), SettingsError> {
+        self.article_view.line_height = line_height;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_paragraph_spacing(&self) -> f64 {
+        self.article_view.paragraph_spacing
+    }
+
+    pub fn set_article_view_paragraph_spacing(&mut self, paragraph_spacing: f64) -> Result<(), SettingsError> {
+        self.article_view.paragraph_spacing = paragraph_spacing;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_justify(&self) -> bool {
+        self.article_view.justify
+    }
+
+    pub fn set_article_view_justify(&mut self, justify: bool) -> Result<(), SettingsError> {
+        self.article_view.justify = justify;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_hyphenate(&self) -> bool {
+        self.article_view.hyphenate
+    }
+
+    pub fn set_article_view_hyphenate(&mut self, hyphenate: bool) -> Result<(), SettingsError> {
+        self.article_view.hyphenate = hyphenate;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_feed_zoom(&self, feed_id: &FeedID) -> f64 {
+        self.article_view
+            .feed_zoom
+            .get(&feed_id.to_string())
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    pub fn set_article_view_feed_zoom(&mut self, feed_id: &FeedID, zoom: f64) -> Result<(), SettingsError> {
+        if (zoom - self.get_article_view_feed_zoom(feed_id)).abs() < std::f64::EPSILON {
+            return Ok(());
+        }
+        if (zoom - 1.0).abs() < std::f64::EPSILON {
+            self.article_view.feed_zoom.remove(&feed_id.to_string());
+        } else {
+            self.article_view.feed_zoom.insert(feed_id.to_string(), zoom);
+        }
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_reading_position(&self, article_id: &ArticleID) -> Option<f64> {
+        self.reading_positions.get(&article_id.to_string())
+    }
+
+    /// Only kept in memory, `write_reading_positions` saves them.
+    pub fn set_article_view_reading_position(&mut self, article_id: &ArticleID, position: Option<f64>) {
+        self.reading_positions.set(&article_id.to_string(), position);
+    }
+
+    pub fn get_article_view_reading_position_ids(&self) -> Vec<ArticleID> {
+        self.reading_positions
+            .article_ids()
+            .into_iter()
+            .map(|id| ArticleID::new(&id))
+            .collect()
+    }
+
+    /// Forget the reading positions of all articles except `keep`.
+    pub fn prune_article_view_reading_positions(&mut self, keep: &[ArticleID]) {
+        let keep: Vec<String> = keep.iter().map(|id| id.to_string()).collect();
+        self.reading_positions.retain(|id| keep.iter().any(|keep| keep == id));
+    }
+
+    pub fn write_reading_positions(&mut self) -> Result<(), SettingsError> {
+        self.reading_positions.write()
+    }
+
+    pub fn get_article_view_enclosure_position(&self, url: &str) -> Option<f64> {
+        self.article_view.enclosure_positions.get(url).copied()
+    }
+
+    pub fn get_article_view_playback_rate(&self) -> f64 {
+        self.article_view.playback_rate
+    }
+
+    /// Store the playback rate and the positions of all enclosures of an article at once,
+    /// a position of `None` means the enclosure was finished or never started.
+    pub fn set_article_view_playback_state(
+        &mut self,
+        playback_rate: f64,
+        positions: &[(String, Option<f64>)],
+    ) -> Result<(), SettingsError> {
+        let unchanged = (self.article_view.playback_rate - playback_rate).abs() < std::f64::EPSILON
+            && positions
+                .iter()
+                .all(|(url, position)| self.get_article_view_enclosure_position(url) == *position);
+        if unchanged {
+            return Ok(());
+        }
+        self.article_view.playback_rate = playback_rate;
+        for (url, position) in positions {
+            match position {
+                Some(position) => self.article_view.enclosure_positions.insert(url.clone(), *position),
+                None => self.article_view.enclosure_positions.remove(url),
+            };
+        }
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_read_aloud_continue(&self) -> bool {
+        self.article_view.read_aloud_continue
+    }
+
+    pub fn set_article_view_read_aloud_continue(&mut self, enabled: bool) -> Result<(), SettingsError> {
+        self.article_view.read_aloud_continue = enabled;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_internal_links(&self) -> bool {
+        self.article_view.internal_links
+    }
+
+    pub fn set_article_view_internal_links(&mut self, enabled: bool) -> Result<(), SettingsError> {
+        self.article_view.internal_links = enabled;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_feed_internal_links(&self, feed_id: &FeedID) -> Option<bool> {
+        self.article_view.feed_internal_links.get(&feed_id.to_string()).copied()
+    }
+
+    pub fn set_article_view_feed_internal_links(
+        &mut self,
+        feed_id: &FeedID,
+        internal: Option<bool>,
+    ) -> Result<(), SettingsError> {
+        match internal {
+            Some(internal) => self
+                .article_view
+                .feed_internal_links
+                .insert(feed_id.to_string(), internal),
+            None => self.article_view.feed_internal_links.remove(&feed_id.to_string()),
+        };
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_feed_full_content(&self, feed_id: &FeedID) -> bool {
+        self.article_view.feed_full_content.contains(&feed_id.to_string())
+    }
+
+    pub fn set_article_view_feed_full_content(
+        &mut self,
+        feed_id: &FeedID,
+        full_content: bool,
+    ) -> Result<(), SettingsError> {
+        if full_content {
+            self.article_view.feed_full_content.insert(feed_id.to_string());
+        } else {
+            self.article_view.feed_full_content.remove(&feed_id.to_string());
+        }
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_article_view_full_content_feeds(&self) -> Vec<FeedID> {
+        self.article_view
+            .feed_full_content
+            .iter()
+            .map(|feed_id| FeedID::new(feed_id))
+            .collect()
+    }
+
     pub fn get_keybind_shortcut(&self) -> Option<String> {
         self.keybindings.general.shortcut.clone()
     }
@@ -157,6 +619,16 @@ impl Settings {
         Ok(())
     }
 
+    pub fn get_keybind_undo(&self) -> Option<String> {
+        self.keybindings.general.undo.clone()
+    }
+
+    pub fn set_keybind_undo(&mut self, key: Option<String>) -> Result<(), SettingsError> {
+        self.keybindings.general.undo = key;
+        self.write()?;
+        Ok(())
+    }
+
     pub fn get_keybind_search(&self) -> Option<String> {
         self.keybindings.general.search.clone()
     }
@@ -237,6 +709,36 @@ impl Settings {
         Ok(())
     }
 
+    pub fn get_keybind_article_view_find(&self) -> Option<String> {
+        self.keybindings.article_view.find.clone()
+    }
+
+    pub fn set_keybind_article_view_find(&mut self, key: Option<String>) -> Result<(), SettingsError> {
+        self.keybindings.article_view.find = key;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_keybind_article_view_find_next(&self) -> Option<String> {
+        self.keybindings.article_view.find_next.clone()
+    }
+
+    pub fn set_keybind_article_view_find_next(&mut self, key: Option<String>) -> Result<(), SettingsError> {
+        self.keybindings.article_view.find_next = key;
+        self.write()?;
+        Ok(())
+    }
+
+    pub fn get_keybind_article_view_find_prev(&self) -> Option<String> {
+        self.keybindings.article_view.find_previous.clone()
+    }
+
+    pub fn set_keybind_article_view_find_prev(&mut self, key: Option<String>) -> Result<(), SettingsError> {
+        self.keybindings.article_view.find_previous = key;
+        self.write()?;
+        Ok(())
+    }
+
     pub fn get_keybind_article_list_next(&self) -> Option<String> {
         self.keybindings.article_list.next.clone()
     }
@@ -0,0 +1,100 @@
+use super::error::{SettingsError, SettingsErrorKind};
+use failure::ResultExt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+static POSITIONS_NAME: &str = "reading_positions.json";
+static MAX_POSITIONS: usize = 500;
+
+/// Scroll positions of articles that were left unfinished.
+/// Kept apart from the settings file and limited to the most recently read articles.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReadingPositions {
+    /// (article id, scroll position), least recently read first
+    positions: Vec<(String, f64)>,
+    #[serde(skip)]
+    changed: bool,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl ReadingPositions {
+    pub fn open() -> Self {
+        let path = crate::app::DATA_DIR.join(POSITIONS_NAME);
+        let mut positions = fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<Self>(&data).ok())
+            .unwrap_or_default();
+        positions.path = path;
+        positions
+    }
+
+    pub fn get(&self, article_id: &str) -> Option<f64> {
+        self.positions
+            .iter()
+            .find(|(id, _)| id == article_id)
+            .map(|(_, position)| *position)
+    }
+
+    pub fn set(&mut self, article_id: &str, position: Option<f64>) {
+        let index = self.positions.iter().position(|(id, _)| id == article_id);
+        if index.is_none() && position.is_none() {
+            return;
+        }
+        if let Some(index) = index {
+            self.positions.remove(index);
+        }
+        if let Some(position) = position {
+            self.positions.push((article_id.to_owned(), position));
+            if self.positions.len() > MAX_POSITIONS {
+                let overflow = self.positions.len() - MAX_POSITIONS;
+                self.positions.drain(..overflow);
+            }
+        }
+        self.changed = true;
+    }
+
+    pub fn article_ids(&self) -> Vec<String> {
+        self.positions.iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    pub fn retain<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        let count = self.positions.len();
+        self.positions.retain(|(id, _)| keep(id));
+        if self.positions.len() != count {
+            self.changed = true;
+        }
+    }
+
+    /// Write the positions to disk if they changed since the last time.
+    pub fn write(&mut self) -> Result<(), SettingsError> {
+        if !self.changed {
+            return Ok(());
+        }
+        fs::create_dir_all(crate::app::DATA_DIR.as_path()).context(SettingsErrorKind::CreateDirectory)?;
+        let data = serde_json::to_string(self).context(SettingsErrorKind::Serialize)?;
+        fs::write(&self.path, data).context(SettingsErrorKind::WriteToDisk)?;
+        self.changed = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReadingPositions, MAX_POSITIONS};
+
+    #[test]
+    fn keeps_most_recently_read() {
+        let mut positions = ReadingPositions::default();
+        for i in 0..=MAX_POSITIONS {
+            positions.set(&i.to_string(), Some(i as f64));
+        }
+        positions.set("1", Some(10.0));
+
+        assert_eq!(positions.article_ids().len(), MAX_POSITIONS);
+        assert_eq!(positions.get("0"), None);
+        assert_eq!(positions.get("1"), Some(10.0));
+        assert_eq!(positions.article_ids().last().map(String::as_str), Some("1"));
+    }
+}
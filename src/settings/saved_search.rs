@@ -0,0 +1,37 @@
+use crate::content_page::HeaderSelection;
+use crate::search_query::{SearchQuery, SearchQueryError};
+use news_flash::models::{Marked, Read};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: u32,
+    pub label: String,
+    pub query: String,
+    pub header: HeaderSelection,
+}
+
+impl SavedSearch {
+    /// The stored query narrowed down by the stored header filter.
+    /// `None` if both contradict each other, e.g. "is:read" saved while showing only unread articles.
+    pub fn search_query(&self) -> Result<Option<SearchQuery>, SearchQueryError> {
+        let mut query = SearchQuery::parse(&self.query)?;
+        let header_query = match self.header {
+            HeaderSelection::All => SearchQuery::default(),
+            HeaderSelection::Unread => SearchQuery {
+                unread: Some(Read::Unread),
+                ..SearchQuery::default()
+            },
+            HeaderSelection::Marked => SearchQuery {
+                marked: Some(Marked::Marked),
+                ..SearchQuery::default()
+            },
+        };
+
+        if query.combine(header_query) {
+            Ok(Some(query))
+        } else {
+            Ok(None)
+        }
+    }
+}
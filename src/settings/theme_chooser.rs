@@ -1,15 +1,22 @@
+use super::theme_editor::{ThemeEditor, ThemeEditorTarget};
 use crate::app::Action;
-use crate::article_view::{ArticleTheme, ArticleView};
+use crate::article_view::{ArticleTheme, ArticleView, UserThemes};
 use crate::i18n::i18n;
 use crate::settings::Settings;
 use crate::util::{BuilderHelper, Util};
 use chrono::Utc;
-use glib::{clone, object::IsA, Sender};
-use gtk::{Inhibit, ListBox, ListBoxExt, ListBoxRow, ListBoxRowExt, Popover, PopoverExt, Widget, WidgetExt};
+use glib::{clone, object::Cast, object::IsA, Sender};
+use gtk::{
+    Align, Button, ButtonExt, ContainerExt, GtkWindowExt, IconSize, Inhibit, ListBox, ListBoxExt, ListBoxRow,
+    ListBoxRowExt, Overlay, OverlayExt, Popover, PopoverExt, Separator, StyleContextExt, Widget, WidgetExt, Window,
+};
 use news_flash::models::{ArticleID, FatArticle, FeedID, Marked, Read};
 use parking_lot::RwLock;
+use std::fs;
 use std::sync::Arc;
-use webkit2gtk::{WebView, WebViewExt};
+use webkit2gtk::{Settings as WebkitSettings, WebView, WebViewExt};
+
+static USER_THEME_PREFIX: &str = "user:";
 
 pub struct ThemeChooser {
     widget: Popover,
@@ -21,22 +28,9 @@ impl ThemeChooser {
 
         let pop = builder.get::<Popover>("popover");
         pop.set_relative_to(Some(parent));
+        let parent = parent.clone().upcast::<Widget>();
 
-        let mut demo_article = FatArticle {
-            article_id: ArticleID::new("demo"),
-            title: None,
-            author: None,
-            feed_id: FeedID::new("demo_feed"),
-            direction: None,
-            date: Utc::now().naive_utc(),
-            marked: Marked::Unmarked,
-            unread: Read::Unread,
-            url: None,
-            summary: None,
-            html: None,
-            scraped_content: None,
-            plain_text: None,
-        };
+        let mut demo_article = Self::demo_article("");
 
         Self::prepare_theme_selection(
             &builder,
@@ -80,10 +74,26 @@ impl ThemeChooser {
         );
 
         let theme_list = builder.get::<ListBox>("theme_list");
+        let webkit_settings = builder.get::<WebkitSettings>("settings1");
+        for id in UserThemes::list() {
+            Self::add_user_theme_row(
+                &theme_list,
+                &webkit_settings,
+                &pop,
+                &parent,
+                sender,
+                settings,
+                &mut demo_article,
+                &id,
+            );
+        }
+
         theme_list.connect_row_activated(
             clone!(@strong sender, @weak settings, @weak pop => @default-panic, move |_list, row| {
                 let row_name = row.get_widget_name();
-                let result = if "default" == row_name {
+                let result = if let Some(id) = row_name.strip_prefix(USER_THEME_PREFIX) {
+                    settings.write().set_article_view_theme(ArticleTheme::User(id.to_owned()))
+                } else if "default" == row_name {
                     settings.write().set_article_view_theme(ArticleTheme::Default)
                 } else if "spring" == row_name {
                     settings.write().set_article_view_theme(ArticleTheme::Spring)
@@ -107,6 +117,45 @@ impl ThemeChooser {
             }),
         );
 
+        let new_theme_button = builder.get::<Button>("new_theme_button");
+        new_theme_button.connect_clicked(clone!(
+            @weak parent,
+            @weak settings,
+            @weak pop,
+            @strong sender => @default-panic, move |_button|
+        {
+            pop.popdown();
+            match UserThemes::create(&i18n("Custom Theme")) {
+                Ok(id) => {
+                    if settings.write().set_article_view_theme(ArticleTheme::User(id.clone())).is_err() {
+                        Util::send(
+                            &sender,
+                            Action::ErrorSimpleMessage("Failed to set theme setting.".to_owned()),
+                        );
+                    }
+                    Self::open_editor(&parent, ThemeEditorTarget::Theme(id), &sender, &settings);
+                }
+                Err(_) => Util::send(
+                    &sender,
+                    Action::ErrorSimpleMessage("Failed to create new theme.".to_owned()),
+                ),
+            }
+        }));
+
+        let open_folder_button = builder.get::<Button>("open_folder_button");
+        open_folder_button.connect_clicked(clone!(@weak pop, @strong sender => @default-panic, move |_button| {
+            pop.popdown();
+            let dir = UserThemes::dir();
+            if fs::create_dir_all(&dir).is_err() {
+                Util::send(
+                    &sender,
+                    Action::ErrorSimpleMessage("Failed to create theme folder.".to_owned()),
+                );
+                return;
+            }
+            Util::send(&sender, Action::OpenUrlInDefaultBrowser(format!("file://{}", dir.display())));
+        }));
+
         ThemeChooser { widget: pop }
     }
 
@@ -114,6 +163,105 @@ impl ThemeChooser {
         self.widget.clone()
     }
 
+    pub fn demo_article(title: &str) -> FatArticle {
+        FatArticle {
+            article_id: ArticleID::new("demo"),
+            title: Some(title.to_owned()),
+            author: None,
+            feed_id: FeedID::new("demo_feed"),
+            direction: None,
+            date: Utc::now().naive_utc(),
+            marked: Marked::Unmarked,
+            unread: Read::Unread,
+            url: None,
+            summary: None,
+            html: None,
+            scraped_content: None,
+            plain_text: None,
+        }
+    }
+
+    pub fn open_editor(
+        parent: &Widget,
+        target: ThemeEditorTarget,
+        sender: &Sender<Action>,
+        settings: &Arc<RwLock<Settings>>,
+    ) {
+        if let Some(window) = parent
+            .get_toplevel()
+            .and_then(|toplevel| toplevel.downcast::<Window>().ok())
+        {
+            let editor = ThemeEditor::new(&window, target, sender, settings);
+            editor.widget().present();
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_user_theme_row(
+        theme_list: &ListBox,
+        webkit_settings: &WebkitSettings,
+        pop: &Popover,
+        parent: &Widget,
+        sender: &Sender<Action>,
+        settings: &Arc<RwLock<Settings>>,
+        article: &mut FatArticle,
+        id: &str,
+    ) {
+        let separator_row = ListBoxRow::new();
+        separator_row.set_activatable(false);
+        separator_row.set_selectable(false);
+        separator_row.get_style_context().add_class("separator-row");
+        separator_row.add(&Separator::new(gtk::Orientation::Horizontal));
+        theme_list.add(&separator_row);
+
+        let view = WebView::with_settings(webkit_settings);
+        let edit_button = Button::from_icon_name(Some("document-edit-symbolic"), IconSize::Button);
+        edit_button.set_tooltip_text(Some(&i18n("Edit Theme")));
+        edit_button.set_halign(Align::End);
+        edit_button.set_valign(Align::Start);
+        edit_button.set_margin_top(6);
+        edit_button.set_margin_end(6);
+        edit_button.get_style_context().add_class("osd");
+        let target = ThemeEditorTarget::Theme(id.to_owned());
+        edit_button.connect_clicked(clone!(
+            @weak parent,
+            @weak settings,
+            @weak pop,
+            @strong sender => @default-panic, move |_button|
+        {
+            pop.popdown();
+            Self::open_editor(&parent, target.clone(), &sender, &settings);
+        }));
+
+        let overlay = Overlay::new();
+        overlay.add(&view);
+        overlay.add_overlay(&edit_button);
+
+        let row = ListBoxRow::new();
+        row.set_widget_name(&format!("{}{}", USER_THEME_PREFIX, id));
+        row.set_size_request(-1, 80);
+        row.add(&overlay);
+        row.show_all();
+        separator_row.show_all();
+        theme_list.add(&row);
+
+        view.connect_button_press_event(clone!(@weak row => @default-panic, move |_view, _event| {
+            row.emit_activate();
+            Inhibit(true)
+        }));
+        article.title = Some(id.to_owned());
+        let html = ArticleView::build_article_static(
+            "theme_preview",
+            article,
+            "Feed Name",
+            settings,
+            Some(ArticleTheme::User(id.to_owned())),
+            Some(10240),
+            false,
+        );
+        view.load_html(&html, None);
+    }
+
     fn prepare_theme_selection(
         builder: &BuilderHelper,
         settings: &Arc<RwLock<Settings>>,
@@ -0,0 +1,147 @@
+use super::theme_chooser::ThemeChooser;
+use crate::app::Action;
+use crate::article_view::{ArticleTheme, ArticleView, UserThemes};
+use crate::i18n::i18n;
+use crate::settings::Settings;
+use crate::util::{BuilderHelper, Util};
+use glib::{clone, object::IsA, Sender};
+use gtk::{
+    Button, ButtonExt, Dialog, DialogExt, GtkWindowExt, HeaderBar, HeaderBarExt, TextBuffer, TextBufferExt, TextView,
+    TextViewExt, WidgetExt, Window,
+};
+use news_flash::models::FatArticle;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use webkit2gtk::{WebView, WebViewExt};
+
+#[derive(Debug, Clone)]
+pub enum ThemeEditorTarget {
+    Theme(String),
+    Stylesheet,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThemeEditor {
+    widget: Dialog,
+}
+
+impl ThemeEditor {
+    pub fn new<D: IsA<Window> + GtkWindowExt>(
+        parent: &D,
+        target: ThemeEditorTarget,
+        sender: &Sender<Action>,
+        settings: &Arc<RwLock<Settings>>,
+    ) -> Self {
+        let builder = BuilderHelper::new("theme_editor");
+        let dialog = builder.get::<Dialog>("dialog");
+        dialog.set_transient_for(Some(parent));
+
+        let headerbar = builder.get::<HeaderBar>("headerbar");
+        let text_view = builder.get::<TextView>("text_view");
+        let preview = builder.get::<WebView>("preview");
+        let save_button = builder.get::<Button>("save_button");
+        let cancel_button = builder.get::<Button>("cancel_button");
+
+        let css = match &target {
+            ThemeEditorTarget::Theme(id) => {
+                headerbar.set_title(Some(&i18n("Edit Theme")));
+                headerbar.set_subtitle(Some(id));
+                UserThemes::load(id).unwrap_or_default()
+            }
+            ThemeEditorTarget::Stylesheet => {
+                headerbar.set_title(Some(&i18n("Custom Stylesheet")));
+                UserThemes::load_stylesheet()
+            }
+        };
+
+        let demo_article = ThemeChooser::demo_article(&i18n("Preview"));
+        let buffer = text_view.get_buffer().expect("TextView without buffer");
+        buffer.set_text(&css);
+        Self::update_preview(&preview, &target, &css, &demo_article, settings);
+
+        buffer.connect_changed(clone!(
+            @weak preview,
+            @weak settings,
+            @strong target => @default-panic, move |buffer|
+        {
+            Self::update_preview(&preview, &target, &Self::buffer_text(buffer), &demo_article, &settings);
+        }));
+
+        save_button.connect_clicked(clone!(
+            @weak dialog,
+            @weak buffer,
+            @strong sender,
+            @strong target => @default-panic, move |_button|
+        {
+            let css = Self::buffer_text(&buffer);
+            let result = match &target {
+                ThemeEditorTarget::Theme(id) => UserThemes::save(id, &css),
+                ThemeEditorTarget::Stylesheet => UserThemes::save_stylesheet(&css),
+            };
+            if result.is_ok() {
+                Util::send(&sender, Action::RedrawArticle);
+            } else {
+                Util::send(&sender, Action::ErrorSimpleMessage("Failed to save stylesheet.".to_owned()));
+            }
+            dialog.emit_close();
+        }));
+        cancel_button.connect_clicked(clone!(@weak dialog => @default-panic, move |_button| {
+            dialog.emit_close();
+        }));
+
+        ThemeEditor { widget: dialog }
+    }
+
+    pub fn widget(&self) -> Dialog {
+        self.widget.clone()
+    }
+
+    fn buffer_text(buffer: &TextBuffer) -> String {
+        let (start, end) = buffer.get_bounds();
+        buffer
+            .get_text(&start, &end, false)
+            .map(|text| text.to_string())
+            .unwrap_or_default()
+    }
+
+    fn update_preview(
+        preview: &WebView,
+        target: &ThemeEditorTarget,
+        css: &str,
+        article: &FatArticle,
+        settings: &Arc<RwLock<Settings>>,
+    ) {
+        let html = match target {
+            ThemeEditorTarget::Theme(id) => {
+                let user_css = UserThemes::stylesheet_css(settings);
+                ArticleView::build_article_with_css(
+                    "theme_preview",
+                    article,
+                    "Feed Name",
+                    settings,
+                    &ArticleTheme::User(id.clone()),
+                    Some(css),
+                    user_css.as_deref(),
+                    None,
+                    false,
+                )
+            }
+            ThemeEditorTarget::Stylesheet => {
+                let theme = settings.read().get_article_view_theme();
+                let theme_css = UserThemes::theme_css(&theme);
+                ArticleView::build_article_with_css(
+                    "theme_preview",
+                    article,
+                    "Feed Name",
+                    settings,
+                    &theme,
+                    theme_css.as_deref(),
+                    Some(css),
+                    None,
+                    false,
+                )
+            }
+        };
+        preview.load_html(&html, None);
+    }
+}
@@ -522,7 +522,7 @@ impl FeedListTree {
                 }
             }
         }
-        SidebarIterateItem::SelectAll
+        SidebarIterateItem::SelectPodcasts
     }
 
     #[allow(dead_code)]
@@ -53,11 +53,14 @@ impl SidebarFooter {
     pub fn update(&self) {
         self.add_button
             .set_sensitive(!self.state.read().get_offline() && *self.support_mutation.read());
-        self.remove_button.set_sensitive(
-            !self.state.read().get_offline()
-                && *self.support_mutation.read()
-                && *self.sidebar_selection.read() != SidebarSelection::All,
-        );
+        // saved searches only live in the local settings
+        let removable = match &*self.sidebar_selection.read() {
+            SidebarSelection::All | SidebarSelection::Podcasts => false,
+            SidebarSelection::SavedSearch(_, _) => true,
+            _ => *self.support_mutation.read(),
+        };
+        self.remove_button
+            .set_sensitive(!self.state.read().get_offline() && removable);
     }
 
     pub fn update_features(&self, features: &Arc<RwLock<Option<PluginCapabilities>>>) {
@@ -2,6 +2,7 @@ mod error;
 mod feed_list;
 mod footer;
 pub mod models;
+mod saved_search_list;
 mod tag_list;
 
 use self::error::{SidebarError, SidebarErrorKind};
@@ -9,6 +10,7 @@ use self::footer::SidebarFooter;
 use crate::app::Action;
 use crate::i18n::i18n;
 use crate::main_window_state::MainWindowState;
+use crate::settings::SavedSearch;
 use crate::util::{BuilderHelper, GtkUtil, Util};
 use failure::ResultExt;
 pub use feed_list::models::{FeedListDndAction, FeedListItemID, FeedListTree};
@@ -24,6 +26,7 @@ use models::SidebarSelection;
 use news_flash::models::{PluginCapabilities, PluginID, PluginIcon};
 use news_flash::NewsFlash;
 use parking_lot::RwLock;
+use saved_search_list::SavedSearchList;
 use std::sync::Arc;
 pub use tag_list::models::TagListModel;
 use tag_list::TagList;
@@ -34,21 +37,27 @@ pub struct SideBar {
     state: Arc<RwLock<MainWindowState>>,
     sidebar: Box,
     tags_box: Box,
+    saved_searches_box: Box,
     logo: Image,
     all_event_box: EventBox,
     all_label: Label,
+    podcasts_event_box: EventBox,
     item_count: i64,
     service_label: Label,
     scale_factor: i32,
     pub feed_list: Arc<RwLock<FeedList>>,
     pub tag_list: Arc<RwLock<TagList>>,
+    pub saved_search_list: Arc<RwLock<SavedSearchList>>,
     selection: Arc<RwLock<SidebarSelection>>,
     categories_expander: Image,
     tags_expander: Image,
+    saved_searches_expander: Image,
     categories_revealer: Revealer,
     tags_revealer: Revealer,
+    saved_searches_revealer: Revealer,
     expanded_categories: Arc<RwLock<bool>>,
     expanded_tags: Arc<RwLock<bool>>,
+    expanded_saved_searches: Arc<RwLock<bool>>,
     delayed_all_selection: Arc<RwLock<Option<u32>>>,
     pub footer: Arc<SidebarFooter>,
 }
@@ -63,6 +72,7 @@ impl SideBar {
 
         let sidebar = builder.get::<Box>("toplevel");
         let tags_box = builder.get::<Box>("tags");
+        let saved_searches_box = builder.get::<Box>("saved_searches");
         let logo = builder.get::<Image>("logo");
         let all_label = builder.get::<Label>("unread_count_all");
         let item_count = 0;
@@ -71,11 +81,16 @@ impl SideBar {
         let categories_expander = builder.get::<Image>("categories_expander");
         let tags_event_box = builder.get::<EventBox>("tags_event_box");
         let tags_expander = builder.get::<Image>("tags_expander");
+        let saved_searches_event_box = builder.get::<EventBox>("saved_searches_event_box");
+        let saved_searches_expander = builder.get::<Image>("saved_searches_expander");
         let categories_revealer = builder.get::<Revealer>("categories_revealer");
         let tags_revealer = builder.get::<Revealer>("tags_revealer");
+        let saved_searches_revealer = builder.get::<Revealer>("saved_searches_revealer");
         let all_event_box = builder.get::<EventBox>("all_event_box");
+        let podcasts_event_box = builder.get::<EventBox>("podcasts_event_box");
         let feed_list_box = builder.get::<Box>("feed_list_box");
         let tag_list_box = builder.get::<Box>("tags_list_box");
+        let saved_search_list_box = builder.get::<Box>("saved_searches_list_box");
         let sidebar_scroll = builder.get::<ScrolledWindow>("sidebar_scroll");
 
         let selection_handle = Arc::new(RwLock::new(SidebarSelection::All));
@@ -83,6 +98,7 @@ impl SideBar {
 
         let feed_list = FeedList::new(&sidebar_scroll, state, sender.clone());
         let tag_list = TagList::new(state);
+        let saved_search_list = SavedSearchList::new(state, &sender);
         let footer = Arc::new(SidebarFooter::new(
             &builder,
             state,
@@ -93,9 +109,11 @@ impl SideBar {
 
         let feed_list_handle = Arc::new(RwLock::new(feed_list));
         let tag_list_handle = Arc::new(RwLock::new(tag_list));
+        let saved_search_list_handle = Arc::new(RwLock::new(saved_search_list));
 
         feed_list_box.pack_start(&feed_list_handle.read().widget(), false, true, 0);
         tag_list_box.pack_start(&tag_list_handle.read().widget(), false, true, 0);
+        saved_search_list_box.pack_start(&saved_search_list_handle.read().widget(), false, true, 0);
 
         feed_list_handle.read().widget().connect_row_activated(
             clone!(@strong sender, @weak footer, @weak selection_handle => @default-panic, move |_list, _row| {
@@ -109,7 +127,9 @@ impl SideBar {
 
         feed_list_handle.read().widget().connect_row_selected(clone!(
             @weak all_event_box,
+            @weak podcasts_event_box,
             @weak tag_list_handle,
+            @weak saved_search_list_handle,
             @strong feed_list_handle as self_handle,
             @strong selection_handle,
             @weak delayed_all_selection => @default-panic, move |_list, row|
@@ -118,9 +138,10 @@ impl SideBar {
             if row.is_none() {
                 return;
             }
-            // deselect 'all' & tag_list
-            Self::deselect_all_button(&all_event_box, &delayed_all_selection);
+            // deselect 'all', 'podcasts', tag_list & saved_search_list
+            Self::deselect_fixed_buttons(&all_event_box, &podcasts_event_box, &delayed_all_selection);
             tag_list_handle.read().deselect();
+            saved_search_list_handle.read().deselect();
 
             if let Some((item, title)) = self_handle.read().get_selection() {
                 let selection = SidebarSelection::from_feed_list_selection(item, title);
@@ -140,7 +161,9 @@ impl SideBar {
 
         tag_list_handle.read().widget().connect_row_selected(clone!(
             @weak all_event_box,
+            @weak podcasts_event_box,
             @weak feed_list_handle,
+            @weak saved_search_list_handle,
             @strong tag_list_handle,
             @weak selection_handle,
             @weak delayed_all_selection => @default-panic, move |_list, row| {
@@ -148,9 +171,10 @@ impl SideBar {
             if row.is_none() {
                 return;
             }
-            // deselect 'all' & tag_list
-            Self::deselect_all_button(&all_event_box, &delayed_all_selection);
+            // deselect 'all', 'podcasts', feed_list & saved_search_list
+            Self::deselect_fixed_buttons(&all_event_box, &podcasts_event_box, &delayed_all_selection);
             feed_list_handle.read().deselect();
+            saved_search_list_handle.read().deselect();
 
             if let Some((selected_id, title)) = tag_list_handle.read().get_selection() {
                 let selection = SidebarSelection::Tag(selected_id, title);
@@ -158,10 +182,43 @@ impl SideBar {
             }
         }));
 
+        saved_search_list_handle.read().widget().connect_row_activated(
+            clone!(@weak selection_handle, @weak footer, @strong sender => @default-panic, move |_list, _row| {
+                Util::send(
+                    &sender,
+                    Action::SidebarSelection((*selection_handle.read()).clone()),
+                );
+                footer.update();
+            }),
+        );
+
+        saved_search_list_handle.read().widget().connect_row_selected(clone!(
+            @weak all_event_box,
+            @weak podcasts_event_box,
+            @weak feed_list_handle,
+            @weak tag_list_handle,
+            @strong saved_search_list_handle,
+            @weak selection_handle,
+            @weak delayed_all_selection => @default-panic, move |_list, row| {
+            // do nothing if selection was cleared
+            if row.is_none() {
+                return;
+            }
+            // deselect 'all', 'podcasts', feed_list & tag_list
+            Self::deselect_fixed_buttons(&all_event_box, &podcasts_event_box, &delayed_all_selection);
+            feed_list_handle.read().deselect();
+            tag_list_handle.read().deselect();
+
+            if let Some((id, title)) = saved_search_list_handle.read().get_selection() {
+                *selection_handle.write() = SidebarSelection::SavedSearch(id, title);
+            }
+        }));
+
         let scale = GtkUtil::get_scale(&sidebar);
 
         let expanded_categories = Arc::new(RwLock::new(true));
         let expanded_tags = Arc::new(RwLock::new(false));
+        let expanded_saved_searches = Arc::new(RwLock::new(true));
 
         Self::setup_expander(
             &categories_event_box,
@@ -170,11 +227,33 @@ impl SideBar {
             &expanded_categories,
         );
         Self::setup_expander(&tags_event_box, &tags_expander, &tags_revealer, &expanded_tags);
-        Self::setup_all_button(
+        Self::setup_expander(
+            &saved_searches_event_box,
+            &saved_searches_expander,
+            &saved_searches_revealer,
+            &expanded_saved_searches,
+        );
+        all_event_box.get_style_context().add_class("selected");
+        Self::setup_fixed_button(
+            &all_event_box,
+            SidebarSelection::All,
+            &podcasts_event_box,
+            &sender,
+            feed_list_handle.clone(),
+            tag_list_handle.clone(),
+            saved_search_list_handle.clone(),
+            selection_handle.clone(),
+            footer.clone(),
+            &delayed_all_selection,
+        );
+        Self::setup_fixed_button(
+            &podcasts_event_box,
+            SidebarSelection::Podcasts,
             &all_event_box,
             &sender,
             feed_list_handle.clone(),
             tag_list_handle.clone(),
+            saved_search_list_handle.clone(),
             selection_handle.clone(),
             footer.clone(),
             &delayed_all_selection,
@@ -185,21 +264,27 @@ impl SideBar {
             state: state.clone(),
             sidebar,
             tags_box,
+            saved_searches_box,
             logo,
             all_event_box,
             all_label,
+            podcasts_event_box,
             item_count,
             service_label,
             scale_factor: scale,
             feed_list: feed_list_handle,
             tag_list: tag_list_handle,
+            saved_search_list: saved_search_list_handle,
             selection: selection_handle,
             categories_expander,
             tags_expander,
+            saved_searches_expander,
             categories_revealer,
             tags_revealer,
+            saved_searches_revealer,
             expanded_categories,
             expanded_tags,
+            expanded_saved_searches,
             delayed_all_selection,
             footer,
         }
@@ -229,6 +314,16 @@ impl SideBar {
         self.tags_box.show();
     }
 
+    pub fn update_saved_searches(&self, saved_searches: Vec<(SavedSearch, i64)>) {
+        let is_empty = saved_searches.is_empty();
+        self.saved_search_list.read().update(saved_searches);
+        if is_empty {
+            self.saved_searches_box.hide();
+        } else {
+            self.saved_searches_box.show_all();
+        }
+    }
+
     pub fn update_all(&mut self, item_count: i64) {
         self.item_count = item_count;
         self.update_all_label();
@@ -328,17 +423,20 @@ impl SideBar {
         *expanded.write() = expand;
     }
 
-    fn setup_all_button(
+    /// 'All Articles' and 'Podcasts' sit above the lists, only one of them can be selected at a time.
+    #[allow(clippy::too_many_arguments)]
+    fn setup_fixed_button(
         event_box: &EventBox,
+        selection: SidebarSelection,
+        other_event_box: &EventBox,
         sender: &Sender<Action>,
         feed_list_handle: Arc<RwLock<FeedList>>,
         tag_list_handle: Arc<RwLock<TagList>>,
+        saved_search_list_handle: Arc<RwLock<SavedSearchList>>,
         selection_handle: Arc<RwLock<SidebarSelection>>,
         footer: Arc<SidebarFooter>,
         delayed_selection: &Arc<RwLock<Option<u32>>>,
     ) {
-        let context = event_box.get_style_context();
-        context.add_class("selected");
         event_box.set_events(EventMask::BUTTON_PRESS_MASK);
         event_box.set_events(EventMask::ENTER_NOTIFY_MASK);
         event_box.set_events(EventMask::LEAVE_NOTIFY_MASK);
@@ -356,6 +454,7 @@ impl SideBar {
         event_box.connect_button_press_event(clone!(
             @strong sender,
             @weak footer,
+            @weak other_event_box,
             @weak delayed_selection => @default-panic, move |widget, event| {
             if event.get_button() != 1 {
                 return Inhibit(false);
@@ -367,8 +466,10 @@ impl SideBar {
 
             feed_list_handle.read().deselect();
             tag_list_handle.read().deselect();
+            saved_search_list_handle.read().deselect();
+            other_event_box.get_style_context().remove_class("selected");
 
-            Self::select_all_button(widget, &sender, &selection_handle, &delayed_selection);
+            Self::select_fixed_button(widget, selection.clone(), &sender, &selection_handle, &delayed_selection);
             footer.update();
             Inhibit(false)
         }));
@@ -377,18 +478,20 @@ impl SideBar {
     pub fn select_all_button_no_update(&self) {
         *self.selection.write() = SidebarSelection::All;
         GtkUtil::remove_source(*self.delayed_all_selection.read());
+        self.podcasts_event_box.get_style_context().remove_class("selected");
         let context = self.all_event_box.get_style_context();
         context.add_class("selected");
     }
 
-    fn select_all_button(
-        all_event_box: &EventBox,
+    fn select_fixed_button(
+        event_box: &EventBox,
+        selection: SidebarSelection,
         sender: &Sender<Action>,
         selection_handle: &Arc<RwLock<SidebarSelection>>,
         delayed_selection: &Arc<RwLock<Option<u32>>>,
     ) {
-        *selection_handle.write() = SidebarSelection::All;
-        let context = all_event_box.get_style_context();
+        *selection_handle.write() = selection.clone();
+        let context = event_box.get_style_context();
         context.add_class("selected");
 
         GtkUtil::remove_source(*delayed_selection.read());
@@ -397,7 +500,7 @@ impl SideBar {
                 50,
                 clone!(
                     @strong sender, @weak delayed_selection as source_id => @default-panic, move || {
-                    Util::send(&sender, Action::SidebarSelection(SidebarSelection::All));
+                    Util::send(&sender, Action::SidebarSelection(selection.clone()));
                     *source_id.write() = None;
                     Continue(false)
                 }),
@@ -406,31 +509,39 @@ impl SideBar {
         );
     }
 
-    fn deselect_all_button(all_event_box: &EventBox, delayed_selection: &Arc<RwLock<Option<u32>>>) {
-        let context = all_event_box.get_style_context();
-        context.remove_class("selected");
+    fn deselect_fixed_buttons(
+        all_event_box: &EventBox,
+        podcasts_event_box: &EventBox,
+        delayed_selection: &Arc<RwLock<Option<u32>>>,
+    ) {
+        all_event_box.get_style_context().remove_class("selected");
+        podcasts_event_box.get_style_context().remove_class("selected");
         GtkUtil::remove_source(*delayed_selection.read());
         *delayed_selection.write() = None;
     }
 
     pub fn select_next_item(&self) -> Result<(), SidebarError> {
         let select_next = match *self.selection.read() {
-            SidebarSelection::All => SidebarIterateItem::FeedListSelectFirstItem,
+            SidebarSelection::All => SidebarIterateItem::SelectPodcasts,
+            SidebarSelection::Podcasts => SidebarIterateItem::FeedListSelectFirstItem,
             SidebarSelection::Category(_, _) | SidebarSelection::Feed(_, _, _) => {
                 self.feed_list.read().select_next_item()
             }
             SidebarSelection::Tag(_, _) => self.tag_list.read().get_next_item(),
+            SidebarSelection::SavedSearch(_, _) => self.saved_search_list.read().get_next_item(),
         };
         self.select_item(select_next)
     }
 
     pub fn select_prev_item(&self) -> Result<(), SidebarError> {
         let select_next = match *self.selection.read() {
-            SidebarSelection::All => SidebarIterateItem::TagListSelectLastItem,
+            SidebarSelection::All => SidebarIterateItem::SavedSearchListSelectLastItem,
+            SidebarSelection::Podcasts => SidebarIterateItem::SelectAll,
             SidebarSelection::Category(_, _) | SidebarSelection::Feed(_, _, _) => {
                 self.feed_list.read().select_prev_item()
             }
             SidebarSelection::Tag(_, _) => self.tag_list.read().get_prev_item(),
+            SidebarSelection::SavedSearch(_, _) => self.saved_search_list.read().get_prev_item(),
         };
         self.select_item(select_next)
     }
@@ -440,8 +551,18 @@ impl SideBar {
 
         match selection {
             SidebarIterateItem::SelectAll => {
-                Self::select_all_button(
+                Self::select_fixed_button(
                     &self.all_event_box,
+                    SidebarSelection::All,
+                    &self.sender,
+                    &self.selection,
+                    &self.delayed_all_selection,
+                );
+            }
+            SidebarIterateItem::SelectPodcasts => {
+                Self::select_fixed_button(
+                    &self.podcasts_event_box,
+                    SidebarSelection::Podcasts,
                     &self.sender,
                     &self.selection,
                     &self.delayed_all_selection,
@@ -494,9 +615,9 @@ impl SideBar {
                     .context(SidebarErrorKind::Selection)?;
             }
             SidebarIterateItem::TagListSelectFirstItem => {
-                // if tags not supported or not available jump back to "All Articles"
+                // if tags not supported or not available continue with the saved searches
                 if !self.tags_box.is_visible() {
-                    return self.select_item(SidebarIterateItem::SelectAll);
+                    return self.select_item(SidebarIterateItem::SavedSearchListSelectFirstItem);
                 }
                 Self::expand_list(true, &self.tags_revealer, &self.tags_expander, &self.expanded_tags);
                 if let Some(item) = self.tag_list.read().get_first_item() {
@@ -519,6 +640,39 @@ impl SideBar {
                         .context(SidebarErrorKind::Selection)?;
                 }
             }
+            SidebarIterateItem::SelectSavedSearch(id) => {
+                self.saved_search_list.read().set_selection(id)?;
+            }
+            SidebarIterateItem::SavedSearchListSelectFirstItem => {
+                // without saved searches jump back to "All Articles"
+                if !self.saved_searches_box.is_visible() {
+                    return self.select_item(SidebarIterateItem::SelectAll);
+                }
+                Self::expand_list(
+                    true,
+                    &self.saved_searches_revealer,
+                    &self.saved_searches_expander,
+                    &self.expanded_saved_searches,
+                );
+                if let Some(id) = self.saved_search_list.read().get_first_item() {
+                    self.saved_search_list.read().set_selection(id)?;
+                }
+            }
+            SidebarIterateItem::SavedSearchListSelectLastItem => {
+                // without saved searches continue with the tags
+                if !self.saved_searches_box.is_visible() {
+                    return self.select_item(SidebarIterateItem::TagListSelectLastItem);
+                }
+                Self::expand_list(
+                    true,
+                    &self.saved_searches_revealer,
+                    &self.saved_searches_expander,
+                    &self.expanded_saved_searches,
+                );
+                if let Some(id) = self.saved_search_list.read().get_last_item() {
+                    self.saved_search_list.read().set_selection(id)?;
+                }
+            }
             SidebarIterateItem::NothingSelected => { /* nothing */ }
         }
         Ok(())
@@ -529,11 +683,17 @@ impl SideBar {
     }
 
     fn deselect(&self) {
-        Self::deselect_all_button(&self.all_event_box, &self.delayed_all_selection);
+        Self::deselect_fixed_buttons(
+            &self.all_event_box,
+            &self.podcasts_event_box,
+            &self.delayed_all_selection,
+        );
         self.feed_list.read().cancel_selection();
         self.feed_list.read().widget().unselect_all();
         self.tag_list.read().cancel_selection();
         self.tag_list.read().widget().unselect_all();
+        self.saved_search_list.read().cancel_selection();
+        self.saved_search_list.read().widget().unselect_all();
     }
 
     pub fn expand_collapse_selected_category(&self) {
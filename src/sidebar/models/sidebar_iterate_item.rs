@@ -4,12 +4,16 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SidebarIterateItem {
     SelectAll,
+    SelectPodcasts,
     FeedListSelectFirstItem,
     FeedListSelectLastItem,
     TagListSelectFirstItem,
     TagListSelectLastItem,
+    SavedSearchListSelectFirstItem,
+    SavedSearchListSelectLastItem,
     SelectFeedListCategory(CategoryID),
     SelectFeedListFeed(FeedID, CategoryID),
     SelectTagList(TagID),
+    SelectSavedSearch(u32),
     NothingSelected,
 }
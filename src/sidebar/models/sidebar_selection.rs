@@ -5,9 +5,11 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SidebarSelection {
     All,
+    Podcasts,
     Category(CategoryID, String),
     Feed(FeedID, CategoryID, String),
     Tag(TagID, String),
+    SavedSearch(u32, String),
 }
 
 impl SidebarSelection {
@@ -17,6 +19,18 @@ impl SidebarSelection {
             FeedListItemID::Category(id) => SidebarSelection::Category(id, title),
         }
     }
+
+    /// Stable identifier of the selection that per-view settings can be stored under.
+    pub fn view_key(&self) -> String {
+        match self {
+            SidebarSelection::All => "all".to_owned(),
+            SidebarSelection::Podcasts => "podcasts".to_owned(),
+            SidebarSelection::Category(id, _title) => format!("category:{}", id),
+            SidebarSelection::Feed(id, _parent_id, _title) => format!("feed:{}", id),
+            SidebarSelection::Tag(id, _title) => format!("tag:{}", id),
+            SidebarSelection::SavedSearch(id, _title) => format!("search:{}", id),
+        }
+    }
 }
 
 impl PartialEq for SidebarSelection {
@@ -26,6 +40,10 @@ impl PartialEq for SidebarSelection {
                 SidebarSelection::All => true,
                 _ => false,
             },
+            SidebarSelection::Podcasts => match other {
+                SidebarSelection::Podcasts => true,
+                _ => false,
+            },
             SidebarSelection::Category(self_id, _title) => match other {
                 SidebarSelection::Category(other_id, _title) => self_id == other_id,
                 _ => false,
@@ -40,6 +58,10 @@ impl PartialEq for SidebarSelection {
                 SidebarSelection::Tag(other_id, _title) => self_id == other_id,
                 _ => false,
             },
+            SidebarSelection::SavedSearch(self_id, _title) => match other {
+                SidebarSelection::SavedSearch(other_id, _title) => self_id == other_id,
+                _ => false,
+            },
         }
     }
 }
@@ -0,0 +1,216 @@
+mod saved_search_row;
+
+use crate::app::Action;
+use crate::main_window_state::MainWindowState;
+use crate::settings::SavedSearch;
+use crate::sidebar::error::{SidebarError, SidebarErrorKind};
+use crate::sidebar::{SidebarIterateItem, SidebarSelection};
+use crate::util::{BuilderHelper, GtkUtil, Util};
+use gio::{ActionMapExt, SimpleAction, SimpleActionGroup};
+use glib::{clone, source::Continue, translate::ToGlib, Sender, VariantTy};
+use gtk::{ContainerExt, ListBox, ListBoxExt, ListBoxRowExt, SelectionMode, WidgetExt};
+use parking_lot::RwLock;
+use saved_search_row::SavedSearchRow;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct SavedSearchList {
+    list: ListBox,
+    rows: Arc<RwLock<Vec<SavedSearchRow>>>,
+    state: Arc<RwLock<MainWindowState>>,
+    delayed_selection: Arc<RwLock<Option<u32>>>,
+}
+
+impl SavedSearchList {
+    pub fn new(state: &Arc<RwLock<MainWindowState>>, sender: &Sender<Action>) -> Self {
+        let builder = BuilderHelper::new("sidebar_list");
+        let list_box = builder.get::<ListBox>("sidebar_list");
+
+        // set selection mode from NONE -> SINGLE after a delay after it's been shown
+        // this ensures selection mode is in SINGLE without having a selected row in the list
+        list_box.connect_show(|list| {
+            gtk::timeout_add(
+                50,
+                clone!(@weak list => @default-panic, move || {
+                    list.set_selection_mode(SelectionMode::Single);
+                    Continue(false)
+                }),
+            );
+        });
+        Self::setup_row_actions(&list_box, sender);
+
+        SavedSearchList {
+            list: list_box,
+            rows: Arc::new(RwLock::new(Vec::new())),
+            state: state.clone(),
+            delayed_selection: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn setup_row_actions(list: &ListBox, sender: &Sender<Action>) {
+        let id_type = VariantTy::new("u").expect("Invalid variant type");
+        let action_group = SimpleActionGroup::new();
+
+        let row_actions: [(&str, fn(u32) -> Action); 4] = [
+            ("rename", Action::RenameSavedSearchDialog),
+            ("move-up", |id| Action::MoveSavedSearch(id, -1)),
+            ("move-down", |id| Action::MoveSavedSearch(id, 1)),
+            ("delete", Action::DeleteSavedSearch),
+        ];
+        for (name, action) in &row_actions {
+            let action = *action;
+            let simple_action = SimpleAction::new(name, Some(id_type));
+            simple_action.connect_activate(clone!(@strong sender => @default-panic, move |_action, parameter| {
+                if let Some(id) = parameter.and_then(|parameter| parameter.get::<u32>()) {
+                    Util::send(&sender, action(id));
+                }
+            }));
+            action_group.add_action(&simple_action);
+        }
+
+        list.insert_action_group("saved-search", Some(&action_group));
+    }
+
+    pub fn widget(&self) -> ListBox {
+        self.list.clone()
+    }
+
+    pub fn on_window_hidden(&self) {
+        self.list.set_selection_mode(SelectionMode::None);
+    }
+
+    pub fn on_window_show(&self) {
+        gtk::timeout_add(
+            50,
+            clone!(
+                @weak self.list as list,
+                @weak self.state as state,
+                @weak self.rows as rows => @default-panic, move ||
+            {
+                list.set_selection_mode(SelectionMode::Single);
+                if let SidebarSelection::SavedSearch(id, _label) = state.read().get_sidebar_selection() {
+                    if let Some(row) = rows.read().iter().find(|row| &row.id == id) {
+                        list.select_row(Some(&row.widget()));
+                    }
+                }
+                Continue(false)
+            }),
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.read().is_empty()
+    }
+
+    pub fn update(&self, saved_searches: Vec<(SavedSearch, i64)>) {
+        let unchanged = {
+            let rows = self.rows.read();
+            rows.len() == saved_searches.len()
+                && rows
+                    .iter()
+                    .zip(&saved_searches)
+                    .all(|(row, (search, _count))| row.id == search.id && row.label == search.label)
+        };
+
+        if unchanged {
+            for (row, (_search, count)) in self.rows.read().iter().zip(&saved_searches) {
+                row.update_item_count(*count);
+            }
+            return;
+        }
+
+        // searches were added, removed, renamed or reordered: rebuild the list and restore the selection
+        for row in self.rows.write().drain(..) {
+            self.list.remove(&row.widget());
+        }
+        for (search, count) in &saved_searches {
+            let row = SavedSearchRow::new(search.id, &search.label, *count);
+            self.list.insert(&row.widget(), -1);
+            self.rows.write().push(row);
+        }
+        self.list.show_all();
+
+        if let SidebarSelection::SavedSearch(id, _label) = self.state.read().get_sidebar_selection() {
+            if let Some(row) = self.rows.read().iter().find(|row| &row.id == id) {
+                self.list.select_row(Some(&row.widget()));
+            }
+        }
+    }
+
+    pub fn deselect(&self) {
+        self.list.unselect_all();
+    }
+
+    pub fn get_selection(&self) -> Option<(u32, String)> {
+        let row = self.list.get_selected_row()?;
+        self.rows
+            .read()
+            .get(row.get_index() as usize)
+            .map(|row| (row.id, row.label.clone()))
+    }
+
+    pub fn get_next_item(&self) -> SidebarIterateItem {
+        self.iterate(1, SidebarIterateItem::SelectAll)
+    }
+
+    pub fn get_prev_item(&self) -> SidebarIterateItem {
+        self.iterate(-1, SidebarIterateItem::TagListSelectLastItem)
+    }
+
+    fn iterate(&self, offset: i32, fallback: SidebarIterateItem) -> SidebarIterateItem {
+        if let Some(row) = self.list.get_selected_row() {
+            let index = row.get_index() + offset;
+            if index < 0 {
+                return fallback;
+            }
+            return match self.rows.read().get(index as usize) {
+                Some(row) => SidebarIterateItem::SelectSavedSearch(row.id),
+                None => fallback,
+            };
+        }
+        SidebarIterateItem::NothingSelected
+    }
+
+    pub fn get_first_item(&self) -> Option<u32> {
+        self.rows.read().first().map(|row| row.id)
+    }
+
+    pub fn get_last_item(&self) -> Option<u32> {
+        self.rows.read().last().map(|row| row.id)
+    }
+
+    pub fn set_selection(&self, id: u32) -> Result<(), SidebarError> {
+        self.cancel_selection();
+
+        let row = match self.rows.read().iter().find(|row| row.id == id) {
+            Some(row) => row.widget(),
+            None => return Err(SidebarErrorKind::Selection.into()),
+        };
+        gtk::idle_add(clone!(
+            @weak self.delayed_selection as delayed_selection,
+            @weak self.list as list => @default-panic, move ||
+        {
+            list.select_row(Some(&row));
+
+            let active_row = row.clone();
+            let source_id = delayed_selection.clone();
+            *delayed_selection.write() = Some(
+                gtk::timeout_add(300, move || {
+                    active_row.emit_activate();
+                    *source_id.write() = None;
+                    Continue(false)
+                })
+                .to_glib(),
+            );
+
+            row.emit_activate();
+            Continue(false)
+        }));
+        Ok(())
+    }
+
+    pub fn cancel_selection(&self) {
+        GtkUtil::remove_source(*self.delayed_selection.read());
+        *self.delayed_selection.write() = None;
+    }
+}
@@ -0,0 +1,86 @@
+use crate::i18n::i18n;
+use crate::util::BuilderHelper;
+use gdk::{EventType, Rectangle};
+use gio::{Menu, MenuItem};
+use glib::ToVariant;
+use gtk::{
+    ContainerExt, EventBox, Inhibit, Label, LabelExt, ListBoxRow, ListBoxRowExt, Popover, PopoverExt, StyleContextExt,
+    WidgetExt,
+};
+
+#[derive(Clone, Debug)]
+pub struct SavedSearchRow {
+    pub id: u32,
+    pub label: String,
+    widget: ListBoxRow,
+    title: Label,
+    item_count: Label,
+}
+
+impl SavedSearchRow {
+    pub fn new(id: u32, label: &str, count: i64) -> Self {
+        let builder = BuilderHelper::new("saved_search");
+        let eventbox = builder.get::<EventBox>("saved_search_row");
+        let title = builder.get::<Label>("saved_search_title");
+        let item_count = builder.get::<Label>("item_count");
+
+        let row = ListBoxRow::new();
+        row.set_activatable(true);
+        row.set_can_focus(false);
+        row.add(&eventbox);
+        row.get_style_context().remove_class("activatable");
+        Self::setup_context_menu(&eventbox, id);
+
+        let saved_search = SavedSearchRow {
+            id,
+            label: label.to_owned(),
+            widget: row,
+            title,
+            item_count,
+        };
+        saved_search.title.set_label(label);
+        saved_search.update_item_count(count);
+        saved_search
+    }
+
+    fn setup_context_menu(eventbox: &EventBox, id: u32) {
+        eventbox.connect_button_press_event(move |eventbox, event| {
+            if event.get_button() != 3 || event.get_event_type() != EventType::ButtonPress {
+                return Inhibit(false);
+            }
+
+            let id = id.to_variant();
+            let model = Menu::new();
+            for (label, action) in &[
+                (i18n("Rename"), "saved-search.rename"),
+                (i18n("Move Up"), "saved-search.move-up"),
+                (i18n("Move Down"), "saved-search.move-down"),
+                (i18n("Delete"), "saved-search.delete"),
+            ] {
+                let item = MenuItem::new(Some(label), None);
+                item.set_action_and_target_value(Some(action), Some(&id));
+                model.append_item(&item);
+            }
+
+            let (x, y) = event.get_position();
+            let popover = Popover::from_model(Some(eventbox), &model);
+            popover.set_pointing_to(&Rectangle {
+                x: x as i32,
+                y: y as i32,
+                width: 1,
+                height: 1,
+            });
+            popover.popup();
+            Inhibit(true)
+        });
+    }
+
+    pub fn widget(&self) -> ListBoxRow {
+        self.widget.clone()
+    }
+
+    pub fn update_item_count(&self, count: i64) {
+        self.item_count.set_label(&count.to_string());
+        self.item_count.set_visible(count > 0);
+    }
+}
@@ -113,7 +113,7 @@ impl TagListModel {
 
     pub fn calculate_next_item(&self, selected_index: i32) -> SidebarIterateItem {
         match self.calculate_selection(selected_index + 1) {
-            None => SidebarIterateItem::SelectAll,
+            None => SidebarIterateItem::SavedSearchListSelectFirstItem,
             Some((_index, model)) => SidebarIterateItem::SelectTagList(model.id.clone()),
         }
     }
@@ -1,7 +1,7 @@
 mod models;
 
 use crate::app::Action;
-use crate::i18n::i18n_f;
+use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::util::{BuilderHelper, GtkUtil, Util};
 use glib::{clone, source::Continue, translate::ToGlib, Sender};
 use gtk::{Button, ButtonExt, InfoBar, InfoBarExt, Label, LabelExt, ResponseType, WidgetExt};
@@ -12,13 +12,15 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 static ACTION_DELAY: u32 = 10000;
+static MAX_HISTORY: usize = 20;
 
 #[derive(Clone, Debug)]
 pub struct UndoBar {
     widget: InfoBar,
     label: Label,
     button: Button,
-    current_action: Arc<RwLock<Option<UndoAction>>>,
+    history: Arc<RwLock<Vec<UndoAction>>>,
+    hide_timeout: Arc<RwLock<Option<u32>>>,
     processing_actions: Arc<RwLock<HashSet<UndoActionModel>>>,
     sender: Sender<Action>,
 }
@@ -29,7 +31,8 @@ impl UndoBar {
             widget: builder.get::<InfoBar>("undo_bar"),
             label: builder.get::<Label>("undo_label"),
             button: builder.get::<Button>("undo_button"),
-            current_action: Arc::new(RwLock::new(None)),
+            history: Arc::new(RwLock::new(Vec::new())),
+            hide_timeout: Arc::new(RwLock::new(None)),
             processing_actions: Arc::new(RwLock::new(HashSet::new())),
             sender,
         };
@@ -39,35 +42,21 @@ impl UndoBar {
     }
 
     fn init(&self) {
-        self.button.connect_clicked(clone!(
-            @weak self.widget as info_bar,
-            @weak self.current_action as current_action,
-            @strong self.sender as sender => @default-panic, move |_button|
-        {
-            if let Some(current_action) = current_action.read().as_ref() {
-                GtkUtil::remove_source(Some(current_action.get_timeout()));
-            }
-            current_action.write().take();
-            info_bar.set_revealed(false);
-
-            // update lists
-            Util::send(&sender, Action::UpdateSidebar);
-            Util::send(&sender, Action::UpdateArticleList);
-        }));
+        self.button
+            .connect_clicked(clone!(@strong self.sender as sender => @default-panic, move |_button| {
+                Util::send(&sender, Action::Undo);
+            }));
 
         self.widget.connect_response(clone!(
-            @weak self.current_action as current_action,
+            @weak self.history as history,
+            @weak self.hide_timeout as hide_timeout,
             @weak self.processing_actions as processing_actions,
             @strong self.sender as sender => @default-panic, move |info_bar, response|
         {
             if response == ResponseType::Close {
-                if let Some(current_action) = current_action.read().as_ref() {
-                    Self::execute_action(&current_action.get_model(), &sender, &processing_actions);
-                    GtkUtil::remove_source(Some(current_action.get_timeout()));
-                }
-
-                current_action.write().take();
-                info_bar.set_revealed(false);
+                Self::execute_deferred_actions(&history, &sender, &processing_actions);
+                GtkUtil::remove_source(hide_timeout.write().take());
+                Self::hide(info_bar, &history);
             }
         }));
 
@@ -80,73 +69,212 @@ impl UndoBar {
         processing_actions: &Arc<RwLock<HashSet<UndoActionModel>>>,
     ) {
         let sender = sender.clone();
-        processing_actions.write().insert(action.clone());
         match action {
             UndoActionModel::DeleteFeed(feed_id, _label) => {
+                processing_actions.write().insert(action.clone());
                 Util::send(&sender, Action::DeleteFeed(feed_id.clone()));
             }
             UndoActionModel::DeleteCategory(category_id, _label) => {
+                processing_actions.write().insert(action.clone());
                 Util::send(&sender, Action::DeleteCategory(category_id.clone()));
             }
             UndoActionModel::DeleteTag(tag_id, _label) => {
+                processing_actions.write().insert(action.clone());
                 Util::send(&sender, Action::DeleteTag(tag_id.clone()));
             }
+            UndoActionModel::DeleteSavedSearch(id, _label) => {
+                Util::send(&sender, Action::RemoveSavedSearch(*id));
+            }
+            UndoActionModel::MarkReadOnScroll(article_ids) => {
+                Util::send(&sender, Action::MarkArticlesRead(article_ids.clone()));
+            }
+            // already executed
+            _ => {}
         }
     }
 
-    pub fn add_action(&self, action: UndoActionModel) {
-        if let Some(current_action) = self.current_action.read().as_ref() {
-            debug!("remove current action: {}", current_action.get_model());
-            GtkUtil::remove_source(Some(current_action.get_timeout()));
-            Self::execute_action(current_action.get_model(), &self.sender, &self.processing_actions);
+    fn execute_deferred_actions(
+        history: &Arc<RwLock<Vec<UndoAction>>>,
+        sender: &Sender<Action>,
+        processing_actions: &Arc<RwLock<HashSet<UndoActionModel>>>,
+    ) {
+        history.write().retain(|action| match action.get_timeout() {
+            Some(timeout) => {
+                GtkUtil::remove_source(Some(timeout));
+                Self::execute_action(action.get_model(), sender, processing_actions);
+                false
+            }
+            None => true,
+        });
+    }
+
+    fn action_label(action: &UndoActionModel) -> String {
+        match action {
+            UndoActionModel::DeleteCategory(_id, label) => i18n_f("Deleted Category '{}'", &[label]),
+            UndoActionModel::DeleteFeed(_id, label) => i18n_f("Deleted Feed '{}'", &[label]),
+            UndoActionModel::DeleteTag(_id, label) => i18n_f("Deleted Tag '{}'", &[label]),
+            UndoActionModel::DeleteSavedSearch(_id, label) => i18n_f("Deleted Search '{}'", &[label]),
+            UndoActionModel::MarkReadOnScroll(ids) | UndoActionModel::SetRead(ids) => ni18n_f(
+                "Marked {} Article as Read",
+                "Marked {} Articles as Read",
+                ids.len() as u32,
+                &[&ids.len().to_string()],
+            ),
+            UndoActionModel::SetUnread(ids) => ni18n_f(
+                "Marked {} Article as Unread",
+                "Marked {} Articles as Unread",
+                ids.len() as u32,
+                &[&ids.len().to_string()],
+            ),
+            UndoActionModel::SetMarked(ids) => ni18n_f(
+                "Starred {} Article",
+                "Starred {} Articles",
+                ids.len() as u32,
+                &[&ids.len().to_string()],
+            ),
+            UndoActionModel::SetUnmarked(ids) => ni18n_f(
+                "Unstarred {} Article",
+                "Unstarred {} Articles",
+                ids.len() as u32,
+                &[&ids.len().to_string()],
+            ),
+            UndoActionModel::MoveFeed(_id, _from, _to) => i18n("Moved Feed"),
+            UndoActionModel::MoveCategory(_id, _from, _to) => i18n("Moved Category"),
+            UndoActionModel::RenameFeed(_id, _from, to) => i18n_f("Renamed Feed to '{}'", &[to]),
+            UndoActionModel::RenameCategory(_id, _from, to) => i18n_f("Renamed Category to '{}'", &[to]),
+            UndoActionModel::TagArticles(ids, _tag_id) => ni18n_f(
+                "Tagged {} Article",
+                "Tagged {} Articles",
+                ids.len() as u32,
+                &[&ids.len().to_string()],
+            ),
+            UndoActionModel::UntagArticles(ids, _tag_id) => ni18n_f(
+                "Removed Tag from {} Article",
+                "Removed Tag from {} Articles",
+                ids.len() as u32,
+                &[&ids.len().to_string()],
+            ),
         }
+    }
+
+    /// Show the most recent action of the history, or hide the bar if there is nothing left to undo.
+    fn show_latest(
+        widget: &InfoBar,
+        label: &Label,
+        history: &Arc<RwLock<Vec<UndoAction>>>,
+        hide_timeout: &Arc<RwLock<Option<u32>>>,
+    ) {
+        GtkUtil::remove_source(hide_timeout.write().take());
 
-        match &action {
-            UndoActionModel::DeleteCategory(_id, label) => {
-                self.label.set_label(&i18n_f("Deleted Category '{}'", &[label]))
+        let latest = history.read().last().map(|action| action.get_model().clone());
+        match latest {
+            Some(action) => {
+                label.set_label(&Self::action_label(&action));
+                widget.set_revealed(true);
+
+                let source_id = gtk::timeout_add(
+                    ACTION_DELAY,
+                    clone!(
+                        @strong widget,
+                        @strong history,
+                        @strong hide_timeout => @default-panic, move ||
+                    {
+                        hide_timeout.write().take();
+                        Self::hide(&widget, &history);
+                        Continue(false)
+                    }),
+                );
+                hide_timeout.write().replace(source_id.to_glib());
+            }
+            None => Self::hide(widget, history),
+        }
+    }
+
+    /// Hide the bar and forget the actions that already ran, they can't be undone anymore.
+    /// Deferred actions stay until their own timeout executes them.
+    fn hide(widget: &InfoBar, history: &Arc<RwLock<Vec<UndoAction>>>) {
+        history.write().retain(|action| action.get_timeout().is_some());
+        widget.set_revealed(false);
+    }
+
+    pub fn add_action(&self, action: UndoActionModel) {
+        let deferred = action.is_deferred();
+        let timeout = if deferred {
+            let source_id = gtk::timeout_add(
+                ACTION_DELAY,
+                clone!(
+                    @strong action,
+                    @weak self.widget as widget,
+                    @weak self.label as label,
+                    @weak self.history as history,
+                    @weak self.hide_timeout as hide_timeout,
+                    @weak self.processing_actions as processing_actions,
+                    @strong self.sender as sender => @default-panic, move ||
+                {
+                    Self::execute_action(&action, &sender, &processing_actions);
+                    history.write().retain(|entry| entry.get_model() != &action);
+                    if history.read().is_empty() {
+                        GtkUtil::remove_source(hide_timeout.write().take());
+                        Self::hide(&widget, &history);
+                    } else {
+                        label.set_label(&Self::action_label(history.read().last().expect("history is not empty").get_model()));
+                    }
+                    Continue(false)
+                }),
+            );
+            Some(source_id.to_glib())
+        } else {
+            None
+        };
+
+        self.history.write().push(UndoAction::new(action, timeout));
+        if self.history.read().len() > MAX_HISTORY {
+            let oldest = self.history.write().remove(0);
+            debug!("drop oldest undo action: {}", oldest.get_model());
+            if let Some(timeout) = oldest.get_timeout() {
+                GtkUtil::remove_source(Some(timeout));
+                Self::execute_action(oldest.get_model(), &self.sender, &self.processing_actions);
             }
-            UndoActionModel::DeleteFeed(_id, label) => self.label.set_label(&i18n_f("Deleted Feed '{}'", &[label])),
-            UndoActionModel::DeleteTag(_id, label) => self.label.set_label(&i18n_f("Deleted Tag '{}'", &[label])),
         }
 
-        self.widget.set_revealed(true);
-
-        let source_id = gtk::timeout_add(
-            ACTION_DELAY,
-            clone!(
-                @strong action,
-                @weak self.widget as widget,
-                @weak self.current_action as current_action,
-                @weak self.processing_actions as processing_actions,
-                @strong self.sender as sender => @default-panic, move ||
-            {
-                Self::execute_action(&action, &sender, &processing_actions);
-                widget.set_revealed(false);
-                current_action.write().take();
-                Continue(false)
-            }),
-        );
-
-        self.current_action
-            .write()
-            .replace(UndoAction::new(action, source_id.to_glib()));
-
-        // update lists
-        Util::send(&self.sender, Action::UpdateSidebar);
-        Util::send(&self.sender, Action::UpdateArticleList);
+        Self::show_latest(&self.widget, &self.label, &self.history, &self.hide_timeout);
+
+        if deferred {
+            // update lists
+            Util::send(&self.sender, Action::UpdateSidebar);
+            Util::send(&self.sender, Action::UpdateArticleList);
+        }
     }
 
-    pub fn get_current_action(&self) -> Option<UndoActionModel> {
-        if let Some(current_action) = self.current_action.read().as_ref() {
-            return Some(current_action.get_model().clone());
+    /// Take the most recent action off the history. Deferred actions are dropped without being executed.
+    /// Already executed actions are returned so the caller can revert them.
+    /// Nothing can be undone once the bar is hidden again.
+    pub fn pop_action(&self) -> Option<UndoActionModel> {
+        if !self.widget.get_revealed() {
+            return None;
+        }
+        let action = self.history.write().pop()?;
+        debug!("undo action: {}", action.get_model());
+        if let Some(timeout) = action.get_timeout() {
+            GtkUtil::remove_source(Some(timeout));
         }
-        None
+        Self::show_latest(&self.widget, &self.label, &self.history, &self.hide_timeout);
+
+        Some(action.get_model().clone())
+    }
+
+    /// All actions that are waiting for the undo bar to time out.
+    pub fn get_pending_actions(&self) -> Vec<UndoActionModel> {
+        self.history
+            .read()
+            .iter()
+            .filter(|action| action.get_timeout().is_some())
+            .map(|action| action.get_model().clone())
+            .collect()
     }
 
     pub fn execute_pending_action(&self) {
-        if let Some(current_action) = self.get_current_action() {
-            Self::execute_action(&current_action, &self.sender, &self.processing_actions);
-        }
+        Self::execute_deferred_actions(&self.history, &self.sender, &self.processing_actions);
     }
 
     pub fn processing_actions(&self) -> Arc<RwLock<HashSet<UndoActionModel>>> {
@@ -1,4 +1,4 @@
-use news_flash::models::{CategoryID, FeedID, TagID};
+use news_flash::models::{ArticleID, CategoryID, FeedID, TagID};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -7,6 +7,38 @@ pub enum UndoActionModel {
     DeleteFeed(FeedID, String),
     DeleteCategory(CategoryID, String),
     DeleteTag(TagID, String),
+    /// saved search, label
+    DeleteSavedSearch(u32, String),
+    MarkReadOnScroll(Vec<ArticleID>),
+    SetRead(Vec<ArticleID>),
+    SetUnread(Vec<ArticleID>),
+    SetMarked(Vec<ArticleID>),
+    SetUnmarked(Vec<ArticleID>),
+    /// feed, previous category, new category
+    MoveFeed(FeedID, CategoryID, CategoryID),
+    /// category, previous parent, new parent
+    MoveCategory(CategoryID, CategoryID, CategoryID),
+    /// feed, previous title, new title
+    RenameFeed(FeedID, String, String),
+    /// category, previous title, new title
+    RenameCategory(CategoryID, String, String),
+    TagArticles(Vec<ArticleID>, TagID),
+    UntagArticles(Vec<ArticleID>, TagID),
+}
+
+impl UndoActionModel {
+    /// Deferred actions are only executed once the undo bar times out, so undoing them just means dropping them.
+    /// All other actions are executed right away and get undone by applying the inverse action.
+    pub fn is_deferred(&self) -> bool {
+        matches!(
+            self,
+            UndoActionModel::DeleteFeed(_, _)
+                | UndoActionModel::DeleteCategory(_, _)
+                | UndoActionModel::DeleteTag(_, _)
+                | UndoActionModel::DeleteSavedSearch(_, _)
+                | UndoActionModel::MarkReadOnScroll(_)
+        )
+    }
 }
 
 impl fmt::Display for UndoActionModel {
@@ -15,28 +47,81 @@ impl fmt::Display for UndoActionModel {
             UndoActionModel::DeleteFeed(id, label) => write!(f, "Delete Feed '{}' (id: {})", label, id),
             UndoActionModel::DeleteCategory(id, label) => write!(f, "Delete Category '{}' (id: {})", label, id),
             UndoActionModel::DeleteTag(id, label) => write!(f, "Delete Tag '{}' (id: {})", label, id),
+            UndoActionModel::DeleteSavedSearch(id, label) => {
+                write!(f, "Delete Saved Search '{}' (id: {})", label, id)
+            }
+            UndoActionModel::MarkReadOnScroll(ids) => write!(f, "Mark {} Articles Read on Scroll", ids.len()),
+            UndoActionModel::SetRead(ids) => write!(f, "Mark {} Articles Read", ids.len()),
+            UndoActionModel::SetUnread(ids) => write!(f, "Mark {} Articles Unread", ids.len()),
+            UndoActionModel::SetMarked(ids) => write!(f, "Star {} Articles", ids.len()),
+            UndoActionModel::SetUnmarked(ids) => write!(f, "Unstar {} Articles", ids.len()),
+            UndoActionModel::MoveFeed(id, from, to) => {
+                write!(f, "Move Feed '{}' from '{}' to '{}'", id, from, to)
+            }
+            UndoActionModel::MoveCategory(id, from, to) => {
+                write!(f, "Move Category '{}' from '{}' to '{}'", id, from, to)
+            }
+            UndoActionModel::RenameFeed(id, from, to) => write!(f, "Rename Feed '{}' to '{}' (id: {})", from, to, id),
+            UndoActionModel::RenameCategory(id, from, to) => {
+                write!(f, "Rename Category '{}' to '{}' (id: {})", from, to, id)
+            }
+            UndoActionModel::TagArticles(ids, tag_id) => write!(f, "Tag {} Articles with '{}'", ids.len(), tag_id),
+            UndoActionModel::UntagArticles(ids, tag_id) => {
+                write!(f, "Untag {} Articles from '{}'", ids.len(), tag_id)
+            }
         }
     }
 }
 
 impl PartialEq for UndoActionModel {
     fn eq(&self, other: &Self) -> bool {
-        match self {
-            UndoActionModel::DeleteFeed(self_id, _self_title) => match other {
-                UndoActionModel::DeleteFeed(other_id, __other_title) => self_id == other_id,
-                UndoActionModel::DeleteCategory(_other_id, __other_title) => false,
-                UndoActionModel::DeleteTag(_other_id, __other_title) => false,
-            },
-            UndoActionModel::DeleteCategory(self_id, _title) => match other {
-                UndoActionModel::DeleteFeed(_other_id, __other_title) => false,
-                UndoActionModel::DeleteCategory(other_id, __other_title) => self_id == other_id,
-                UndoActionModel::DeleteTag(_other_id, __other_title) => false,
-            },
-            UndoActionModel::DeleteTag(self_id, _title) => match other {
-                UndoActionModel::DeleteFeed(_other_id, __other_title) => false,
-                UndoActionModel::DeleteCategory(_other_id, __other_title) => false,
-                UndoActionModel::DeleteTag(other_id, __other_title) => self_id == other_id,
-            },
+        match (self, other) {
+            (
+                UndoActionModel::DeleteFeed(self_id, _self_title),
+                UndoActionModel::DeleteFeed(other_id, _other_title),
+            ) => self_id == other_id,
+            (
+                UndoActionModel::DeleteCategory(self_id, _self_title),
+                UndoActionModel::DeleteCategory(other_id, _other_title),
+            ) => self_id == other_id,
+            (UndoActionModel::DeleteTag(self_id, _self_title), UndoActionModel::DeleteTag(other_id, _other_title)) => {
+                self_id == other_id
+            }
+            (
+                UndoActionModel::DeleteSavedSearch(self_id, _self_label),
+                UndoActionModel::DeleteSavedSearch(other_id, _other_label),
+            ) => self_id == other_id,
+            (UndoActionModel::MarkReadOnScroll(self_ids), UndoActionModel::MarkReadOnScroll(other_ids)) => {
+                self_ids == other_ids
+            }
+            (UndoActionModel::SetRead(self_ids), UndoActionModel::SetRead(other_ids)) => self_ids == other_ids,
+            (UndoActionModel::SetUnread(self_ids), UndoActionModel::SetUnread(other_ids)) => self_ids == other_ids,
+            (UndoActionModel::SetMarked(self_ids), UndoActionModel::SetMarked(other_ids)) => self_ids == other_ids,
+            (UndoActionModel::SetUnmarked(self_ids), UndoActionModel::SetUnmarked(other_ids)) => self_ids == other_ids,
+            (
+                UndoActionModel::MoveFeed(self_id, self_from, self_to),
+                UndoActionModel::MoveFeed(other_id, other_from, other_to),
+            ) => self_id == other_id && self_from == other_from && self_to == other_to,
+            (
+                UndoActionModel::MoveCategory(self_id, self_from, self_to),
+                UndoActionModel::MoveCategory(other_id, other_from, other_to),
+            ) => self_id == other_id && self_from == other_from && self_to == other_to,
+            (
+                UndoActionModel::RenameFeed(self_id, self_from, self_to),
+                UndoActionModel::RenameFeed(other_id, other_from, other_to),
+            ) => self_id == other_id && self_from == other_from && self_to == other_to,
+            (
+                UndoActionModel::RenameCategory(self_id, self_from, self_to),
+                UndoActionModel::RenameCategory(other_id, other_from, other_to),
+            ) => self_id == other_id && self_from == other_from && self_to == other_to,
+            (UndoActionModel::TagArticles(self_ids, self_tag), UndoActionModel::TagArticles(other_ids, other_tag)) => {
+                self_ids == other_ids && self_tag == other_tag
+            }
+            (
+                UndoActionModel::UntagArticles(self_ids, self_tag),
+                UndoActionModel::UntagArticles(other_ids, other_tag),
+            ) => self_ids == other_ids && self_tag == other_tag,
+            _ => false,
         }
     }
 }
@@ -44,11 +129,11 @@ impl PartialEq for UndoActionModel {
 #[derive(Clone, Debug)]
 pub struct UndoAction {
     action_model: UndoActionModel,
-    timeout: u32,
+    timeout: Option<u32>,
 }
 
 impl UndoAction {
-    pub fn new(action: UndoActionModel, timout: u32) -> Self {
+    pub fn new(action: UndoActionModel, timout: Option<u32>) -> Self {
         UndoAction {
             action_model: action,
             timeout: timout,
@@ -59,7 +144,7 @@ impl UndoAction {
         &self.action_model
     }
 
-    pub fn get_timeout(&self) -> u32 {
+    pub fn get_timeout(&self) -> Option<u32> {
         self.timeout
     }
 }
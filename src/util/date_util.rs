@@ -1,4 +1,5 @@
-use chrono::{Duration, Local, NaiveDateTime, TimeZone};
+use crate::i18n::i18n;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
 
 pub struct DateUtil;
 
@@ -18,4 +19,25 @@ impl DateUtil {
 
         format!("{} {}", date, local_datetime.format("%k:%M"))
     }
+
+    /// Name of the time span the date falls into, used to group the article list.
+    pub fn format_group(naive_utc: &NaiveDateTime) -> String {
+        let local_date = Self::local_date(naive_utc);
+        let now_date = Local::now().naive_local().date();
+        let age = now_date - local_date;
+
+        if age < Duration::days(1) {
+            i18n("Today")
+        } else if age < Duration::days(2) {
+            i18n("Yesterday")
+        } else if age < Duration::days(7) {
+            i18n("This Week")
+        } else {
+            i18n("Older")
+        }
+    }
+
+    pub fn local_date(naive_utc: &NaiveDateTime) -> NaiveDate {
+        Local.from_utc_datetime(naive_utc).naive_local().date()
+    }
 }
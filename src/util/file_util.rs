@@ -5,6 +5,8 @@ use std::fs::File;
 use std::io::{Read, Write};
 use std::path::PathBuf;
 
+const MAX_FILE_NAME_CHARS: usize = 100;
+
 pub struct FileUtil;
 
 impl FileUtil {
@@ -30,4 +32,24 @@ impl FileUtil {
         file.read_to_string(&mut contents).context(UtilErrorKind::ReadFile)?;
         Ok(contents)
     }
+
+    /// Turn `name`, e.g. an article title, into a file name that is valid on all common file systems.
+    /// Returns `None` if nothing usable is left.
+    pub fn file_name(name: &str) -> Option<String> {
+        let name: String = name
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                c if c.is_control() => ' ',
+                c => c,
+            })
+            .take(MAX_FILE_NAME_CHARS)
+            .collect();
+        let name = name.trim().trim_matches('.').trim();
+        if name.is_empty() {
+            None
+        } else {
+            Some(name.to_owned())
+        }
+    }
 }